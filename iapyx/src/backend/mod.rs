@@ -13,17 +13,31 @@ use chain_impl_mockchain::{
 };
 use chain_ser::deser::Deserialize;
 use jormungandr_lib::interfaces::AccountIdentifier;
-use jormungandr_lib::interfaces::{AccountState, FragmentLog, VotePlanStatus};
+use jormungandr_lib::interfaces::{AccountState, BlockDate, FragmentLog, VotePlanStatus};
 use jormungandr_testing_utils::testing::node::Explorer;
-pub use jormungandr_testing_utils::testing::node::RestSettings as WalletBackendSettings;
 use node::{RestError as NodeRestError, WalletNodeRestClient};
-pub use proxy::{Protocol, ProxyClient, ProxyClientError, ProxyServerError, ProxyServerStub};
+pub use proxy::{
+    forward_buffered, forward_streaming, forward_websocket, read_limited, status_response,
+    Block0Networks, BodyLimits, LimitedBody, Protocol, ProtocolParseError, ProxyClient,
+    ProxyClientError, ProxyRecorder, ProxyRecorderError, ProxyServerError, ProxyServerStub,
+    ProxyStats, ProxyStatsSnapshot, RecordedExchange, RecordedRequest, RecordedResponse,
+    StreamingPaths, UpstreamStats,
+};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::Certificate;
 use std::collections::HashMap;
+use std::path::Path;
 use std::str::FromStr;
 use thiserror::Error;
 use vit_station::{RestError as VitRestError, VitStationRestClient};
 use wallet::{AccountId, Settings};
 
+/// Backend settings are jormungandr `RestSettings`, shared with node rest client, so iapyx
+/// cannot add fields to them. Root certificate is therefore kept in its loaded form
+/// (`certificate`), while its path lives in configurations which build settings
+/// (e.g. `IapyxLoadConfig::root_cert_path`), see `load_root_certificate`
+pub use jormungandr_testing_utils::testing::node::RestSettings as WalletBackendSettings;
+
 pub struct WalletBackend {
     node_client: WalletNodeRestClient,
     vit_client: VitStationRestClient,
@@ -48,6 +62,11 @@ impl WalletBackend {
             explorer_client: Explorer::new(node_address),
         };
 
+        if let Some(certificate) = &node_rest_settings.certificate {
+            backend.vit_client.set_certificate(certificate.clone());
+            backend.proxy_client.set_certificate(certificate.clone());
+        }
+
         if node_rest_settings.enable_debug {
             backend.enable_logs();
         }
@@ -82,6 +101,64 @@ impl WalletBackend {
             .collect())
     }
 
+    /// Submits fragments using up to `parallelism` concurrent requests. Returned ids follow
+    /// input order, however order in which node accepts fragments is not guaranteed, so
+    /// fragments of the same account are still accepted only if their spending counters match
+    pub fn send_fragments_concurrently(
+        &self,
+        transactions: Vec<Vec<u8>>,
+        parallelism: usize,
+    ) -> Result<Vec<FragmentId>, WalletBackendError> {
+        self.try_send_fragments_concurrently(transactions, parallelism)
+            .into_iter()
+            .collect()
+    }
+
+    /// Same as `send_fragments_concurrently`, but does not stop on first failure and
+    /// reports result of each submission, in input order
+    pub fn try_send_fragments_concurrently(
+        &self,
+        transactions: Vec<Vec<u8>>,
+        parallelism: usize,
+    ) -> Vec<Result<FragmentId, WalletBackendError>> {
+        let parallelism = parallelism.max(1).min(transactions.len().max(1));
+        let mut chunks: Vec<Vec<(usize, Vec<u8>)>> = vec![Vec::new(); parallelism];
+        let count = transactions.len();
+        for (idx, tx) in transactions.into_iter().enumerate() {
+            chunks[idx % parallelism].push((idx, tx));
+        }
+
+        let handles: Vec<_> = chunks
+            .into_iter()
+            .map(|chunk| {
+                let client = self.node_client.clone();
+                std::thread::spawn(move || {
+                    chunk
+                        .into_iter()
+                        .map(|(idx, tx)| {
+                            let result = Fragment::deserialize(tx.as_slice())
+                                .map_err(WalletBackendError::from)
+                                .and_then(|fragment| {
+                                    client.send_fragment(tx)?;
+                                    Ok(fragment.id())
+                                });
+                            (idx, result)
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        let mut results: Vec<Option<Result<FragmentId, WalletBackendError>>> =
+            (0..count).map(|_| None).collect();
+        for handle in handles {
+            for (idx, result) in handle.join().expect("fragment sender thread panicked") {
+                results[idx] = Some(result);
+            }
+        }
+        results.into_iter().map(Option::unwrap).collect()
+    }
+
     pub fn send_fragments_at_once(
         &self,
         transactions: Vec<Vec<u8>>,
@@ -123,10 +200,51 @@ impl WalletBackend {
         Ok(self.proxy_client.block0().map(Into::into)?)
     }
 
+    pub fn request_funds(&self, address: &str, value: u64) -> Result<String, WalletBackendError> {
+        Ok(self.proxy_client.faucet(address, value)?)
+    }
+
     pub fn vote_plan_statuses(&self) -> Result<Vec<VotePlanStatus>, WalletBackendError> {
         self.node_client.vote_plan_statuses().map_err(Into::into)
     }
 
+    /// Date of last block known by node
+    pub fn last_block_date(&self) -> Result<BlockDate, WalletBackendError> {
+        let last_block_date = self
+            .node_client
+            .stats()?
+            .stats
+            .and_then(|stats| stats.last_block_date)
+            .ok_or(WalletBackendError::NodeNotBootstrapped)?;
+        BlockDate::from_str(&last_block_date)
+            .map_err(|_| WalletBackendError::InvalidBlockDate(last_block_date))
+    }
+
+    /// Custom headers (e.g. `User-Agent` or gateway routing headers) sent with every request
+    /// to vit station and proxy. Custom headers take precedence over headers set by clients
+    /// with the same name.
+    ///
+    /// Headers are NOT sent with node requests (fragments, account state, fragment logs,
+    /// node stats): those go through jormungandr `JormungandrRest` client, which does not
+    /// accept custom headers. Gateway which routes by header needs to route node paths
+    /// (`/api/v0/message`, `/api/v0/account`, ...) without it
+    pub fn set_headers(
+        &mut self,
+        headers: &HashMap<String, String>,
+    ) -> Result<(), WalletBackendError> {
+        let mut header_map = HeaderMap::new();
+        for (name, value) in headers {
+            let invalid = || WalletBackendError::InvalidHeader(name.clone());
+            header_map.insert(
+                HeaderName::from_bytes(name.as_bytes()).map_err(|_| invalid())?,
+                HeaderValue::from_str(value).map_err(|_| invalid())?,
+            );
+        }
+        self.vit_client.set_headers(header_map.clone());
+        self.proxy_client.set_headers(header_map);
+        Ok(())
+    }
+
     pub fn disable_logs(&mut self) {
         self.node_client.disable_logs();
         self.vit_client.disable_logs();
@@ -156,10 +274,14 @@ impl WalletBackend {
         unimplemented!()
     }
 
-    pub fn settings(&self) -> Result<Settings, WalletBackendError> {
+    pub fn block0_block(&self) -> Result<Block, WalletBackendError> {
         let block0 = self.block0()?;
         let mut block0_bytes = ReadBuf::from(&block0);
-        let block0 = Block::read(&mut block0_bytes).map_err(WalletBackendError::Block0ReadError)?;
+        Block::read(&mut block0_bytes).map_err(WalletBackendError::Block0ReadError)
+    }
+
+    pub fn settings(&self) -> Result<Settings, WalletBackendError> {
+        let block0 = self.block0_block()?;
         Settings::new(&block0).map_err(|e| WalletBackendError::SettingsReadError(Box::new(e)))
     }
 
@@ -168,6 +290,14 @@ impl WalletBackend {
     }
 }
 
+/// Loads PEM encoded root certificate, which can be put into `WalletBackendSettings::certificate`
+/// in order to trust self-signed certificate (for example one used by proxy `--cert` option).
+/// Settings have no `root_cert_path` field, since they are defined outside of iapyx
+pub fn load_root_certificate<P: AsRef<Path>>(path: P) -> Result<Certificate, WalletBackendError> {
+    let content = std::fs::read(path.as_ref())?;
+    Certificate::from_pem(&content).map_err(WalletBackendError::CertificateError)
+}
+
 #[derive(Debug, Error)]
 pub enum WalletBackendError {
     #[error("vit station error")]
@@ -180,6 +310,14 @@ pub enum WalletBackendError {
     IoError(#[from] std::io::Error),
     #[error("block0 retrieve error")]
     Block0ReadError(#[from] chain_core::mempack::ReadError),
+    #[error("cannot read root certificate")]
+    CertificateError(#[source] reqwest::Error),
+    #[error("node is not bootstrapped yet")]
+    NodeNotBootstrapped,
+    #[error("cannot parse block date: {0}")]
+    InvalidBlockDate(String),
+    #[error("invalid header: {0}")]
+    InvalidHeader(String),
     #[error("block0 retrieve error")]
     SettingsReadError(#[from] Box<chain_impl_mockchain::ledger::Error>),
 }
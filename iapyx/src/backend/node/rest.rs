@@ -11,6 +11,10 @@ use regex::Regex;
 use std::collections::HashMap;
 use std::str::FromStr;
 use wallet::AccountId;
+
+/// Node client built on jormungandr rest client. It does not support custom headers,
+/// so `WalletBackend::set_headers` does not apply to it
+#[derive(Clone)]
 pub struct WalletNodeRestClient {
     rest_client: JormungandrRest,
 }
@@ -0,0 +1,137 @@
+use super::streaming::upstream_uri;
+use futures::{Stream, StreamExt};
+use warp::http::header::{HeaderMap, HeaderName};
+use warp::http::{Method, Response, StatusCode};
+use warp::hyper::body::Bytes;
+use warp::hyper::{Body, Client, Request};
+use warp::path::FullPath;
+use warp::Buf;
+
+/// Headers describing single connection, which must not be forwarded by proxy
+const HOP_BY_HOP_HEADERS: [&str; 8] = [
+    "connection",
+    "keep-alive",
+    "proxy-authenticate",
+    "proxy-authorization",
+    "te",
+    "trailer",
+    "transfer-encoding",
+    "upgrade",
+];
+
+/// Body read into memory, unless it exceeded the limit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LimitedBody {
+    Complete(Bytes),
+    TooLarge,
+}
+
+/// Reads body chunk by chunk and stops as soon as it exceeds `max` bytes, so body sent
+/// without content-length (or lying about it) is never buffered over the limit
+pub async fn read_limited<S, B, E>(body: S, max: u64) -> Result<LimitedBody, E>
+where
+    S: Stream<Item = Result<B, E>>,
+    B: Buf,
+{
+    futures::pin_mut!(body);
+    let mut content = Vec::new();
+    while let Some(chunk) = body.next().await {
+        let mut chunk = chunk?;
+        if (content.len() + chunk.remaining()) as u64 > max {
+            return Ok(LimitedBody::TooLarge);
+        }
+        while chunk.has_remaining() {
+            let bytes = chunk.chunk();
+            let len = bytes.len();
+            content.extend_from_slice(bytes);
+            chunk.advance(len);
+        }
+    }
+    Ok(LimitedBody::Complete(content.into()))
+}
+
+/// Forwards request to upstream and reads whole response, which is replaced with 502
+/// when upstream is unreachable or its body exceeds `max_response_body`
+pub async fn forward_buffered(
+    upstream: String,
+    path: FullPath,
+    query: Option<String>,
+    method: Method,
+    mut headers: HeaderMap,
+    body: Bytes,
+    max_response_body: u64,
+) -> Response<Bytes> {
+    remove_hop_by_hop_headers(&mut headers);
+
+    let mut request = Request::builder()
+        .method(method)
+        .uri(upstream_uri("http", &upstream, &path, &query))
+        .body(Body::from(body))
+        .expect("valid request");
+    *request.headers_mut() = headers;
+
+    let response = match Client::new().request(request).await {
+        Ok(response) => response,
+        Err(err) => {
+            eprintln!("upstream error: {:?}", err);
+            return status_response(StatusCode::BAD_GATEWAY);
+        }
+    };
+
+    let (mut parts, body) = response.into_parts();
+    match read_limited(body, max_response_body).await {
+        Ok(LimitedBody::Complete(body)) => {
+            remove_hop_by_hop_headers(&mut parts.headers);
+            Response::from_parts(parts, body)
+        }
+        Ok(LimitedBody::TooLarge) => {
+            eprintln!("upstream response exceeds body limit");
+            status_response(StatusCode::BAD_GATEWAY)
+        }
+        Err(err) => {
+            eprintln!("upstream error: {:?}", err);
+            status_response(StatusCode::BAD_GATEWAY)
+        }
+    }
+}
+
+pub fn status_response(status: StatusCode) -> Response<Bytes> {
+    let mut response = Response::new(Bytes::new());
+    *response.status_mut() = status;
+    response
+}
+
+fn remove_hop_by_hop_headers(headers: &mut HeaderMap) {
+    for name in HOP_BY_HOP_HEADERS.iter() {
+        headers.remove(HeaderName::from_static(name));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunks(sizes: &[usize]) -> impl Stream<Item = Result<Bytes, ()>> {
+        let chunks: Vec<_> = sizes
+            .iter()
+            .map(|size| Ok(vec![0u8; *size].into()))
+            .collect();
+        futures::stream::iter(chunks)
+    }
+
+    #[tokio::test]
+    async fn reads_body_up_to_limit() {
+        assert_eq!(
+            read_limited(chunks(&[4, 4]), 8).await,
+            Ok(LimitedBody::Complete(vec![0u8; 8].into()))
+        );
+    }
+
+    #[tokio::test]
+    async fn stops_reading_body_over_limit() {
+        assert_eq!(
+            read_limited(chunks(&[4, 4, 1]), 8).await,
+            Ok(LimitedBody::TooLarge)
+        );
+    }
+}
@@ -1,8 +1,12 @@
 use hyper::StatusCode;
+use reqwest::header::HeaderMap;
+use reqwest::Certificate;
 use thiserror::Error;
 
 pub struct ProxyClient {
     address: String,
+    certificate: Option<Certificate>,
+    headers: HeaderMap,
     debug: bool,
 }
 
@@ -10,10 +14,21 @@ impl ProxyClient {
     pub fn new(address: String) -> Self {
         Self {
             address,
+            certificate: None,
+            headers: HeaderMap::new(),
             debug: false,
         }
     }
 
+    pub fn set_certificate(&mut self, certificate: Certificate) {
+        self.certificate = Some(certificate);
+    }
+
+    /// Headers sent with every request. They replace headers set by client (e.g. Content-Type)
+    pub fn set_headers(&mut self, headers: HeaderMap) {
+        self.headers = headers;
+    }
+
     pub fn enable_debug(&mut self) {
         self.debug = true;
     }
@@ -34,12 +49,45 @@ impl ProxyClient {
         }
     }
 
+    fn client(&self) -> Result<reqwest::blocking::Client, Error> {
+        let mut builder = reqwest::blocking::Client::builder().gzip(true);
+        if let Some(certificate) = &self.certificate {
+            builder = builder.add_root_certificate(certificate.clone());
+        }
+        Ok(builder.build()?)
+    }
+
     pub fn block0(&self) -> Result<Vec<u8>, Error> {
-        let response = reqwest::blocking::get(&self.path("api/v0/block0"))?;
+        let response = self
+            .client()?
+            .get(&self.path("api/v0/block0"))
+            .headers(self.headers.clone())
+            .send()?;
         self.print_response(&response);
         Ok(response.bytes()?.to_vec())
     }
 
+    /// Requests funds for given address. Returns faucet response (usually id of fragment
+    /// which transfers funds)
+    pub fn faucet(&self, address: &str, value: u64) -> Result<String, Error> {
+        let body = serde_json::json!({ "address": address, "value": value }).to_string();
+        let response = self
+            .client()?
+            .post(&self.path("api/v0/faucet"))
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body)
+            .headers(self.headers.clone())
+            .send()?;
+        self.print_response(&response);
+        match response.status() {
+            StatusCode::NOT_FOUND | StatusCode::METHOD_NOT_ALLOWED => {
+                Err(Error::FaucetNotAvailable(self.address.clone()))
+            }
+            status if !status.is_success() => Err(Error::ErrorStatusCode(status)),
+            _ => Ok(response.text()?),
+        }
+    }
+
     fn path(&self, path: &str) -> String {
         let path = format!("{}/{}", self.address, path);
         self.print_request_path(&path);
@@ -58,6 +106,8 @@ pub enum Error {
     RequestError(#[from] reqwest::Error),
     #[error("server is not up")]
     ServerIsNotUp,
+    #[error("backend '{0}' does not expose faucet endpoint")]
+    FaucetNotAvailable(String),
     #[error("Error code recieved: {0}")]
     ErrorStatusCode(StatusCode),
 }
@@ -1,5 +1,18 @@
+mod buffered;
 mod client;
+mod recorder;
 mod server;
+mod stats;
+mod streaming;
 
+pub use buffered::{forward_buffered, read_limited, status_response, LimitedBody};
 pub use client::{Error as ProxyClientError, ProxyClient};
-pub use server::{Error as ProxyServerError, Protocol, ProxyServerStub};
+pub use recorder::{
+    Error as ProxyRecorderError, ProxyRecorder, RecordedExchange, RecordedRequest, RecordedResponse,
+};
+pub use server::{
+    Block0Networks, BodyLimits, Error as ProxyServerError, Protocol, ProtocolParseError,
+    ProxyServerStub, DEFAULT_MAX_REQUEST_BODY, DEFAULT_MAX_RESPONSE_BODY,
+};
+pub use stats::{ProxyStats, ProxyStatsSnapshot, UpstreamStats};
+pub use streaming::{forward_streaming, forward_websocket, StreamingPaths};
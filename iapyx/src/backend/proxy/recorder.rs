@@ -0,0 +1,146 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use thiserror::Error;
+
+pub const REDACTED: &str = "<redacted>";
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("io error")]
+    IoError(#[from] std::io::Error),
+    #[error("serialization error")]
+    SerdeError(#[from] serde_json::Error),
+    #[error("recorded body is not valid hex")]
+    HexError(#[from] hex::FromHexError),
+    #[error("cannot replay request")]
+    RequestError(#[from] reqwest::Error),
+    #[error("unsupported method: {0}")]
+    UnsupportedMethod(String),
+}
+
+/// Writes every proxied request/response pair as separate json file in given directory.
+/// Values of redacted headers are replaced, so recordings can be shared safely
+pub struct ProxyRecorder {
+    dir: PathBuf,
+    redacted_headers: Vec<String>,
+    counter: AtomicU64,
+}
+
+impl ProxyRecorder {
+    pub fn new<P: AsRef<Path>>(dir: P, redacted_headers: Vec<String>) -> Result<Self, Error> {
+        std::fs::create_dir_all(dir.as_ref())?;
+        Ok(Self {
+            dir: dir.as_ref().to_path_buf(),
+            redacted_headers: redacted_headers
+                .into_iter()
+                .map(|header| header.to_lowercase())
+                .collect(),
+            counter: AtomicU64::new(0),
+        })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn redact(&self, mut headers: BTreeMap<String, String>) -> BTreeMap<String, String> {
+        for (name, value) in headers.iter_mut() {
+            if self.redacted_headers.contains(&name.to_lowercase()) {
+                *value = REDACTED.to_string();
+            }
+        }
+        headers
+    }
+
+    /// Saves exchange in file named after current timestamp and sequence number
+    pub fn record(&self, mut exchange: RecordedExchange) -> Result<PathBuf, Error> {
+        exchange.request.headers = self.redact(exchange.request.headers);
+        exchange.response.headers = self.redact(exchange.response.headers);
+
+        let counter = self.counter.fetch_add(1, Ordering::SeqCst);
+        let file_name = format!(
+            "{}_{:06}.json",
+            chrono::Utc::now().format("%Y%m%dT%H%M%S%.3f"),
+            counter
+        );
+        let path = self.dir.join(file_name);
+        std::fs::write(&path, serde_json::to_string_pretty(&exchange)?)?;
+        Ok(path)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedExchange {
+    pub upstream: String,
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+impl RecordedExchange {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Sends recorded request again to given address (e.g. "http://127.0.0.1:8000").
+    /// Redacted headers are not sent
+    pub fn replay(&self, address: &str) -> Result<reqwest::blocking::Response, Error> {
+        let method = reqwest::Method::from_bytes(self.request.method.as_bytes())
+            .map_err(|_| Error::UnsupportedMethod(self.request.method.clone()))?;
+
+        let mut url = format!("{}{}", address.trim_end_matches('/'), self.request.path);
+        if let Some(query) = &self.request.query {
+            url = format!("{}?{}", url, query);
+        }
+
+        let mut request = reqwest::blocking::Client::new()
+            .request(method, &url)
+            .body(hex::decode(&self.request.body)?);
+        for (name, value) in &self.request.headers {
+            if value != REDACTED {
+                request = request.header(name.as_str(), value.as_str());
+            }
+        }
+        Ok(request.send()?)
+    }
+}
+
+/// Body is kept as hex, since not every payload (e.g. fragments) is valid utf8
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    pub query: Option<String>,
+    pub headers: BTreeMap<String, String>,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    pub status: u16,
+    pub headers: BTreeMap<String, String>,
+    pub body: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacts_headers_case_insensitive() {
+        let recorder = ProxyRecorder {
+            dir: PathBuf::new(),
+            redacted_headers: vec!["authorization".to_string()],
+            counter: AtomicU64::new(0),
+        };
+        let mut headers = BTreeMap::new();
+        headers.insert("Authorization".to_string(), "secret".to_string());
+        headers.insert("content-type".to_string(), "application/json".to_string());
+
+        let headers = recorder.redact(headers);
+        assert_eq!(headers["Authorization"], REDACTED);
+        assert_eq!(headers["content-type"], "application/json");
+    }
+}
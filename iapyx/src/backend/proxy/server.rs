@@ -1,6 +1,10 @@
-use serde::{Deserialize, Serialize};
+use super::{ProxyRecorder, ProxyStats, StreamingPaths};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::BTreeMap;
 use std::net::SocketAddr;
 use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -13,7 +17,12 @@ pub enum Error {
     MalformedNodeRestAddress(String),
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+/// Protocol exposed by proxy. Serialized with explicit tag, e.g.
+/// `{"protocol":"https","key_path":"key.pem","cert_path":"cert.pem"}`.
+/// Deserialization also accepts string form (see `FromStr`) and old
+/// representation (`"Http"`, `{"Https":{"key_path":..,"cert_path":..}}`)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "protocol", rename_all = "lowercase")]
 pub enum Protocol {
     Http,
     Https {
@@ -22,18 +31,174 @@ pub enum Protocol {
     },
 }
 
+impl<'de> Deserialize<'de> for Protocol {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match ProtocolRepr::deserialize(deserializer)? {
+            ProtocolRepr::Tagged(TaggedProtocol::Http)
+            | ProtocolRepr::Legacy(LegacyProtocol::Http) => Ok(Self::Http),
+            ProtocolRepr::Tagged(TaggedProtocol::Https {
+                key_path,
+                cert_path,
+            })
+            | ProtocolRepr::Legacy(LegacyProtocol::Https {
+                key_path,
+                cert_path,
+            }) => Ok(Self::Https {
+                key_path,
+                cert_path,
+            }),
+            ProtocolRepr::Text(text) => text.parse().map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+/// Tagged variant is tried first, so https settings are never ignored
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ProtocolRepr {
+    Tagged(TaggedProtocol),
+    Legacy(LegacyProtocol),
+    Text(String),
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "protocol", rename_all = "lowercase")]
+enum TaggedProtocol {
+    Http,
+    Https {
+        key_path: PathBuf,
+        cert_path: PathBuf,
+    },
+}
+
+#[derive(Deserialize)]
+enum LegacyProtocol {
+    Http,
+    Https {
+        key_path: PathBuf,
+        cert_path: PathBuf,
+    },
+}
+
 impl Protocol {
     pub fn http() -> Self {
         Self::Http
     }
 }
 
+/// Parses `http` or `https:<key_path>,<cert_path>`
+impl FromStr for Protocol {
+    type Err = ProtocolParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.trim().splitn(2, ':');
+        match (parts.next(), parts.next()) {
+            (Some(protocol), None) if protocol.eq_ignore_ascii_case("http") => Ok(Self::Http),
+            (Some(protocol), paths) if protocol.eq_ignore_ascii_case("https") => {
+                let mut paths = paths.unwrap_or_default().splitn(2, ',');
+                match (paths.next(), paths.next()) {
+                    (Some(key_path), Some(cert_path))
+                        if !key_path.is_empty() && !cert_path.is_empty() =>
+                    {
+                        Ok(Self::Https {
+                            key_path: PathBuf::from(key_path),
+                            cert_path: PathBuf::from(cert_path),
+                        })
+                    }
+                    _ => Err(ProtocolParseError::MissingCertificate(s.to_string())),
+                }
+            }
+            _ => Err(ProtocolParseError::UnknownProtocol(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ProtocolParseError {
+    #[error("unknown protocol '{0}', expected: http or https:<key_path>,<cert_path>")]
+    UnknownProtocol(String),
+    #[error("https requires key and cert paths: https:<key_path>,<cert_path>, got '{0}'")]
+    MissingCertificate(String),
+}
+
+/// Block0 files served by proxy fronting several networks. Default block0 is served unless
+/// request selects network either by path prefix (`/{network}/api/v0/block0`) or by Host
+/// header equal to network name or starting with `{network}.`
+#[derive(Debug, Clone, Default)]
+pub struct Block0Networks {
+    default: Vec<u8>,
+    networks: BTreeMap<String, Vec<u8>>,
+}
+
+impl Block0Networks {
+    pub fn new(default: Vec<u8>) -> Self {
+        Self {
+            default,
+            networks: BTreeMap::new(),
+        }
+    }
+
+    pub fn add(&mut self, name: String, block0: Vec<u8>) {
+        self.networks.insert(name, block0);
+    }
+
+    pub fn default_block0(&self) -> &[u8] {
+        &self.default
+    }
+
+    pub fn get(&self, network: &str) -> Option<&[u8]> {
+        self.networks.get(network).map(Vec::as_slice)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &String> {
+        self.networks.keys()
+    }
+
+    pub fn for_host(&self, host: Option<&str>) -> &[u8] {
+        host.and_then(|host| host.split(':').next())
+            .and_then(|host| {
+                self.networks.iter().find(|(name, _)| {
+                    host == name.as_str() || host.starts_with(&format!("{}.", name))
+                })
+            })
+            .map(|(_, block0)| block0.as_slice())
+            .unwrap_or(&self.default)
+    }
+}
+
+pub const DEFAULT_MAX_REQUEST_BODY: u64 = 16 * 1024 * 1024;
+pub const DEFAULT_MAX_RESPONSE_BODY: u64 = 128 * 1024 * 1024;
+
+/// Maximum sizes (in bytes) of bodies passing through proxy. Request over the limit
+/// is rejected with 413, while too large upstream response is replaced with 502.
+/// Streaming paths are not limited
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BodyLimits {
+    pub max_request_body: u64,
+    pub max_response_body: u64,
+}
+
+impl Default for BodyLimits {
+    fn default() -> Self {
+        Self {
+            max_request_body: DEFAULT_MAX_REQUEST_BODY,
+            max_response_body: DEFAULT_MAX_RESPONSE_BODY,
+        }
+    }
+}
+
 pub struct ProxyServerStub {
     protocol: Protocol,
     address: String,
     vit_address: String,
     node_rest_address: String,
-    block0: Vec<u8>,
+    vit_host: Option<String>,
+    node_host: Option<String>,
+    block0: Block0Networks,
+    stats: Arc<ProxyStats>,
+    recorder: Option<Arc<ProxyRecorder>>,
+    streaming_paths: StreamingPaths,
+    body_limits: BodyLimits,
 }
 
 impl ProxyServerStub {
@@ -88,11 +253,71 @@ impl ProxyServerStub {
             address,
             vit_address,
             node_rest_address,
-            block0,
+            vit_host: None,
+            node_host: None,
+            block0: Block0Networks::new(block0),
+            stats: Arc::new(ProxyStats::default()),
+            recorder: None,
+            streaming_paths: StreamingPaths::default(),
+            body_limits: BodyLimits::default(),
         }
     }
 
+    pub fn with_vit_host(mut self, vit_host: Option<String>) -> Self {
+        self.vit_host = vit_host;
+        self
+    }
+
+    pub fn with_node_host(mut self, node_host: Option<String>) -> Self {
+        self.node_host = node_host;
+        self
+    }
+
+    /// Records every proxied request/response pair
+    pub fn with_recorder(mut self, recorder: Option<ProxyRecorder>) -> Self {
+        self.recorder = recorder.map(Arc::new);
+        self
+    }
+
+    pub fn recorder(&self) -> Option<Arc<ProxyRecorder>> {
+        self.recorder.clone()
+    }
+
+    /// Paths forwarded to vit station without buffering (server-sent events, websockets)
+    pub fn with_streaming_paths(mut self, streaming_paths: Vec<String>) -> Self {
+        self.streaming_paths = StreamingPaths::new(streaming_paths);
+        self
+    }
+
+    pub fn streaming_paths(&self) -> StreamingPaths {
+        self.streaming_paths.clone()
+    }
+
+    pub fn with_body_limits(mut self, body_limits: BodyLimits) -> Self {
+        self.body_limits = body_limits;
+        self
+    }
+
+    pub fn body_limits(&self) -> BodyLimits {
+        self.body_limits
+    }
+
+    /// Request counters shared by all proxy routes
+    pub fn stats(&self) -> Arc<ProxyStats> {
+        self.stats.clone()
+    }
+
+    /// Serves given block0 for requests selecting network `name`
+    pub fn with_network(mut self, name: String, block0: Vec<u8>) -> Self {
+        self.block0.add(name, block0);
+        self
+    }
+
     pub fn block0(&self) -> Vec<u8> {
+        self.block0.default_block0().to_vec()
+    }
+
+    pub fn block0_networks(&self) -> Block0Networks {
         self.block0.clone()
     }
 
@@ -108,6 +333,14 @@ impl ProxyServerStub {
         self.node_rest_address.parse().unwrap()
     }
 
+    pub fn vit_host(&self) -> Option<String> {
+        self.vit_host.clone()
+    }
+
+    pub fn node_host(&self) -> Option<String> {
+        self.node_host.clone()
+    }
+
     pub fn base_address(&self) -> SocketAddr {
         self.address.parse().unwrap()
     }
@@ -120,3 +353,90 @@ impl ProxyServerStub {
         format!("http://{}/", self.node_rest_address)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block0_selected_by_host() {
+        let mut networks = Block0Networks::new(vec![0]);
+        networks.add("private".to_string(), vec![1]);
+
+        assert_eq!(networks.for_host(None), &[0]);
+        assert_eq!(networks.for_host(Some("localhost:8000")), &[0]);
+        assert_eq!(networks.for_host(Some("private")), &[1]);
+        assert_eq!(networks.for_host(Some("private.example.com:8000")), &[1]);
+        assert_eq!(networks.for_host(Some("privatefund.example.com")), &[0]);
+        assert_eq!(networks.get("private"), Some(&[1u8][..]));
+        assert_eq!(networks.get("public"), None);
+    }
+
+    #[test]
+    fn protocol_from_str() {
+        assert_eq!("http".parse(), Ok(Protocol::Http));
+        assert_eq!(
+            "https:key.pem,cert.pem".parse(),
+            Ok(Protocol::Https {
+                key_path: PathBuf::from("key.pem"),
+                cert_path: PathBuf::from("cert.pem"),
+            })
+        );
+        assert!(matches!(
+            "https".parse::<Protocol>(),
+            Err(ProtocolParseError::MissingCertificate(_))
+        ));
+        assert!(matches!(
+            "ftp".parse::<Protocol>(),
+            Err(ProtocolParseError::UnknownProtocol(_))
+        ));
+    }
+
+    #[test]
+    fn protocol_serde_round_trip() {
+        let protocols = vec![
+            Protocol::Http,
+            Protocol::Https {
+                key_path: PathBuf::from("key.pem"),
+                cert_path: PathBuf::from("cert.pem"),
+            },
+        ];
+        for protocol in protocols {
+            let json = serde_json::to_string(&protocol).unwrap();
+            let deserialized: Protocol = serde_json::from_str(&json).unwrap();
+            assert_eq!(protocol, deserialized);
+        }
+    }
+
+    #[test]
+    fn legacy_protocol_representation_is_accepted() {
+        let protocol: Protocol = serde_json::from_str(r#""Http""#).unwrap();
+        assert_eq!(protocol, Protocol::Http);
+
+        let protocol: Protocol =
+            serde_json::from_str(r#"{"Https":{"key_path":"key.pem","cert_path":"cert.pem"}}"#)
+                .unwrap();
+        assert_eq!(
+            protocol,
+            Protocol::Https {
+                key_path: PathBuf::from("key.pem"),
+                cert_path: PathBuf::from("cert.pem"),
+            }
+        );
+    }
+
+    #[test]
+    fn protocol_string_representation_is_accepted() {
+        let protocol: Protocol = serde_json::from_str(r#""https:key.pem,cert.pem""#).unwrap();
+        assert!(matches!(protocol, Protocol::Https { .. }));
+    }
+
+    #[test]
+    fn https_protocol_is_not_deserialized_as_http() {
+        let protocol: Protocol = serde_json::from_str(
+            r#"{"protocol":"https","key_path":"key.pem","cert_path":"cert.pem"}"#,
+        )
+        .unwrap();
+        assert!(matches!(protocol, Protocol::Https { .. }));
+    }
+}
@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Counters of requests forwarded by proxy, shared between all routes
+#[derive(Debug, Default)]
+pub struct ProxyStats {
+    upstreams: Mutex<BTreeMap<String, Counters>>,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Counters {
+    requests: u64,
+    errors: u64,
+    total_latency: Duration,
+}
+
+impl Counters {
+    fn average_latency_ms(&self) -> f64 {
+        if self.requests == 0 {
+            return 0.0;
+        }
+        self.total_latency.as_secs_f64() * 1000.0 / self.requests as f64
+    }
+}
+
+impl ProxyStats {
+    /// Records single forwarded request. Request is an error if upstream could not be
+    /// reached or responded with server error
+    pub fn record(&self, upstream: &str, latency: Duration, error: bool) {
+        let mut upstreams = self.upstreams.lock().unwrap();
+        let counters = upstreams.entry(upstream.to_string()).or_default();
+        counters.requests += 1;
+        counters.total_latency += latency;
+        if error {
+            counters.errors += 1;
+        }
+    }
+
+    pub fn snapshot(&self) -> ProxyStatsSnapshot {
+        let upstreams = self.upstreams.lock().unwrap();
+        let total = upstreams
+            .values()
+            .fold(Counters::default(), |mut total, counters| {
+                total.requests += counters.requests;
+                total.errors += counters.errors;
+                total.total_latency += counters.total_latency;
+                total
+            });
+        ProxyStatsSnapshot {
+            total_requests: total.requests,
+            errors: total.errors,
+            average_latency_ms: total.average_latency_ms(),
+            upstreams: upstreams
+                .iter()
+                .map(|(name, counters)| {
+                    (
+                        name.clone(),
+                        UpstreamStats {
+                            requests: counters.requests,
+                            errors: counters.errors,
+                            average_latency_ms: counters.average_latency_ms(),
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProxyStatsSnapshot {
+    pub total_requests: u64,
+    pub errors: u64,
+    pub average_latency_ms: f64,
+    pub upstreams: BTreeMap<String, UpstreamStats>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UpstreamStats {
+    pub requests: u64,
+    pub errors: u64,
+    pub average_latency_ms: f64,
+}
@@ -0,0 +1,176 @@
+use futures::{SinkExt, StreamExt};
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::header::{
+    HeaderValue as UpstreamHeaderValue, HOST as UPSTREAM_HOST,
+};
+use tokio_tungstenite::tungstenite::Message as UpstreamMessage;
+use warp::http::header::{HeaderMap, HeaderValue, HOST};
+use warp::http::{Method, Response, StatusCode};
+use warp::hyper::{Body, Client, Request};
+use warp::path::FullPath;
+use warp::ws::{Message, WebSocket};
+use warp::Buf;
+
+/// Paths (prefixes of full request path, e.g. "/api/v0/tally/stream") which are forwarded
+/// to vit station without buffering. Request and response bodies are passed chunk by chunk,
+/// so server-sent events reach client as soon as upstream emits them. Websocket upgrade
+/// requests on those paths are tunneled to upstream websocket
+#[derive(Debug, Clone, Default)]
+pub struct StreamingPaths(Vec<String>);
+
+impl StreamingPaths {
+    pub fn new(paths: Vec<String>) -> Self {
+        Self(paths)
+    }
+
+    pub fn is_streaming(&self, path: &str) -> bool {
+        self.0
+            .iter()
+            .any(|prefix| path.starts_with(prefix.as_str()))
+    }
+
+    pub fn paths(&self) -> &[String] {
+        &self.0
+    }
+}
+
+/// Forwards request to upstream and returns response with body streamed as it arrives
+pub async fn forward_streaming<S, B>(
+    upstream: String,
+    host: Option<String>,
+    path: FullPath,
+    query: Option<String>,
+    method: Method,
+    mut headers: HeaderMap,
+    body: S,
+) -> Result<Response<Body>, warp::Rejection>
+where
+    S: futures::Stream<Item = Result<B, warp::Error>> + Send + 'static,
+    B: Buf,
+{
+    if let Some(host) = &host {
+        if let Ok(value) = HeaderValue::from_str(host) {
+            headers.insert(HOST, value);
+        }
+    }
+
+    let body = body.map(|chunk| chunk.map(|mut buf| buf.copy_to_bytes(buf.remaining())));
+    let mut request = Request::builder()
+        .method(method)
+        .uri(upstream_uri("http", &upstream, &path, &query))
+        .body(Body::wrap_stream(body))
+        .expect("valid request");
+    *request.headers_mut() = headers;
+
+    match Client::new().request(request).await {
+        Ok(response) => Ok(response),
+        Err(err) => {
+            eprintln!("streaming upstream error: {:?}", err);
+            Ok(Response::builder()
+                .status(StatusCode::BAD_GATEWAY)
+                .body(Body::empty())
+                .expect("valid response"))
+        }
+    }
+}
+
+/// Connects to upstream websocket on the same path and passes messages in both directions
+/// until one of the sides closes connection. Host header of upgrade request is replaced
+/// with `host`, same as in `forward_streaming`
+pub async fn forward_websocket(
+    client: WebSocket,
+    upstream: String,
+    host: Option<String>,
+    path: FullPath,
+    query: Option<String>,
+) {
+    let uri = upstream_uri("ws", &upstream, &path, &query);
+    let mut request = match uri.as_str().into_client_request() {
+        Ok(request) => request,
+        Err(err) => {
+            eprintln!("invalid upstream websocket uri {}: {:?}", uri, err);
+            return;
+        }
+    };
+    if let Some(host) = &host {
+        if let Ok(value) = UpstreamHeaderValue::from_str(host) {
+            request.headers_mut().insert(UPSTREAM_HOST, value);
+        }
+    }
+
+    let upstream = match tokio_tungstenite::connect_async(request).await {
+        Ok((upstream, _)) => upstream,
+        Err(err) => {
+            eprintln!("cannot connect to upstream websocket {}: {:?}", uri, err);
+            return;
+        }
+    };
+
+    let (mut client_tx, mut client_rx) = client.split();
+    let (mut upstream_tx, mut upstream_rx) = upstream.split();
+
+    let to_upstream = async {
+        while let Some(Ok(message)) = client_rx.next().await {
+            let message = if message.is_close() {
+                UpstreamMessage::Close(None)
+            } else if message.is_binary() {
+                UpstreamMessage::Binary(message.into_bytes())
+            } else if message.is_ping() {
+                UpstreamMessage::Ping(message.into_bytes())
+            } else if message.is_pong() {
+                UpstreamMessage::Pong(message.into_bytes())
+            } else {
+                UpstreamMessage::Text(String::from_utf8_lossy(message.as_bytes()).to_string())
+            };
+            if upstream_tx.send(message).await.is_err() {
+                break;
+            }
+        }
+        let _ = upstream_tx.close().await;
+    };
+
+    let to_client = async {
+        while let Some(Ok(message)) = upstream_rx.next().await {
+            let message = match message {
+                UpstreamMessage::Text(text) => Message::text(text),
+                UpstreamMessage::Binary(data) => Message::binary(data),
+                UpstreamMessage::Ping(data) => Message::ping(data),
+                UpstreamMessage::Pong(_) => continue,
+                UpstreamMessage::Close(_) => break,
+            };
+            if client_tx.send(message).await.is_err() {
+                break;
+            }
+        }
+        let _ = client_tx.close().await;
+    };
+
+    futures::future::select(Box::pin(to_upstream), Box::pin(to_client)).await;
+}
+
+pub(super) fn upstream_uri(
+    scheme: &str,
+    upstream: &str,
+    path: &FullPath,
+    query: &Option<String>,
+) -> String {
+    let mut uri = format!("{}://{}{}", scheme, upstream, path.as_str());
+    if let Some(query) = query {
+        uri = format!("{}?{}", uri, query);
+    }
+    uri
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_path_prefixes() {
+        let paths = StreamingPaths::new(vec!["/api/v0/tally/stream".to_string()]);
+        assert!(paths.is_streaming("/api/v0/tally/stream"));
+        assert!(paths.is_streaming("/api/v0/tally/stream/1"));
+        assert!(!paths.is_streaming("/api/v0/proposals"));
+        assert!(!StreamingPaths::default().is_streaming("/api/v0/proposals"));
+    }
+}
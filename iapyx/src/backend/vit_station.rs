@@ -4,6 +4,8 @@ use crate::data::ServiceVersion;
 use crate::data::{Fund, Proposal};
 use hyper::StatusCode;
 use reqwest::blocking::Response;
+use reqwest::header::HeaderMap;
+use reqwest::Certificate;
 use thiserror::Error;
 pub const API_TOKEN_HEADER: &str = "API-Token";
 
@@ -47,6 +49,8 @@ impl RestClientLogger {
 pub struct VitStationRestClient {
     path_builder: RestPathBuilder,
     api_token: Option<String>,
+    certificate: Option<Certificate>,
+    headers: HeaderMap,
     logger: RestClientLogger,
 }
 
@@ -54,6 +58,8 @@ impl VitStationRestClient {
     pub fn new(address: String) -> Self {
         Self {
             api_token: None,
+            certificate: None,
+            headers: HeaderMap::new(),
             path_builder: RestPathBuilder::new(address),
             logger: RestClientLogger { enabled: false },
         }
@@ -164,17 +170,27 @@ impl VitStationRestClient {
 
     pub fn get(&self, path: &str) -> Result<reqwest::blocking::Response, reqwest::Error> {
         self.logger.log_request(path);
-        let client = reqwest::blocking::Client::new();
+        let client = self.client()?;
         let mut res = client.get(path);
 
         if let Some(api_token) = &self.api_token {
             res = res.header(API_TOKEN_HEADER, api_token.to_string());
         }
-        let response = res.send()?;
+        let response = res.headers(self.headers.clone()).send()?;
         self.logger.log_response(&response);
         Ok(response)
     }
 
+    /// Client negotiates gzip compression and transparently decompresses responses,
+    /// so all logged and returned content is already plain text
+    fn client(&self) -> Result<reqwest::blocking::Client, reqwest::Error> {
+        let mut builder = reqwest::blocking::Client::builder().gzip(true);
+        if let Some(certificate) = &self.certificate {
+            builder = builder.add_root_certificate(certificate.clone());
+        }
+        builder.build()
+    }
+
     fn get_and_verify_status_code(
         &self,
         path: &str,
@@ -199,14 +215,23 @@ impl VitStationRestClient {
         self.api_token = Some(token);
     }
 
+    pub fn set_certificate(&mut self, certificate: Certificate) {
+        self.certificate = Some(certificate);
+    }
+
+    /// Headers sent with every request. They replace headers set by client (e.g. API-Token)
+    pub fn set_headers(&mut self, headers: HeaderMap) {
+        self.headers = headers;
+    }
+
     pub fn post(&self, path: &str, data: String) -> Result<serde_json::Value, RestError> {
-        let client = reqwest::blocking::Client::new();
+        let client = self.client()?;
         let mut res = client.post(path).body(String::into_bytes(data));
 
         if let Some(api_token) = &self.api_token {
             res = res.header(API_TOKEN_HEADER, api_token.to_string());
         }
-        let response = res.send()?;
+        let response = res.headers(self.headers.clone()).send()?;
         self.logger.log_response(&response);
         let result = response.text();
         Ok(serde_json::from_str(&result?)?)
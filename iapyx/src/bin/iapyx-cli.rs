@@ -1,8 +1,33 @@
-use iapyx::cli::args::interactive::IapyxInteractiveCommandExec;
-use iapyx::cli::args::interactive::{UserInteractionContoller, WalletState};
+use iapyx::cli::args::interactive::{
+    IapyxInteractiveCommand, IapyxInteractiveCommandExec, UserInteractionContoller, WalletState,
+};
 use jortestkit::console::UserInteraction;
+use structopt::StructOpt;
 
 pub fn main() {
+    let command = IapyxInteractiveCommand::from_args();
+
+    let mut exec = IapyxInteractiveCommandExec {
+        controller: UserInteractionContoller {
+            state: WalletState::New,
+            controller: None,
+            alias: None,
+            wallets: Default::default(),
+            output_format: command.output_format,
+            backend_address: "127.0.0.1:80".to_string(),
+            settings: Default::default(),
+        },
+    };
+
+    if let Some(script) = &command.script {
+        let summary = exec.run_script(script, command.continue_on_error).unwrap();
+        summary.print();
+        if !summary.is_success() {
+            std::process::exit(1);
+        }
+        return;
+    }
+
     let user_interaction = UserInteraction::new(
         "iapyx".to_string(),
         "wallet interactive console".to_string(),
@@ -18,14 +43,5 @@ pub fn main() {
         ],
     );
 
-    user_interaction
-        .interact(&mut IapyxInteractiveCommandExec {
-            controller: UserInteractionContoller {
-                state: WalletState::New,
-                controller: None,
-                backend_address: "127.0.0.1:80".to_string(),
-                settings: Default::default(),
-            },
-        })
-        .unwrap();
+    user_interaction.interact(&mut exec).unwrap();
 }
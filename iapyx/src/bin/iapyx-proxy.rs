@@ -1,65 +1,141 @@
-use iapyx::{cli::args::proxy::IapyxProxyCommand, Protocol};
+use iapyx::utils::block0::block0_hash;
+use iapyx::{
+    cli::args::proxy::IapyxProxyCommand, forward_buffered, forward_streaming, forward_websocket,
+    read_limited, status_response, BodyLimits, LimitedBody, Protocol, ProxyRecorder, ProxyStats,
+    RecordedExchange, RecordedRequest, RecordedResponse,
+};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Instant;
 use structopt::StructOpt;
-use warp::Filter;
-use warp_reverse_proxy::reverse_proxy_filter;
+use warp::http::header::{HeaderMap, HeaderValue, HOST};
+use warp::http::{Method, Response, StatusCode};
+use warp::hyper::body::Bytes;
+use warp::path::FullPath;
+use warp::{Filter, Rejection};
 
 #[tokio::main]
 async fn main() {
     let server_stub = IapyxProxyCommand::from_args().build().unwrap();
 
+    match block0_hash(&server_stub.block0()) {
+        Ok(hash) => println!("serving block0 with hash: {}", hash),
+        Err(err) => eprintln!("cannot compute block0 hash: {}", err),
+    }
+    let block0_networks = server_stub.block0_networks();
+    for name in block0_networks.names() {
+        match block0_hash(block0_networks.get(name).unwrap()) {
+            Ok(hash) => println!("serving block0 for network '{}' with hash: {}", name, hash),
+            Err(err) => eprintln!("cannot compute block0 hash for network '{}': {}", name, err),
+        }
+    }
+
+    let stats = server_stub.stats();
+    let recorder = server_stub.recorder();
+    let limits = server_stub.body_limits();
+    let vit_upstream = upstream(
+        "vit_station",
+        server_stub.vit_address(),
+        server_stub.vit_host(),
+        stats.clone(),
+        recorder.clone(),
+        limits,
+    );
+    let node_upstream = upstream(
+        "node",
+        server_stub.node_rest_address(),
+        server_stub.node_host(),
+        stats.clone(),
+        recorder,
+        limits,
+    );
+
+    let streaming = {
+        let streaming_paths = server_stub.streaming_paths();
+        let vit_address = server_stub.vit_address();
+        let vit_host = server_stub.vit_host();
+
+        let path = warp::path::full()
+            .and_then(move |path: FullPath| {
+                let is_streaming = streaming_paths.is_streaming(path.as_str());
+                async move {
+                    if is_streaming {
+                        Ok(path)
+                    } else {
+                        Err(warp::reject::not_found())
+                    }
+                }
+            })
+            .and(
+                warp::query::raw()
+                    .map(Some)
+                    .or(warp::any().map(|| None))
+                    .unify(),
+            );
+
+        let websocket = {
+            let vit_address = vit_address.clone();
+            let vit_host = vit_host.clone();
+            path.clone().and(warp::ws()).map(
+                move |path: FullPath, query: Option<String>, ws: warp::ws::Ws| {
+                    let upstream = vit_address.clone();
+                    let host = vit_host.clone();
+                    ws.on_upgrade(move |socket| {
+                        forward_websocket(socket, upstream, host, path, query)
+                    })
+                },
+            )
+        };
+
+        let stream = path
+            .and(warp::method())
+            .and(warp::header::headers_cloned())
+            .and(warp::body::stream())
+            .and_then(move |path, query, method, headers, body| {
+                forward_streaming(
+                    vit_address.clone(),
+                    vit_host.clone(),
+                    path,
+                    query,
+                    method,
+                    headers,
+                    body,
+                )
+            });
+
+        websocket.or(stream)
+    };
+
     let api = warp::path!("api" / ..);
 
     let v0 = {
         let root = warp::path!("v0" / ..);
 
-        let proposals = warp::path!("proposals" / ..).and(reverse_proxy_filter(
-            "".to_string(),
-            server_stub.http_vit_address(),
-        ));
+        let proposals = warp::path!("proposals" / ..).and(vit_upstream.clone());
 
-        let challenges = warp::path!("challenges" / ..).and(reverse_proxy_filter(
-            "".to_string(),
-            server_stub.http_vit_address(),
-        ));
+        let challenges = warp::path!("challenges" / ..).and(vit_upstream.clone());
 
-        let fund = warp::path!("fund" / ..).and(reverse_proxy_filter(
-            "".to_string(),
-            server_stub.http_vit_address(),
-        ));
+        let fund = warp::path!("fund" / ..).and(vit_upstream.clone());
 
-        let account = warp::path!("account" / ..).and(reverse_proxy_filter(
-            "".to_string(),
-            server_stub.http_node_address(),
-        ));
+        let account = warp::path!("account" / ..).and(node_upstream.clone());
 
-        let fragment = warp::path!("fragment" / "logs").and(reverse_proxy_filter(
-            "".to_string(),
-            server_stub.http_node_address(),
-        ));
+        let fragment = warp::path!("fragment" / "logs").and(node_upstream.clone());
 
-        let message = warp::path!("message" / ..).and(reverse_proxy_filter(
-            "".to_string(),
-            server_stub.http_node_address(),
-        ));
+        let message = warp::path!("message" / ..).and(node_upstream.clone());
 
-        let settings = warp::path!("settings" / ..).and(reverse_proxy_filter(
-            "".to_string(),
-            server_stub.http_node_address(),
-        ));
+        let settings = warp::path!("settings" / ..).and(node_upstream.clone());
 
-        let explorer = warp::path!("explorer" / "graphql").and(reverse_proxy_filter(
-            "".to_string(),
-            server_stub.http_node_address(),
-        ));
+        let explorer = warp::path!("explorer" / "graphql").and(node_upstream.clone());
 
-        let vote = warp::path!("vote" / "active" / ..).and(reverse_proxy_filter(
-            "".to_string(),
-            server_stub.http_node_address(),
-        ));
+        let vote = warp::path!("vote" / "active" / ..).and(node_upstream.clone());
 
-        let block0_content = server_stub.block0();
+        let block0_networks = server_stub.block0_networks();
 
-        let block0 = warp::path!("block0").map(move || Ok(block0_content.clone()));
+        let block0 = warp::path!("block0")
+            .and(warp::header::optional::<String>("host"))
+            .map(move |host: Option<String>| {
+                Ok(block0_networks.for_host(host.as_deref()).to_vec())
+            });
 
         root.and(
             proposals
@@ -78,19 +154,26 @@ async fn main() {
     let v1 = {
         let root = warp::path!("v1" / ..);
 
-        let fragments = warp::path!("fragments" / ..).and(reverse_proxy_filter(
-            "".to_string(),
-            server_stub.http_node_address(),
-        ));
+        let fragments = warp::path!("fragments" / ..).and(node_upstream.clone());
 
         root.and(fragments)
     };
 
-    let vit_version = warp::path!("vit-version").and(reverse_proxy_filter(
-        "".to_string(),
-        server_stub.http_vit_address(),
-    ));
-    let app = api.and(v0.or(v1).or(vit_version));
+    let network_block0 = {
+        let block0_networks = server_stub.block0_networks();
+        warp::path!(String / "api" / "v0" / "block0").and_then(move |network: String| {
+            let block0 = block0_networks.get(&network).map(<[u8]>::to_vec);
+            async move { block0.ok_or_else(warp::reject::not_found) }
+        })
+    };
+
+    let vit_version = warp::path!("vit-version").and(vit_upstream.clone());
+    let stats = warp::path!("stats").map(move || warp::reply::json(&stats.snapshot()));
+    let app = streaming
+        .or(api.and(v0.or(v1).or(vit_version)))
+        .or(stats)
+        .or(network_block0)
+        .recover(handle_rejection);
 
     match server_stub.protocol() {
         Protocol::Https {
@@ -109,3 +192,130 @@ async fn main() {
         }
     }
 }
+
+/// Forwards request to upstream address, optionally replacing Host header
+/// (required when upstream is served as a virtual host). Request and response bodies are
+/// read with running byte cap, so neither of them is buffered over the limit. Each request
+/// is recorded in stats under upstream name and, if recording is enabled, saved together
+/// with response
+fn upstream(
+    name: &'static str,
+    address: String,
+    host: Option<String>,
+    stats: Arc<ProxyStats>,
+    recorder: Option<Arc<ProxyRecorder>>,
+    limits: BodyLimits,
+) -> impl Filter<Extract = (Response<Bytes>,), Error = Rejection> + Clone {
+    request_body_limit(limits.max_request_body)
+        .and(warp::path::full())
+        .and(
+            warp::query::raw()
+                .map(Some)
+                .or(warp::any().map(|| None))
+                .unify(),
+        )
+        .and(warp::method())
+        .and(warp::header::headers_cloned())
+        .and(warp::body::stream())
+        .and_then(
+            move |uri: FullPath,
+                  params: Option<String>,
+                  method: Method,
+                  mut headers: HeaderMap,
+                  body| {
+                if let Some(host) = &host {
+                    if let Ok(value) = HeaderValue::from_str(host) {
+                        headers.insert(HOST, value);
+                    }
+                }
+                let address = address.clone();
+                let stats = stats.clone();
+                let recorder = recorder.clone();
+                async move {
+                    // bodies sent without content-length are checked while received
+                    let body = match read_limited(body, limits.max_request_body).await {
+                        Ok(LimitedBody::Complete(body)) => body,
+                        Ok(LimitedBody::TooLarge) => {
+                            return Ok::<_, Rejection>(status_response(
+                                StatusCode::PAYLOAD_TOO_LARGE,
+                            ))
+                        }
+                        Err(_) => return Ok(status_response(StatusCode::BAD_REQUEST)),
+                    };
+                    let request = recorder.as_ref().map(|_| RecordedRequest {
+                        method: method.to_string(),
+                        path: uri.as_str().to_string(),
+                        query: params.clone(),
+                        headers: headers_to_map(&headers),
+                        body: hex::encode(&body),
+                    });
+
+                    let started = Instant::now();
+                    let response = forward_buffered(
+                        address,
+                        uri,
+                        params,
+                        method,
+                        headers,
+                        body,
+                        limits.max_response_body,
+                    )
+                    .await;
+                    stats.record(name, started.elapsed(), response.status().is_server_error());
+
+                    if let (Some(recorder), Some(request)) = (recorder, request) {
+                        let exchange = RecordedExchange {
+                            upstream: name.to_string(),
+                            request,
+                            response: RecordedResponse {
+                                status: response.status().as_u16(),
+                                headers: headers_to_map(response.headers()),
+                                body: hex::encode(response.body()),
+                            },
+                        };
+                        if let Err(err) = recorder.record(exchange) {
+                            eprintln!("cannot record request: {:?}", err);
+                        }
+                    }
+                    Ok(response)
+                }
+            },
+        )
+}
+
+#[derive(Debug)]
+struct RequestBodyTooLarge;
+
+impl warp::reject::Reject for RequestBodyTooLarge {}
+
+/// Rejects request declaring larger content-length than allowed, before its body is read
+fn request_body_limit(max: u64) -> impl Filter<Extract = (), Error = Rejection> + Clone {
+    warp::header::optional::<u64>("content-length")
+        .and_then(move |length: Option<u64>| async move {
+            match length {
+                Some(length) if length > max => Err(warp::reject::custom(RequestBodyTooLarge)),
+                _ => Ok(()),
+            }
+        })
+        .untuple_one()
+}
+
+async fn handle_rejection(err: Rejection) -> Result<Response<Bytes>, Rejection> {
+    if err.find::<RequestBodyTooLarge>().is_some() {
+        Ok(status_response(StatusCode::PAYLOAD_TOO_LARGE))
+    } else {
+        Err(err)
+    }
+}
+
+fn headers_to_map(headers: &HeaderMap) -> BTreeMap<String, String> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            (
+                name.to_string(),
+                String::from_utf8_lossy(value.as_bytes()).to_string(),
+            )
+        })
+        .collect()
+}
@@ -1,10 +1,17 @@
-use super::WalletState;
+use super::{OutputFormat, WalletState};
 use crate::cli::args::interactive::UserInteractionContoller;
-use crate::Controller;
+use crate::controller::read_bech32;
+use crate::utils::mnemonics::{validate_mnemonics, MnemonicsError};
+use crate::{load_root_certificate, Controller, ControllerError, Proposal, Wallet};
+use bech32::FromBase32;
 use bip39::Type;
 use chain_addr::{AddressReadable, Discrimination};
 use jormungandr_testing_utils::testing::node::RestSettings;
+use serde::Serialize;
+use serde_json::json;
+use std::convert::TryInto;
 use std::path::PathBuf;
+use std::time::Duration;
 use structopt::{clap::AppSettings, StructOpt};
 use thiserror::Error;
 use wallet_core::Choice;
@@ -18,139 +25,351 @@ pub enum IapyxCommand {
     Generate(Generate),
     /// connect to backend
     Connect(Connect),
+    /// drops all wallets loaded in session, so session can be started again
+    /// (e.g. against different backend)
+    Disconnect,
+    /// manage wallets loaded in session
+    Wallets(Wallets),
     /// confirms transaction
     ConfirmTx,
     Value,
-    /// Prints wallets, nodes which can be used. Draw topology
+    /// Prints backend connection, wallets loaded in session and active account state
     Status,
-    /// Prints wallets, nodes which can be used. Draw topology
+    /// Prints blockchain settings used by active wallet (discrimination, fees, block0 hash, slot timing)
+    Settings,
+    /// Refreshes active wallet state (value, spending counter) and blockchain settings from backend
     Refresh,
     /// get Address
     Address(Address),
+    /// derive address from mnemonics or secret key without connecting to backend
+    DeriveAddress(DeriveAddress),
     Logs,
+    /// prints fragment status changes as they happen
+    WatchLogs(WatchLogs),
     /// Exit interactive mode
     Exit,
     Proposals,
     Vote(Vote),
+    /// send funds to given address
+    Send(SendFunds),
+    /// request funds from backend faucet to active wallet
+    Faucet(Faucet),
     Votes,
+    /// prints receipts of all votes cast in this session
+    Receipts,
     PendingTransactions,
+    /// prints snapshot of utxos assigned to active wallet in block0 (including already spent ones)
+    Block0Utxos,
 }
 
 impl IapyxCommand {
     pub fn exec(&self, model: &mut UserInteractionContoller) -> Result<(), IapyxCommandError> {
         match self {
             IapyxCommand::PendingTransactions => {
-                if let Some(controller) = model.controller.as_mut() {
-                    let fragment_ids = controller
-                        .pending_transactions()
-                        .iter()
-                        .map(|x| x.to_string())
-                        .collect::<Vec<String>>();
-                    println!("===================");
-                    for (id, fragment_ids) in fragment_ids.iter().enumerate() {
-                        println!("{}. {}", (id + 1), fragment_ids);
-                    }
-                    println!("===================");
-                    return Ok(());
+                let output_format = model.output_format;
+                let controller = model.active_controller()?;
+                let fragment_ids = controller
+                    .pending_transactions()
+                    .iter()
+                    .map(|x| x.to_string())
+                    .collect::<Vec<String>>();
+                if output_format == OutputFormat::Json {
+                    return print_json(&fragment_ids);
+                }
+                println!("===================");
+                for (id, fragment_ids) in fragment_ids.iter().enumerate() {
+                    println!("{}. {}", (id + 1), fragment_ids);
                 }
-                Err(IapyxCommandError::GeneralError(
-                    "wallet not recovered or generated".to_string(),
-                ))
+                println!("===================");
+                Ok(())
+            }
+            IapyxCommand::Block0Utxos => {
+                let output_format = model.output_format;
+                let controller = model.active_controller()?;
+                let utxos = controller.block0_utxos()?;
+                if output_format == OutputFormat::Json {
+                    return print_json(&utxos);
+                }
+                println!("===================");
+                for (id, utxo) in utxos.iter().enumerate() {
+                    println!("{}. {}", (id + 1), utxo);
+                }
+                println!("===================");
+                Ok(())
             }
             IapyxCommand::Votes => {
-                if let Some(controller) = model.controller.as_mut() {
-                    println!("===================");
-                    for (id, vote) in controller.active_votes()?.iter().enumerate() {
-                        println!("{}. {}", (id + 1), vote);
-                    }
-                    println!("===================");
-                    return Ok(());
+                let output_format = model.output_format;
+                let controller = model.active_controller()?;
+                if output_format == OutputFormat::Json {
+                    let rejected: Vec<_> = controller
+                        .rejected_votes()?
+                        .into_iter()
+                        .map(|(id, reason)| json!({ "fragment_id": id.to_string(), "reason": reason }))
+                        .collect();
+                    return print_json(&json!({
+                        "votes": controller.active_votes()?,
+                        "rejected": rejected,
+                    }));
+                }
+                println!("===================");
+                for (id, vote) in controller.active_votes()?.iter().enumerate() {
+                    println!("{}. {}", (id + 1), vote);
                 }
-                Err(IapyxCommandError::GeneralError(
-                    "wallet not recovered or generated".to_string(),
-                ))
+                println!("===================");
+                print_rejected_votes(controller)?;
+                Ok(())
+            }
+            IapyxCommand::Receipts => {
+                let output_format = model.output_format;
+                let controller = model.active_controller()?;
+                if output_format == OutputFormat::Json {
+                    return print_json(&controller.vote_receipts());
+                }
+                println!("===================");
+                for (id, receipt) in controller.vote_receipts().iter().enumerate() {
+                    println!("{}. {}", (id + 1), receipt);
+                }
+                println!("===================");
+                Ok(())
             }
             IapyxCommand::Proposals => {
-                if let Some(controller) = model.controller.as_mut() {
-                    println!("===================");
-                    for (id, proposal) in controller.get_proposals()?.iter().enumerate() {
-                        println!(
-                            "{}. #{} [{}] {}",
-                            (id + 1),
-                            proposal.chain_proposal_id_as_str(),
-                            proposal.proposal_title,
-                            proposal.proposal_summary
-                        );
-                        println!("{:#?}", proposal.chain_vote_options.0);
-                    }
-                    println!("===================");
-                    return Ok(());
+                let output_format = model.output_format;
+                let controller = model.active_controller()?;
+                if output_format == OutputFormat::Json {
+                    return print_json(&controller.get_proposals()?);
                 }
-                Err(IapyxCommandError::GeneralError(
-                    "wallet not recovered or generated".to_string(),
-                ))
+                println!("===================");
+                for (id, proposal) in controller.get_proposals()?.iter().enumerate() {
+                    println!(
+                        "{}. #{} [{}] {}",
+                        (id + 1),
+                        proposal.chain_proposal_id_as_str(),
+                        proposal.proposal_title,
+                        proposal.proposal_summary
+                    );
+                    println!("{:#?}", proposal.chain_vote_options.0);
+                }
+                println!("===================");
+                Ok(())
             }
             IapyxCommand::Vote(vote) => vote.exec(model),
+            IapyxCommand::Send(send) => send.exec(model),
+            IapyxCommand::Faucet(faucet) => faucet.exec(model),
             IapyxCommand::ConfirmTx => {
-                if let Some(controller) = model.controller.as_mut() {
-                    controller.confirm_all_transactions();
-                    return Ok(());
-                }
-                Err(IapyxCommandError::GeneralError(
-                    "wallet not recovered or generated".to_string(),
-                ))
+                let controller = model.active_controller()?;
+                controller.confirm_all_transactions();
+                Ok(())
             }
             IapyxCommand::Recover(recover) => recover.exec(model),
             IapyxCommand::Exit => Ok(()),
             IapyxCommand::Generate(generate) => generate.exec(model),
             IapyxCommand::Connect(connect) => connect.exec(model),
+            IapyxCommand::Disconnect => {
+                model.disconnect();
+                Ok(())
+            }
+            IapyxCommand::Wallets(wallets) => wallets.exec(model),
             IapyxCommand::Value => {
-                if let Some(controller) = model.controller.as_mut() {
-                    println!("Total Value: {}", controller.total_value());
-                    return Ok(());
+                let output_format = model.output_format;
+                let controller = model.active_controller()?;
+                if output_format == OutputFormat::Json {
+                    return print_json(&json!({ "total_value": controller.total_value().0 }));
                 }
-                Err(IapyxCommandError::GeneralError(
-                    "wallet not recovered or generated".to_string(),
-                ))
+                println!("Total Value: {}", controller.total_value());
+                Ok(())
+            }
+            IapyxCommand::Settings => {
+                let output_format = model.output_format;
+                let settings = model.active_controller()?.settings().clone();
+                let discrimination = format!("{:?}", settings.discrimination);
+                let block0_hash = settings.block0_initial_hash.to_string();
+                if output_format == OutputFormat::Json {
+                    return print_json(&json!({
+                        "discrimination": discrimination,
+                        "block0_hash": block0_hash,
+                        "fees": {
+                            "constant": settings.fees.constant,
+                            "coefficient": settings.fees.coefficient,
+                            "certificate": settings.fees.certificate,
+                        },
+                        "block0_date": settings.block0_date.to_string(),
+                        "slot_duration": settings.slot_duration,
+                    }));
+                }
+                println!("-------------------------");
+                println!("- Discrimination: {}", discrimination);
+                println!("- Block0 hash: {}", block0_hash);
+                println!(
+                    "- Fees: constant {}, coefficient {}, certificate {}",
+                    settings.fees.constant, settings.fees.coefficient, settings.fees.certificate
+                );
+                println!("- Block0 date: {}", settings.block0_date);
+                println!("- Slot duration: {}s", settings.slot_duration);
+                println!("-------------------------");
+                Ok(())
             }
             IapyxCommand::Status => {
-                if let Some(controller) = model.controller.as_ref() {
-                    let account_state = controller.get_account_state()?;
-                    println!("-------------------------");
-                    println!("- Delegation: {:?}", account_state.delegation());
-                    println!("- Value: {}", account_state.value());
-                    println!("- Spending counter: {}", account_state.counter());
-                    println!("- Rewards: {:?}", account_state.last_rewards());
-                    println!("--------------------------");
-                    return Ok(());
+                let wallets = model.wallet_aliases();
+                let account_state = model
+                    .controller
+                    .as_ref()
+                    .map(|controller| controller.get_account_state())
+                    .transpose()?;
+                // fund may not be available (e.g. when connected directly to node)
+                let voting_power = model
+                    .controller
+                    .as_ref()
+                    .and_then(|controller| controller.voting_power_status().ok());
+
+                if model.output_format == OutputFormat::Json {
+                    return print_json(&json!({
+                        "backend": model.backend_address,
+                        "use_https_for_post": model.settings.use_https_for_post,
+                        "wallet_state": format!("{:?}", model.state),
+                        "wallets": wallets,
+                        "account": account_state,
+                        "voting_power": voting_power,
+                    }));
+                }
+                println!("-------------------------");
+                println!("- Backend: {}", model.backend_address);
+                println!("- Https for post: {}", model.settings.use_https_for_post);
+                println!("- Wallet state: {:?}", model.state);
+                println!("- Wallets:");
+                for (alias, active) in &wallets {
+                    let marker = if *active { "*" } else { " " };
+                    println!("  {} {}", marker, alias);
+                }
+                match account_state {
+                    Some(account_state) => {
+                        println!("- Delegation: {:?}", account_state.delegation());
+                        println!("- Value: {}", account_state.value());
+                        println!("- Spending counter: {}", account_state.counter());
+                        println!("- Rewards: {:?}", account_state.last_rewards());
+                    }
+                    None => println!("- Account: wallet not recovered or generated"),
+                }
+                if let Some(voting_power) = voting_power {
+                    println!("- Voting power: {}", voting_power);
                 }
-                Err(IapyxCommandError::GeneralError(
-                    "wallet not recovered or generated".to_string(),
-                ))
+                println!("--------------------------");
+                Ok(())
             }
             IapyxCommand::Refresh => {
-                if let Some(controller) = model.controller.as_mut() {
-                    controller.refresh_state()?;
-                    return Ok(());
-                }
-                Err(IapyxCommandError::GeneralError(
-                    "wallet not recovered or generated".to_string(),
-                ))
+                let controller = model.active_controller()?;
+                controller.refresh_settings()?;
+                controller.refresh_state()?;
+                model.state = WalletState::FundsRetrieved;
+                Ok(())
             }
             IapyxCommand::Address(address) => address.exec(model),
+            IapyxCommand::DeriveAddress(derive_address) => derive_address.exec(),
             IapyxCommand::Logs => {
-                if let Some(controller) = model.controller.as_mut() {
-                    println!("{:#?}", controller.fragment_logs());
-                    return Ok(());
-                }
-                Err(IapyxCommandError::GeneralError(
-                    "wallet not recovered or generated".to_string(),
-                ))
+                let controller = model.active_controller()?;
+                println!("{:#?}", controller.fragment_logs());
+                print_rejected_votes(controller)?;
+                Ok(())
             }
+            IapyxCommand::WatchLogs(watch_logs) => watch_logs.exec(model),
         }
     }
 }
 
+#[derive(StructOpt, Debug)]
+pub struct WatchLogs {
+    /// polling interval in milliseconds
+    #[structopt(long = "interval", default_value = "1000")]
+    pub interval: u64,
+
+    /// how long to watch in seconds
+    #[structopt(long = "timeout", default_value = "60")]
+    pub timeout: u64,
+}
+
+impl WatchLogs {
+    pub fn exec(&self, model: &mut UserInteractionContoller) -> Result<(), IapyxCommandError> {
+        let controller = model.active_controller()?;
+        let updates = controller.watch_fragment_logs(
+            Duration::from_millis(self.interval),
+            Duration::from_secs(self.timeout),
+        );
+        for update in updates {
+            let update = update?;
+            match update.previous {
+                Some(previous) => println!("{}: {:?} -> {:?}", update.id, previous, update.current),
+                None => println!("{}: {:?}", update.id, update.current),
+            }
+        }
+        Ok(())
+    }
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<(), IapyxCommandError> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+fn print_rejected_votes(controller: &Controller) -> Result<(), IapyxCommandError> {
+    let rejected_votes = controller.rejected_votes()?;
+    if rejected_votes.is_empty() {
+        return Ok(());
+    }
+    println!("Rejected:");
+    for (id, (fragment_id, reason)) in rejected_votes.iter().enumerate() {
+        println!("{}. {} - {}", (id + 1), fragment_id, reason);
+    }
+    println!("===================");
+    Ok(())
+}
+
+#[derive(StructOpt, Debug)]
+pub struct DeriveAddress {
+    #[structopt(short = "m", long = "mnemonics", conflicts_with = "secret")]
+    pub mnemonics: Vec<String>,
+
+    /// optional BIP39 passphrase (25th word)
+    #[structopt(long = "passphrase", default_value = "")]
+    pub passphrase: String,
+
+    /// file with bech32 encoded secret key
+    #[structopt(short = "s", long = "secret")]
+    pub secret: Option<PathBuf>,
+
+    /// use testing discrimination
+    #[structopt(short = "t", long = "testing")]
+    pub testing: bool,
+}
+
+impl DeriveAddress {
+    pub fn exec(&self) -> Result<(), IapyxCommandError> {
+        let wallet = if let Some(secret) = &self.secret {
+            let (_, data) = read_bech32(secret)?;
+            let key_bytes = Vec::<u8>::from_base32(&data).map_err(ControllerError::from)?;
+            let key: [u8; 64] = key_bytes.try_into().map_err(|_| {
+                IapyxCommandError::GeneralError("secret key should be 64 bytes long".to_string())
+            })?;
+            Wallet::recover_from_utxo(&key)?
+        } else if !self.mnemonics.is_empty() {
+            let mnemonics = self.mnemonics.join(" ");
+            validate_mnemonics(&mnemonics)?;
+            Wallet::recover(&mnemonics, self.passphrase.as_bytes())?
+        } else {
+            return Err(IapyxCommandError::GeneralError(
+                "either mnemonics or secret key should be provided".to_string(),
+            ));
+        };
+
+        let (prefix, discrimination) = if self.testing {
+            ("ta", Discrimination::Test)
+        } else {
+            ("ca", Discrimination::Production)
+        };
+        let address = AddressReadable::from_address(prefix, &wallet.account(discrimination));
+        println!("Address: {}", address.to_string());
+        Ok(())
+    }
+}
+
 #[derive(StructOpt, Debug)]
 pub struct Address {
     /// blocks execution until fragment is in block
@@ -160,56 +379,112 @@ pub struct Address {
 
 impl Address {
     pub fn exec(&self, model: &mut UserInteractionContoller) -> Result<(), IapyxCommandError> {
-        if let Some(controller) = model.controller.as_mut() {
-            let (prefix, discrimination) = {
-                if self.testing {
-                    ("ca", Discrimination::Test)
-                } else {
-                    ("ta", Discrimination::Production)
-                }
-            };
-            let address =
-                AddressReadable::from_address(prefix, &controller.account(discrimination));
-            println!("Address: {}", address.to_string());
-            return Ok(());
-        }
-        Err(IapyxCommandError::GeneralError(
-            "wallet not recovered or generated".to_string(),
-        ))
+        let controller = model.active_controller()?;
+        let (prefix, discrimination) = {
+            if self.testing {
+                ("ta", Discrimination::Test)
+            } else {
+                ("ca", Discrimination::Production)
+            }
+        };
+        let address = AddressReadable::from_address(prefix, &controller.account(discrimination));
+        println!("Address: {}", address.to_string());
+        Ok(())
     }
 }
 
 #[derive(StructOpt, Debug)]
 pub struct Vote {
-    /// choice
+    /// choice name or numeric option index
     #[structopt(short = "c", long = "choice")]
     pub choice: String,
     /// chain proposal id
     #[structopt(short = "p", long = "id")]
     pub proposal_id: String,
+    /// only prints signed vote fragment as hex without sending it
+    #[structopt(long = "no-send")]
+    pub no_send: bool,
+    /// fails before sending when chain is outside of vote plan voting window.
+    /// Requires direct access to node stats
+    #[structopt(long = "check-voting-window")]
+    pub check_voting_window: bool,
 }
 
 impl Vote {
     pub fn exec(&self, model: &mut UserInteractionContoller) -> Result<(), IapyxCommandError> {
-        if let Some(controller) = model.controller.as_mut() {
-            let proposals = controller.get_proposals()?;
-            let proposal = proposals
-                .iter()
-                .find(|x| x.chain_proposal_id_as_str() == self.proposal_id)
-                .ok_or_else(|| {
-                    IapyxCommandError::GeneralError("Cannot find proposal".to_string())
-                })?;
-            let choice = proposal
-                .chain_vote_options
-                .0
-                .get(&self.choice)
-                .ok_or_else(|| IapyxCommandError::GeneralError("wrong choice".to_string()))?;
-            controller.vote(proposal, Choice::new(*choice))?;
+        let controller = model.funded_controller()?;
+        let proposals = controller.get_proposals()?;
+        let proposal = proposals
+            .iter()
+            .find(|x| x.chain_proposal_id_as_str() == self.proposal_id)
+            .ok_or_else(|| IapyxCommandError::GeneralError("Cannot find proposal".to_string()))?;
+        let choice = resolve_choice(proposal, &self.choice)?;
+        if self.check_voting_window {
+            controller.ensure_voting_open(&proposal.chain_voteplan_id)?;
+        }
+        if self.no_send {
+            let fragment = controller.build_vote(proposal, Choice::new(choice))?;
+            println!("{}", hex::encode(fragment));
             return Ok(());
         }
-        Err(IapyxCommandError::GeneralError(
-            "wallet not recovered or generated".to_string(),
-        ))
+        controller.vote(proposal, Choice::new(choice))?;
+        Ok(())
+    }
+}
+
+/// Resolves choice either by option name or, when it is numeric, directly
+/// as option index which must fit in proposal options range
+fn resolve_choice(proposal: &Proposal, choice: &str) -> Result<u8, IapyxCommandError> {
+    let options = &proposal.chain_vote_options.0;
+    if let Some(choice) = options.get(choice) {
+        return Ok(*choice);
+    }
+    let numeric: u8 = choice
+        .parse()
+        .map_err(|_| IapyxCommandError::GeneralError("wrong choice".to_string()))?;
+    // chain options are created with length of options map, so valid indexes are 0..len
+    if numeric as usize >= options.len() {
+        return Err(IapyxCommandError::ChoiceOutOfRange {
+            choice: numeric,
+            options: options.len(),
+        });
+    }
+    Ok(numeric)
+}
+
+#[derive(StructOpt, Debug)]
+pub struct SendFunds {
+    /// receiver address
+    #[structopt(short = "a", long = "address")]
+    pub address: String,
+    /// amount of lovelace to send
+    #[structopt(short = "v", long = "value")]
+    pub value: u64,
+}
+
+impl SendFunds {
+    pub fn exec(&self, model: &mut UserInteractionContoller) -> Result<(), IapyxCommandError> {
+        let controller = model.funded_controller()?;
+        let fragment_id = controller.transfer(&self.address, self.value)?;
+        println!("Transaction sent: {}", fragment_id);
+        Ok(())
+    }
+}
+
+#[derive(StructOpt, Debug)]
+pub struct Faucet {
+    /// amount of lovelace to request
+    #[structopt(short = "v", long = "value")]
+    pub value: u64,
+}
+
+impl Faucet {
+    pub fn exec(&self, model: &mut UserInteractionContoller) -> Result<(), IapyxCommandError> {
+        let controller = model.active_controller()?;
+        let response = controller.request_funds(self.value)?;
+        model.state = WalletState::FundsRetrieved;
+        println!("Funds requested: {}", response);
+        Ok(())
     }
 }
 
@@ -225,6 +500,10 @@ pub struct Connect {
     /// uses https for sending fragments
     #[structopt(short = "d", long = "enable-debug")]
     pub enable_debug: bool,
+
+    /// root certificate used to verify https connection
+    #[structopt(long = "root-cert")]
+    pub root_cert_path: Option<PathBuf>,
 }
 
 impl Connect {
@@ -232,12 +511,16 @@ impl Connect {
         let settings = RestSettings {
             use_https_for_post: self.use_https_for_post,
             enable_debug: self.enable_debug,
+            certificate: self
+                .root_cert_path
+                .as_ref()
+                .map(load_root_certificate)
+                .transpose()?,
             ..Default::default()
         };
 
         if let Some(controller) = model.controller.as_mut() {
-            controller.switch_backend(self.address.clone(), settings);
-            return Ok(());
+            controller.switch_backend(self.address.clone(), settings.clone());
         }
 
         model.backend_address = self.address.clone();
@@ -246,6 +529,40 @@ impl Connect {
     }
 }
 
+#[derive(StructOpt, Debug)]
+pub enum Wallets {
+    /// name active wallet, so it can be switched back to later
+    Add(WalletAlias),
+    /// switch active wallet
+    Switch(WalletAlias),
+    /// list all wallets
+    List,
+}
+
+impl Wallets {
+    pub fn exec(&self, model: &mut UserInteractionContoller) -> Result<(), IapyxCommandError> {
+        match self {
+            Wallets::Add(wallet) => model.add_wallet(&wallet.alias),
+            Wallets::Switch(wallet) => model.switch_wallet(&wallet.alias),
+            Wallets::List => {
+                println!("===================");
+                for (id, (alias, active)) in model.wallet_aliases().iter().enumerate() {
+                    let marker = if *active { "*" } else { " " };
+                    println!("{}. {} {}", (id + 1), marker, alias);
+                }
+                println!("===================");
+                Ok(())
+            }
+        }
+    }
+}
+
+#[derive(StructOpt, Debug)]
+pub struct WalletAlias {
+    #[structopt(short = "a", long = "alias")]
+    pub alias: String,
+}
+
 #[derive(StructOpt, Debug)]
 pub enum Recover {
     /// recover wallet funds from mnemonic
@@ -274,12 +591,12 @@ pub struct RecoverFromSecretKey {
 
 impl RecoverFromSecretKey {
     pub fn exec(&self, model: &mut UserInteractionContoller) -> Result<(), IapyxCommandError> {
-        model.controller = Some(Controller::recover_from_sk(
+        let controller = Controller::recover_from_sk(
             model.backend_address.clone(),
             &self.input,
             model.settings.clone(),
-        )?);
-        model.state = WalletState::Recovered;
+        )?;
+        model.set_active_wallet(controller, WalletState::Recovered);
         Ok(())
     }
 }
@@ -295,13 +612,13 @@ pub struct RecoverFromQr {
 
 impl RecoverFromQr {
     pub fn exec(&self, model: &mut UserInteractionContoller) -> Result<(), IapyxCommandError> {
-        model.controller = Some(Controller::recover_from_qr(
+        let controller = Controller::recover_from_qr(
             model.backend_address.clone(),
             &self.qr_code,
             &self.password,
             model.settings.clone(),
-        )?);
-        model.state = WalletState::Recovered;
+        )?;
+        model.set_active_wallet(controller, WalletState::Recovered);
         Ok(())
     }
 }
@@ -310,17 +627,24 @@ impl RecoverFromQr {
 pub struct RecoverFromMnemonics {
     #[structopt(short = "m", long = "mnemonics")]
     pub mnemonics: Vec<String>,
+
+    /// optional BIP39 passphrase (25th word)
+    #[structopt(long = "passphrase", default_value = "")]
+    pub passphrase: String,
 }
 
 impl RecoverFromMnemonics {
     pub fn exec(&self, model: &mut UserInteractionContoller) -> Result<(), IapyxCommandError> {
-        model.controller = Some(Controller::recover(
+        let mnemonics = self.mnemonics.join(" ");
+        validate_mnemonics(&mnemonics)?;
+
+        let controller = Controller::recover(
             model.backend_address.clone(),
-            &self.mnemonics.join(" "),
-            &[],
+            &mnemonics,
+            self.passphrase.as_bytes(),
             model.settings.clone(),
-        )?);
-        model.state = WalletState::Recovered;
+        )?;
+        model.set_active_wallet(controller, WalletState::Recovered);
         Ok(())
     }
 }
@@ -334,12 +658,12 @@ pub struct Generate {
 
 impl Generate {
     pub fn exec(&self, model: &mut UserInteractionContoller) -> Result<(), IapyxCommandError> {
-        model.controller = Some(Controller::generate(
+        let controller = Controller::generate(
             model.backend_address.clone(),
             Type::from_word_count(self.count)?,
             model.settings.clone(),
-        )?);
-        model.state = WalletState::Generated;
+        )?;
+        model.set_active_wallet(controller, WalletState::Generated);
         Ok(())
     }
 }
@@ -349,8 +673,20 @@ impl Generate {
 pub enum IapyxCommandError {
     #[error("{0}")]
     GeneralError(String),
+    #[error("choice {choice} is out of range, proposal has {options} options")]
+    ChoiceOutOfRange { choice: u8, options: usize },
     #[error("{0}")]
     ControllerError(#[from] crate::controller::ControllerError),
+    #[error("{0}")]
+    WalletError(#[from] crate::WalletError),
     #[error("wrong word count for generating wallet")]
     GenerateWalletError(#[from] bip39::Error),
+    #[error("{0}")]
+    InvalidMnemonics(#[from] MnemonicsError),
+    #[error("{0}")]
+    BackendError(#[from] crate::WalletBackendError),
+    #[error("io error")]
+    IoError(#[from] std::io::Error),
+    #[error("serialization error")]
+    SerializationError(#[from] serde_json::Error),
 }
@@ -4,7 +4,10 @@ use crate::Controller;
 pub use command::{IapyxCommand, IapyxCommandError};
 use jormungandr_testing_utils::testing::node::RestSettings;
 use jortestkit::prelude::{ConsoleWriter, InteractiveCommandError, InteractiveCommandExec};
+use std::collections::HashMap;
 use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use structopt::StructOpt;
 
 #[derive(Debug, Copy, Clone)]
@@ -15,10 +18,110 @@ pub enum WalletState {
     FundsRetrieved,
 }
 
+#[derive(StructOpt, Debug)]
+pub struct IapyxInteractiveCommand {
+    /// file with newline separated commands to run instead of interactive console
+    #[structopt(long = "script")]
+    pub script: Option<PathBuf>,
+
+    /// do not abort script on first failed command
+    #[structopt(long = "continue-on-error")]
+    pub continue_on_error: bool,
+
+    /// output format: text or json
+    #[structopt(long = "output", default_value = "text")]
+    pub output_format: OutputFormat,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = IapyxCommandError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            other => Err(IapyxCommandError::GeneralError(format!(
+                "unknown output format: '{}'",
+                other
+            ))),
+        }
+    }
+}
+
 pub struct IapyxInteractiveCommandExec {
     pub controller: UserInteractionContoller,
 }
 
+impl IapyxInteractiveCommandExec {
+    /// Runs each line of script as separate command. Empty lines and lines
+    /// starting with '#' are skipped
+    pub fn run_script<P: AsRef<Path>>(
+        &mut self,
+        script: P,
+        continue_on_error: bool,
+    ) -> Result<ScriptSummary, IapyxCommandError> {
+        let content = std::fs::read_to_string(script.as_ref())?;
+        let mut summary: ScriptSummary = Default::default();
+
+        for (line_no, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            println!("> {}", line);
+
+            let result = match IapyxCommand::from_iter_safe(line.split_whitespace()) {
+                Ok(IapyxCommand::Exit) => break,
+                Ok(command) => command.exec(&mut self.controller),
+                Err(err) => Err(IapyxCommandError::GeneralError(err.to_string())),
+            };
+
+            match result {
+                Ok(()) => summary.succeeded += 1,
+                Err(err) => {
+                    println!("{:?}", err);
+                    summary
+                        .failed
+                        .push((line_no + 1, line.to_string(), err.to_string()));
+                    if !continue_on_error {
+                        break;
+                    }
+                }
+            }
+        }
+        Ok(summary)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ScriptSummary {
+    pub succeeded: usize,
+    /// line number, command and error message of each failed command
+    pub failed: Vec<(usize, String, String)>,
+}
+
+impl ScriptSummary {
+    pub fn is_success(&self) -> bool {
+        self.failed.is_empty()
+    }
+
+    pub fn print(&self) {
+        println!("===================");
+        println!("Succeeded: {}", self.succeeded);
+        println!("Failed: {}", self.failed.len());
+        for (line_no, command, error) in &self.failed {
+            println!("line {}: '{}' -> {}", line_no, command, error);
+        }
+        println!("===================");
+    }
+}
+
 impl InteractiveCommandExec for IapyxInteractiveCommandExec {
     fn parse_and_exec(
         &mut self,
@@ -41,6 +144,107 @@ impl InteractiveCommandExec for IapyxInteractiveCommandExec {
 pub struct UserInteractionContoller {
     pub state: WalletState,
     pub controller: Option<Controller>,
+    pub alias: Option<String>,
+    pub wallets: HashMap<String, (WalletState, Controller)>,
+    pub output_format: OutputFormat,
     pub backend_address: String,
     pub settings: RestSettings,
 }
+
+impl UserInteractionContoller {
+    /// Replaces active wallet. Previous one is kept in registry if it was named
+    pub fn set_active_wallet(&mut self, controller: Controller, state: WalletState) {
+        self.park_active_wallet();
+        self.controller = Some(controller);
+        self.state = state;
+    }
+
+    /// Returns active wallet or error explaining what needs to be done first
+    pub fn active_controller(&mut self) -> Result<&mut Controller, IapyxCommandError> {
+        match self.controller.as_mut() {
+            Some(controller) => Ok(controller),
+            None => Err(IapyxCommandError::GeneralError(format!(
+                "no active wallet, recover or generate one first (backend: {}, use 'connect' to change it)",
+                self.backend_address
+            ))),
+        }
+    }
+
+    /// Returns active wallet only if its funds were already retrieved from backend
+    pub fn funded_controller(&mut self) -> Result<&mut Controller, IapyxCommandError> {
+        match self.state {
+            WalletState::Recovered | WalletState::Generated => {
+                Err(IapyxCommandError::GeneralError(
+                    "wallet funds not retrieved yet, use 'refresh' first".to_string(),
+                ))
+            }
+            WalletState::New | WalletState::FundsRetrieved => self.active_controller(),
+        }
+    }
+
+    /// Names active wallet, so it is not lost when switching to other wallet
+    pub fn add_wallet(&mut self, alias: &str) -> Result<(), IapyxCommandError> {
+        self.active_controller()?;
+        if self.wallets.contains_key(alias) || self.alias.as_deref() == Some(alias) {
+            return Err(IapyxCommandError::GeneralError(format!(
+                "wallet '{}' already exists",
+                alias
+            )));
+        }
+        self.alias = Some(alias.to_string());
+        Ok(())
+    }
+
+    pub fn switch_wallet(&mut self, alias: &str) -> Result<(), IapyxCommandError> {
+        if self.alias.as_deref() == Some(alias) {
+            return Ok(());
+        }
+        if !self.wallets.contains_key(alias) {
+            return Err(IapyxCommandError::GeneralError(format!(
+                "cannot find wallet '{}'",
+                alias
+            )));
+        }
+        if self.controller.is_some() && self.alias.is_none() {
+            return Err(IapyxCommandError::GeneralError(
+                "active wallet has no alias, add it first".to_string(),
+            ));
+        }
+        self.park_active_wallet();
+        let (state, controller) = self.wallets.remove(alias).unwrap();
+        self.controller = Some(controller);
+        self.state = state;
+        self.alias = Some(alias.to_string());
+        Ok(())
+    }
+
+    /// Returns all wallet aliases and flag indicating if wallet is active
+    pub fn wallet_aliases(&self) -> Vec<(String, bool)> {
+        let mut aliases: Vec<(String, bool)> = self
+            .wallets
+            .keys()
+            .cloned()
+            .map(|alias| (alias, false))
+            .collect();
+        if let Some(alias) = &self.alias {
+            aliases.push((alias.clone(), true));
+        }
+        aliases.sort();
+        aliases
+    }
+
+    /// Drops active and all named wallets
+    pub fn disconnect(&mut self) {
+        self.controller = None;
+        self.alias = None;
+        self.wallets.clear();
+        self.state = WalletState::New;
+    }
+
+    fn park_active_wallet(&mut self) {
+        if let (Some(alias), Some(controller)) = (self.alias.take(), self.controller.take()) {
+            self.wallets.insert(alias, (self.state, controller));
+        }
+        self.state = WalletState::New;
+    }
+}
@@ -45,6 +45,10 @@ pub struct ConstDurationIapyxLoadCommand {
     #[structopt(short = "h", long = "https")]
     pub use_https_for_post: bool,
 
+    /// root certificate used to verify https connection
+    #[structopt(long = "root-cert")]
+    pub root_cert_path: Option<PathBuf>,
+
     /// use https for sending fragments
     #[structopt(short = "d", long = "debug")]
     pub debug: bool,
@@ -103,6 +107,7 @@ impl ConstDurationIapyxLoadCommand {
             global_pin: self.global_pin.clone(),
             read_pin_from_filename: self.read_pin_from_filename,
             use_https_for_post: self.use_https_for_post,
+            root_cert_path: self.root_cert_path.clone(),
             debug: self.debug,
         }
     }
@@ -1,4 +1,4 @@
-use crate::backend::ProxyServerStub;
+use crate::backend::{BodyLimits, ProxyRecorder, ProxyServerStub};
 use std::path::PathBuf;
 use structopt::StructOpt;
 use thiserror::Error;
@@ -13,6 +13,10 @@ pub enum IapyxProxyCommandError {
     CertFileDoesNotExist,
     #[error("key file does not exists")]
     KeyFileDoesNotExist,
+    #[error("block0 file does not exists: {0:?}")]
+    Block0FileDoesNotExist(PathBuf),
+    #[error("cannot set up recording")]
+    RecorderError(#[from] crate::backend::ProxyRecorderError),
 }
 
 #[derive(StructOpt, Debug)]
@@ -26,6 +30,14 @@ pub struct IapyxProxyCommand {
     #[structopt(short = "n", long = "node-address", default_value = "127.0.0.1:8080")]
     pub node_address: String,
 
+    /// overrides Host header sent to vit station
+    #[structopt(long = "vit-host")]
+    pub vit_host: Option<String>,
+
+    /// overrides Host header sent to node rest api
+    #[structopt(long = "node-host")]
+    pub node_host: Option<String>,
+
     #[structopt(short = "b", long = "block0")]
     pub block0_path: PathBuf,
 
@@ -34,6 +46,33 @@ pub struct IapyxProxyCommand {
 
     #[structopt(long = "key")]
     pub key_path: Option<PathBuf>,
+
+    /// records every proxied request/response pair as json file in given directory
+    #[structopt(long = "record")]
+    pub record: Option<PathBuf>,
+
+    /// header which value should be redacted in recordings (can be repeated)
+    #[structopt(long = "redact-header")]
+    pub redact_headers: Vec<String>,
+
+    /// prefix of request path (e.g. /api/v0/tally/stream) forwarded to vit station
+    /// without buffering. Used for server-sent events and websockets (can be repeated)
+    #[structopt(long = "streaming-path")]
+    pub streaming_paths: Vec<String>,
+
+    /// maximum size of request body in bytes, larger requests are rejected with 413
+    #[structopt(long = "max-request-body", default_value = "16777216")]
+    pub max_request_body: u64,
+
+    /// maximum size of upstream response body in bytes, larger responses are replaced with 502
+    #[structopt(long = "max-response-body", default_value = "134217728")]
+    pub max_response_body: u64,
+
+    /// additional block0 served for given network, in format `{name}={block0 path}`.
+    /// Network is selected by `/{name}/api/v0/block0` path or Host header `{name}.*`
+    /// (can be repeated)
+    #[structopt(long = "network", parse(try_from_str = parse_network))]
+    pub networks: Vec<(String, PathBuf)>,
 }
 
 impl IapyxProxyCommand {
@@ -42,8 +81,13 @@ impl IapyxProxyCommand {
         let vit_address = self.vit_address.clone();
         let node_address = self.node_address.clone();
         let block0_path = self.block0_path.clone();
+        let recorder = self
+            .record
+            .as_ref()
+            .map(|dir| ProxyRecorder::new(dir, self.redact_headers.clone()))
+            .transpose()?;
 
-        if let Some(cert_path) = &self.cert_path {
+        let server_stub = if let Some(cert_path) = &self.cert_path {
             let key_path = self
                 .key_path
                 .clone()
@@ -57,21 +101,57 @@ impl IapyxProxyCommand {
                 return Err(IapyxProxyCommandError::CertFileDoesNotExist);
             }
 
-            return Ok(ProxyServerStub::new_https(
+            ProxyServerStub::new_https(
                 key_path,
                 cert_path.to_path_buf(),
                 proxy_address,
                 vit_address,
                 node_address,
                 jortestkit::file::get_file_as_byte_vec(&block0_path),
-            ));
+            )
+        } else {
+            ProxyServerStub::new_http(
+                proxy_address,
+                vit_address,
+                node_address,
+                jortestkit::file::get_file_as_byte_vec(&block0_path),
+            )
+        };
+
+        let mut server_stub = server_stub
+            .with_vit_host(self.vit_host.clone())
+            .with_node_host(self.node_host.clone())
+            .with_recorder(recorder)
+            .with_streaming_paths(self.streaming_paths.clone())
+            .with_body_limits(self.body_limits());
+
+        for (name, path) in &self.networks {
+            if !path.exists() {
+                return Err(IapyxProxyCommandError::Block0FileDoesNotExist(path.clone()));
+            }
+            server_stub = server_stub
+                .with_network(name.clone(), jortestkit::file::get_file_as_byte_vec(path));
+        }
+        Ok(server_stub)
+    }
+
+    fn body_limits(&self) -> BodyLimits {
+        BodyLimits {
+            max_request_body: self.max_request_body,
+            max_response_body: self.max_response_body,
         }
+    }
+}
 
-        Ok(ProxyServerStub::new_http(
-            proxy_address,
-            vit_address,
-            node_address,
-            jortestkit::file::get_file_as_byte_vec(&block0_path),
-        ))
+fn parse_network(value: &str) -> Result<(String, PathBuf), String> {
+    let mut parts = value.splitn(2, '=');
+    match (parts.next(), parts.next()) {
+        (Some(name), Some(path)) if !name.is_empty() && !path.is_empty() => {
+            Ok((name.to_string(), PathBuf::from(path)))
+        }
+        _ => Err(format!(
+            "expected network in format {{name}}={{block0 path}}, got: {}",
+            value
+        )),
     }
 }
@@ -1,9 +1,12 @@
 mod address;
+mod pin;
 mod secret;
 mod verify;
 use crate::cli::args::qr::secret::GetSecretFromQrCommand;
 use address::GetAddressFromQrCommand;
 use jormungandr_lib::interfaces::Block0ConfigurationError;
+use pin::CheckPinQrCommand;
+use std::path::PathBuf;
 use structopt::StructOpt;
 use thiserror::Error;
 use verify::VerifyQrCommand;
@@ -13,6 +16,8 @@ pub enum IapyxQrCommand {
     Verify(VerifyQrCommand),
     CheckAddress(GetAddressFromQrCommand),
     Secret(GetSecretFromQrCommand),
+    /// check qr code is readable and find which of candidate pins decodes it
+    CheckPin(CheckPinQrCommand),
 }
 
 impl IapyxQrCommand {
@@ -21,6 +26,7 @@ impl IapyxQrCommand {
             Self::Verify(verify) => verify.exec(),
             Self::CheckAddress(check_address) => check_address.exec(),
             Self::Secret(secret) => secret.exec(),
+            Self::CheckPin(check_pin) => check_pin.exec(),
         }
     }
 }
@@ -41,4 +47,10 @@ pub enum IapyxQrCommandError {
     ReadError(#[from] chain_core::mempack::ReadError),
     #[error("bech32 error")]
     Bech32Error(#[from] bech32::Error),
+    #[error("no candidate pins given, use --pin or --pins-file")]
+    NoCandidatePins,
+    #[error("qr code {0:?} is readable, but none of candidate pins matches")]
+    NoMatchingPin(PathBuf),
+    #[error("qr code {0:?} is not decodable: {1}")]
+    QrNotDecodable(PathBuf, crate::qr::PinReadError),
 }
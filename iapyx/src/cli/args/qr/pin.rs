@@ -0,0 +1,72 @@
+use crate::cli::args::qr::IapyxQrCommandError;
+use crate::{find_pin, is_numeric_pin};
+use chain_addr::{AddressReadable, Discrimination, Kind};
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+/// Checks that qr code is readable and reports which of candidate pins decodes it
+#[derive(StructOpt, Debug)]
+pub struct CheckPinQrCommand {
+    #[structopt(long = "qr")]
+    pub qr: PathBuf,
+
+    /// candidate pin (can be repeated)
+    #[structopt(short = "p", long = "pin")]
+    pub pins: Vec<String>,
+
+    /// file with candidate pins, one per line
+    #[structopt(long = "pins-file")]
+    pub pins_file: Option<PathBuf>,
+
+    #[structopt(short = "t", long = "testing")]
+    pub testing: bool,
+}
+
+impl CheckPinQrCommand {
+    pub fn exec(&self) -> Result<(), IapyxQrCommandError> {
+        let mut pins = self.pins.clone();
+        if let Some(pins_file) = &self.pins_file {
+            pins.extend(
+                std::fs::read_to_string(pins_file)?
+                    .lines()
+                    .map(|line| line.trim().to_string())
+                    .filter(|line| !line.is_empty()),
+            );
+        }
+        if pins.is_empty() {
+            return Err(IapyxQrCommandError::NoCandidatePins);
+        }
+
+        for pin in pins.iter().filter(|pin| !is_numeric_pin(pin)) {
+            println!(
+                "skipping pin candidate {:?}: pin can contain digits only",
+                pin
+            );
+        }
+
+        println!(
+            "Decoding qr from file: {:?} with {} candidate pin(s)...",
+            self.qr,
+            pins.len()
+        );
+        let (pin, secret) = find_pin(&self.qr, &pins)
+            .map_err(|err| IapyxQrCommandError::QrNotDecodable(self.qr.clone(), err))?
+            .ok_or_else(|| IapyxQrCommandError::NoMatchingPin(self.qr.clone()))?;
+
+        let (discrimination, prefix) = if self.testing {
+            (Discrimination::Test, "ta")
+        } else {
+            (Discrimination::Production, "ca")
+        };
+        let public_key = secret.to_public();
+        let address = chain_addr::Address(discrimination, Kind::Account(public_key.clone()));
+
+        println!("pin: {}", pin);
+        println!("account id: {}", hex::encode(public_key.as_ref()));
+        println!(
+            "address: {}",
+            AddressReadable::from_address(prefix, &address).to_string()
+        );
+        Ok(())
+    }
+}
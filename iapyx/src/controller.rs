@@ -1,16 +1,27 @@
+use crate::ProposalsDiff;
 use crate::SimpleVoteStatus;
+use crate::Utxo;
+use crate::VoteReceipt;
+use crate::VotingPowerStatus;
 use crate::Wallet;
 use crate::{data::Proposal as VitProposal, WalletBackend};
 use bech32::FromBase32;
 use bip39::Type;
-use chain_impl_mockchain::{fragment::FragmentId, transaction::Input};
-use jormungandr_lib::interfaces::{AccountState, FragmentLog, FragmentStatus};
+use chain_addr::{AddressReadable, Discrimination, Kind};
+use chain_core::property::Fragment as _;
+use chain_impl_mockchain::{
+    fragment::{Fragment, FragmentId},
+    transaction::Input,
+};
+use jormungandr_lib::interfaces::{AccountState, BlockDate, FragmentLog, FragmentStatus};
 use jormungandr_testing_utils::qr_code::KeyQrCode;
 use jormungandr_testing_utils::testing::node::RestSettings;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::collections::VecDeque;
 use std::convert::TryInto;
 use std::path::Path;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 use wallet::{AccountId, Settings};
 use wallet_core::{Choice, Value};
@@ -18,22 +29,36 @@ use wallet_core::{Choice, Value};
 pub struct Controller {
     backend: WalletBackend,
     wallet: Wallet,
+    /// blockchain settings (fees, discrimination, block0 hash) fetched from backend once,
+    /// when controller is created. Use `refresh_settings` if backend settings changed
     settings: Settings,
+    receipts: Vec<VoteReceipt>,
+    reference_backend: Option<WalletBackend>,
+    counter_mismatch_retry: Option<Duration>,
+    check_voting_window: bool,
 }
 
 impl Controller {
+    fn new_inner(backend: WalletBackend, wallet: Wallet) -> Result<Self, ControllerError> {
+        let settings = backend.settings()?;
+        Ok(Self {
+            backend,
+            wallet,
+            settings,
+            receipts: Vec::new(),
+            reference_backend: None,
+            counter_mismatch_retry: None,
+            check_voting_window: false,
+        })
+    }
+
     pub fn generate(
         proxy_address: String,
         words_length: Type,
         backend_settings: RestSettings,
     ) -> Result<Self, ControllerError> {
         let backend = WalletBackend::new(proxy_address, backend_settings);
-        let settings = backend.settings()?;
-        Ok(Self {
-            backend,
-            wallet: Wallet::generate(words_length)?,
-            settings,
-        })
+        Self::new_inner(backend, Wallet::generate(words_length)?)
     }
 
     pub fn recover_with_backend(
@@ -41,12 +66,7 @@ impl Controller {
         mnemonics: &str,
         password: &[u8],
     ) -> Result<Self, ControllerError> {
-        let settings = backend.settings()?;
-        Ok(Self {
-            backend,
-            wallet: Wallet::recover(mnemonics, password)?,
-            settings,
-        })
+        Self::new_inner(backend, Wallet::recover(mnemonics, password)?)
     }
 
     pub fn recover(
@@ -65,12 +85,7 @@ impl Controller {
         backend_settings: RestSettings,
     ) -> Result<Self, ControllerError> {
         let backend = WalletBackend::new(proxy_address, backend_settings);
-        let settings = backend.settings()?;
-        Ok(Self {
-            backend,
-            wallet: Wallet::recover_from_account(account)?,
-            settings,
-        })
+        Self::new_inner(backend, Wallet::recover_from_account(account)?)
     }
 
     pub fn recover_from_qr<P: AsRef<Path>>(
@@ -91,35 +106,73 @@ impl Controller {
             .clone()
             .leak_secret();
         let backend = WalletBackend::new(proxy_address, backend_settings);
-        let settings = backend.settings()?;
-        Ok(Self {
+        Self::new_inner(
             backend,
-            wallet: Wallet::recover_from_utxo(secret.as_ref().try_into().unwrap())?,
-            settings,
-        })
+            Wallet::recover_from_utxo(secret.as_ref().try_into().unwrap())?,
+        )
     }
 
     pub fn recover_from_sk<P: AsRef<Path>>(
         proxy_address: String,
         private_key: P,
         backend_settings: RestSettings,
+    ) -> Result<Self, ControllerError> {
+        let backend = WalletBackend::new(proxy_address, backend_settings);
+        Self::recover_from_sk_with_backend(backend, private_key)
+    }
+
+    pub fn recover_from_sk_with_backend<P: AsRef<Path>>(
+        backend: WalletBackend,
+        private_key: P,
     ) -> Result<Self, ControllerError> {
         let (_, data) = read_bech32(private_key)?;
         let key_bytes = Vec::<u8>::from_base32(&data)?;
         let data: [u8; 64] = key_bytes.try_into().unwrap();
-        let backend = WalletBackend::new(proxy_address, backend_settings);
-        let settings = backend.settings()?;
-        Ok(Self {
-            backend,
-            wallet: Wallet::recover_from_utxo(&data)?,
-            settings,
-        })
+        Self::new_inner(backend, Wallet::recover_from_utxo(&data)?)
     }
 
     pub fn switch_backend(&mut self, proxy_address: String, backend_settings: RestSettings) {
         self.backend = WalletBackend::new(proxy_address, backend_settings);
     }
 
+    /// Custom headers sent with every request to vit station and proxy. Node requests are sent
+    /// without them, see `WalletBackend::set_headers`
+    pub fn set_backend_headers(
+        &mut self,
+        headers: &HashMap<String, String>,
+    ) -> Result<(), ControllerError> {
+        Ok(self.backend.set_headers(headers)?)
+    }
+
+    /// Fetches blockchain settings from backend again, replacing cached ones.
+    /// Votes and transfers are always built with cached settings
+    pub fn refresh_settings(&mut self) -> Result<(), ControllerError> {
+        self.settings = self.backend.settings()?;
+        Ok(())
+    }
+
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    /// Second backend (e.g. vit station accessed directly instead of through proxy),
+    /// whose responses are compared with main backend ones
+    pub fn set_reference_backend(&mut self, address: String, backend_settings: RestSettings) {
+        self.reference_backend = Some(WalletBackend::new(address, backend_settings));
+    }
+
+    /// Compares proposals served by main and reference backend
+    pub fn diff_proposals(&self) -> Result<ProposalsDiff, ControllerError> {
+        let reference = self
+            .reference_backend
+            .as_ref()
+            .ok_or(ControllerError::NoReferenceBackend)?;
+        Ok(ProposalsDiff::new(
+            &self.backend.proposals()?,
+            &reference.proposals()?,
+        ))
+    }
+
     pub fn account(&self, discrimination: chain_addr::Discrimination) -> chain_addr::Address {
         self.wallet.account(discrimination)
     }
@@ -134,6 +187,21 @@ impl Controller {
             .map_err(Into::into)
     }
 
+    /// Sends fragments, up to `parallelism` at the same time. Returned ids match input order
+    pub fn send_fragments(
+        &self,
+        transactions: Vec<Vec<u8>>,
+        parallelism: usize,
+    ) -> Result<Vec<FragmentId>, ControllerError> {
+        if parallelism > 1 {
+            Ok(self
+                .backend
+                .send_fragments_concurrently(transactions, parallelism)?)
+        } else {
+            Ok(self.backend.send_fragments(transactions)?)
+        }
+    }
+
     pub fn confirm_all_transactions(&mut self) {
         self.wallet.confirm_all_transactions();
     }
@@ -186,6 +254,88 @@ impl Controller {
         }
     }
 
+    /// Returns vote start and tally start dates of given vote plan
+    pub fn vote_plan_timing(
+        &self,
+        vote_plan_id: &str,
+    ) -> Result<(BlockDate, BlockDate), ControllerError> {
+        self.backend
+            .vote_plan_statuses()?
+            .into_iter()
+            .find(|vote_plan| vote_plan.id.to_string() == vote_plan_id)
+            .map(|vote_plan| (vote_plan.vote_start, vote_plan.vote_end))
+            .ok_or_else(|| ControllerError::VotePlanNotFound(vote_plan_id.to_string()))
+    }
+
+    /// Blocks until chain reaches vote start of given vote plan
+    pub fn wait_for_voting_open(
+        &self,
+        vote_plan_id: &str,
+        pace: std::time::Duration,
+        timeout: std::time::Duration,
+    ) -> Result<(), ControllerError> {
+        let (vote_start, _) = self.vote_plan_timing(vote_plan_id)?;
+        self.wait_for_block_date(vote_start, pace, timeout)
+    }
+
+    /// Blocks until chain reaches tally start of given vote plan
+    pub fn wait_for_tally_open(
+        &self,
+        vote_plan_id: &str,
+        pace: std::time::Duration,
+        timeout: std::time::Duration,
+    ) -> Result<(), ControllerError> {
+        let (_, tally_start) = self.vote_plan_timing(vote_plan_id)?;
+        self.wait_for_block_date(tally_start, pace, timeout)
+    }
+
+    /// Opt-in mode in which `vote` calls `ensure_voting_open` before building vote.
+    /// It costs two additional requests per vote and needs node stats endpoint,
+    /// which is not exposed by wallet proxy
+    pub fn check_voting_window(&mut self, enabled: bool) -> &mut Self {
+        self.check_voting_window = enabled;
+        self
+    }
+
+    /// Fails if chain is before vote start or after tally start of given vote plan
+    pub fn ensure_voting_open(&self, vote_plan_id: &str) -> Result<(), ControllerError> {
+        let (vote_start, tally_start) = self.vote_plan_timing(vote_plan_id)?;
+        let current = self.backend.last_block_date()?;
+        if current < vote_start {
+            return Err(ControllerError::VotingNotOpen {
+                opens_at: vote_start.to_string(),
+            });
+        }
+        if current >= tally_start {
+            return Err(ControllerError::VotingClosed {
+                closed_at: tally_start.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    fn wait_for_block_date(
+        &self,
+        target: BlockDate,
+        pace: std::time::Duration,
+        timeout: std::time::Duration,
+    ) -> Result<(), ControllerError> {
+        let started = std::time::Instant::now();
+        loop {
+            let current = self.backend.last_block_date()?;
+            if current >= target {
+                return Ok(());
+            }
+            if started.elapsed() > timeout {
+                return Err(ControllerError::BlockDateNotReached {
+                    target: target.to_string(),
+                    current: current.to_string(),
+                });
+            }
+            std::thread::sleep(pace);
+        }
+    }
+
     pub fn remove_pending_transaction(&mut self, id: &FragmentId) -> Option<Vec<Input>> {
         self.wallet.remove_pending_transaction(id)
     }
@@ -194,6 +344,12 @@ impl Controller {
         self.wallet.total_value()
     }
 
+    /// Compares wallet funds with voting power threshold of current fund
+    pub fn voting_power_status(&self) -> Result<VotingPowerStatus, ControllerError> {
+        let threshold = self.backend.funds()?.voting_power_threshold as u64;
+        Ok(VotingPowerStatus::new(self.total_value().0, threshold))
+    }
+
     pub fn refresh_state(&mut self) -> Result<(), ControllerError> {
         let account_state = self.get_account_state()?;
         let value: u64 = (*account_state.value()).into();
@@ -223,12 +379,49 @@ impl Controller {
                 proposal_index,
             })?;
 
-        let transaction = self.wallet.vote(
-            self.settings.clone(),
-            &proposal.clone().into(),
-            Choice::new(choice),
-        )?;
-        Ok(self.backend.send_fragment(transaction.to_vec())?)
+        let fragment_id = self.vote(proposal, Choice::new(choice))?;
+        match self.counter_mismatch_retry {
+            Some(wait) if self.is_rejected_due_to_counter(&fragment_id, wait)? => {
+                self.remove_pending_transaction(&fragment_id);
+                // receipt of rejected vote is replaced by the one of retried vote
+                let rejected = fragment_id.to_string();
+                self.receipts
+                    .retain(|receipt| receipt.fragment_id != rejected);
+                self.refresh_state()?;
+                self.vote(proposal, Choice::new(choice))
+            }
+            _ => Ok(fragment_id),
+        }
+    }
+
+    /// Opt-in mode in which `vote_for` waits up to `wait` for vote fragment status and,
+    /// if it was rejected due to stale spending counter, refreshes wallet state and sends
+    /// vote once again. Returned fragment id is the one of the last sent vote
+    pub fn retry_on_counter_mismatch(&mut self, wait: Duration) -> &mut Self {
+        self.counter_mismatch_retry = Some(wait);
+        self
+    }
+
+    fn is_rejected_due_to_counter(
+        &self,
+        fragment_id: &FragmentId,
+        wait: Duration,
+    ) -> Result<bool, ControllerError> {
+        let started = Instant::now();
+        loop {
+            match self
+                .fragment_logs()?
+                .get(fragment_id)
+                .map(|log| log.status().clone())
+            {
+                Some(FragmentStatus::Rejected { reason }) => {
+                    return Ok(is_counter_mismatch(&reason))
+                }
+                Some(FragmentStatus::InABlock { .. }) => return Ok(false),
+                _ if started.elapsed() > wait => return Ok(false),
+                _ => std::thread::sleep(Duration::from_millis(500)),
+            }
+        }
     }
 
     pub fn vote(
@@ -236,12 +429,101 @@ impl Controller {
         proposal: &VitProposal,
         choice: Choice,
     ) -> Result<FragmentId, ControllerError> {
-        let transaction =
-            self.wallet
-                .vote(self.settings.clone(), &proposal.clone().into(), choice)?;
+        if self.check_voting_window {
+            self.ensure_voting_open(&proposal.chain_voteplan_id)?;
+        }
+        let transaction = self
+            .wallet
+            .vote(self.settings.clone(), &proposal.clone().into(), choice)?
+            .to_vec();
+        let fragment_id = self.send_raw(transaction)?;
+        self.receipts.push(VoteReceipt {
+            chain_proposal_id: proposal.chain_proposal_id_as_str(),
+            chain_voteplan_id: proposal.chain_voteplan_id.clone(),
+            chain_proposal_index: proposal.chain_proposal_index,
+            choice: choice.as_byte(),
+            fragment_id: fragment_id.to_string(),
+            timestamp: chrono::Utc::now(),
+        });
+        Ok(fragment_id)
+    }
+
+    /// Builds and signs vote fragment without sending it. Wallet state (spending counter,
+    /// pending transactions) is not changed, so fragment can be submitted later using
+    /// `send_raw`, as long as no other transaction is sent from wallet meanwhile
+    pub fn build_vote(
+        &mut self,
+        proposal: &VitProposal,
+        choice: Choice,
+    ) -> Result<Vec<u8>, ControllerError> {
+        Ok(self
+            .wallet
+            .vote_detached(self.settings.clone(), &proposal.clone().into(), choice)?
+            .to_vec())
+    }
+
+    pub fn send_raw(&self, fragment: Vec<u8>) -> Result<FragmentId, ControllerError> {
+        Ok(self.backend.send_fragment(fragment)?)
+    }
+
+    pub fn vote_receipts(&self) -> &[VoteReceipt] {
+        &self.receipts
+    }
+
+    /// Sends funds from wallet account. Like vote, transfer advances spending counter and
+    /// stays in pending transactions until confirmed
+    pub fn transfer(&mut self, address: &str, value: u64) -> Result<FragmentId, ControllerError> {
+        let address = AddressReadable::from_string_anyprefix(address)
+            .map_err(|_| ControllerError::InvalidAddress(address.to_string()))?
+            .to_address();
+        let transaction = self
+            .wallet
+            .transfer(self.settings.clone(), address, Value(value))?;
         Ok(self.backend.send_fragment(transaction.to_vec())?)
     }
 
+    /// Requests funds from backend faucet to wallet account and refreshes wallet state
+    pub fn request_funds(&mut self, value: u64) -> Result<String, ControllerError> {
+        let discrimination = self.settings.discrimination;
+        let address =
+            AddressReadable::from_address(prefix(discrimination), &self.account(discrimination));
+        let response = self.backend.request_funds(&address.to_string(), value)?;
+        self.refresh_state()?;
+        Ok(response)
+    }
+
+    /// Snapshot of utxos assigned to wallet key in block0. Node is not queried, so utxos
+    /// spent since genesis are still listed. For inspection only: it must not be used to
+    /// build transaction inputs (transfers spend from account, see `transfer`)
+    pub fn block0_utxos(&self) -> Result<Vec<Utxo>, ControllerError> {
+        let discrimination = self.settings.discrimination;
+        let public_key = self.account(discrimination).public_key().cloned();
+        let block0 = self.backend.block0_block()?;
+
+        let mut utxos = Vec::new();
+        for fragment in block0.fragments() {
+            if let Fragment::Transaction(transaction) = fragment {
+                for (index, output) in transaction.as_slice().outputs().iter().enumerate() {
+                    let is_utxo =
+                        matches!(output.address.kind(), Kind::Single(_) | Kind::Group(_, _));
+                    if is_utxo && output.address.public_key() == public_key.as_ref() {
+                        utxos.push(Utxo {
+                            fragment_id: fragment.id().to_string(),
+                            output_index: index as u8,
+                            address: AddressReadable::from_address(
+                                prefix(discrimination),
+                                &output.address,
+                            )
+                            .to_string(),
+                            value: output.value.0,
+                        });
+                    }
+                }
+            }
+        }
+        Ok(utxos)
+    }
+
     pub fn get_proposals(&mut self) -> Result<Vec<VitProposal>, ControllerError> {
         Ok(self
             .backend
@@ -256,6 +538,34 @@ impl Controller {
         Ok(self.backend.fragment_logs()?)
     }
 
+    /// Polls fragment logs every `interval` and yields fragments whose status changed
+    /// (including fragments seen for the first time) until `timeout` elapses
+    pub fn watch_fragment_logs(
+        &self,
+        interval: Duration,
+        timeout: Duration,
+    ) -> FragmentStatusUpdates<'_> {
+        FragmentStatusUpdates {
+            backend: &self.backend,
+            interval,
+            deadline: Instant::now() + timeout,
+            known: HashMap::new(),
+            pending: VecDeque::new(),
+            polled: false,
+        }
+    }
+
+    pub fn rejected_votes(&self) -> Result<Vec<(FragmentId, String)>, ControllerError> {
+        Ok(self
+            .fragment_logs()?
+            .into_iter()
+            .filter_map(|(id, log)| match log.status() {
+                FragmentStatus::Rejected { reason } => Some((id, reason.clone())),
+                _ => None,
+            })
+            .collect())
+    }
+
     pub fn active_votes(&self) -> Result<Vec<SimpleVoteStatus>, ControllerError> {
         Ok(self
             .backend
@@ -263,6 +573,82 @@ impl Controller {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct FragmentStatusUpdate {
+    pub id: FragmentId,
+    pub previous: Option<FragmentStatus>,
+    pub current: FragmentStatus,
+}
+
+/// Iterator over fragment status transitions, created by `Controller::watch_fragment_logs`.
+/// Blocks between polls of backend
+pub struct FragmentStatusUpdates<'a> {
+    backend: &'a WalletBackend,
+    interval: Duration,
+    deadline: Instant,
+    known: HashMap<FragmentId, FragmentStatus>,
+    pending: VecDeque<FragmentStatusUpdate>,
+    polled: bool,
+}
+
+impl<'a> FragmentStatusUpdates<'a> {
+    fn poll(&mut self) -> Result<(), ControllerError> {
+        for (id, log) in self.backend.fragment_logs()? {
+            let current = log.status().clone();
+            let previous = self.known.insert(id, current.clone());
+            if previous.as_ref() != Some(&current) {
+                self.pending.push_back(FragmentStatusUpdate {
+                    id,
+                    previous,
+                    current,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Iterator for FragmentStatusUpdates<'a> {
+    type Item = Result<FragmentStatusUpdate, ControllerError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pending.is_empty() {
+            if self.polled {
+                if Instant::now() + self.interval > self.deadline {
+                    return None;
+                }
+                std::thread::sleep(self.interval);
+            }
+            self.polled = true;
+            if let Err(err) = self.poll() {
+                return Some(Err(err));
+            }
+        }
+        self.pending.pop_front().map(Ok)
+    }
+}
+
+/// Ledger errors caused by stale spending counter. Account witness signs the counter,
+/// so ledger without explicit counter check reports it as invalid account signature
+const COUNTER_MISMATCH_REASONS: [&str; 2] = [
+    "account with invalid signature",
+    "spending credential invalid",
+];
+
+fn is_counter_mismatch(reason: &str) -> bool {
+    let reason = reason.to_lowercase();
+    COUNTER_MISMATCH_REASONS
+        .iter()
+        .any(|counter_reason| reason.contains(counter_reason))
+}
+
+fn prefix(discrimination: Discrimination) -> &'static str {
+    match discrimination {
+        Discrimination::Test => "ta",
+        Discrimination::Production => "ca",
+    }
+}
+
 pub fn read_bech32(path: impl AsRef<Path>) -> Result<(String, Vec<bech32::u5>), ControllerError> {
     let line = jortestkit::file::read_file(path);
     bech32::decode(&line).map_err(Into::into)
@@ -285,4 +671,34 @@ pub enum ControllerError {
     CannotReadQrCode(#[from] image::ImageError),
     #[error("bech32 error")]
     Bech32(#[from] bech32::Error),
+    #[error("invalid address: {0}")]
+    InvalidAddress(String),
+    #[error("cannot find vote plan: {0}")]
+    VotePlanNotFound(String),
+    #[error("voting is not open yet, opens at: {opens_at}")]
+    VotingNotOpen { opens_at: String },
+    #[error("voting is already closed, closed at: {closed_at}")]
+    VotingClosed { closed_at: String },
+    #[error("block date {target} was not reached in time, current block date: {current}")]
+    BlockDateNotReached { target: String, current: String },
+    #[error("reference backend is not set")]
+    NoReferenceBackend,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counter_mismatch_is_recognized_only_by_ledger_errors() {
+        assert!(is_counter_mismatch("Account with invalid signature"));
+        assert!(is_counter_mismatch(
+            "Spending credential invalid, expected 2 got 1 in lane 0"
+        ));
+        assert!(!is_counter_mismatch(
+            "Invalid signature of vote plan certificate"
+        ));
+        assert!(!is_counter_mismatch("Counter exceeded its maximum value"));
+        assert!(!is_counter_mismatch("Not enough funds"));
+    }
 }
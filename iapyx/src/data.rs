@@ -1,4 +1,5 @@
 use chain_impl_mockchain::{certificate::VotePlanId, vote::Options};
+use chrono::{DateTime, Utc};
 use jormungandr_testing_utils::wallet::committee::encrypting_key_from_base32;
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, convert::TryFrom, fmt, str};
@@ -120,6 +121,43 @@ pub struct Proposal {
     pub chain_vote_encryption_key: String,
 }
 
+/// Discrepancies between proposal lists returned by two backends, by proposal id
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProposalsDiff {
+    pub only_in_left: Vec<String>,
+    pub only_in_right: Vec<String>,
+    pub different: Vec<String>,
+}
+
+impl ProposalsDiff {
+    pub fn new(left: &[Proposal], right: &[Proposal]) -> Self {
+        let right_by_id: HashMap<&str, &Proposal> = right
+            .iter()
+            .map(|proposal| (proposal.proposal_id.as_str(), proposal))
+            .collect();
+        let mut diff = Self::default();
+        for proposal in left {
+            match right_by_id.get(proposal.proposal_id.as_str()) {
+                None => diff.only_in_left.push(proposal.proposal_id.clone()),
+                Some(other) if *other != proposal => {
+                    diff.different.push(proposal.proposal_id.clone())
+                }
+                Some(_) => {}
+            }
+        }
+        diff.only_in_right = right
+            .iter()
+            .filter(|proposal| !left.iter().any(|x| x.proposal_id == proposal.proposal_id))
+            .map(|proposal| proposal.proposal_id.clone())
+            .collect();
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.only_in_left.is_empty() && self.only_in_right.is_empty() && self.different.is_empty()
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct Challenge {
     pub id: i32,
@@ -187,6 +225,7 @@ pub struct ServiceVersion {
     pub service_version: String,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct SimpleVoteStatus {
     pub chain_proposal_id: String,
     pub proposal_title: String,
@@ -203,6 +242,91 @@ impl fmt::Display for SimpleVoteStatus {
     }
 }
 
+/// Record of a single vote cast by the wallet
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct VoteReceipt {
+    pub chain_proposal_id: String,
+    pub chain_voteplan_id: String,
+    pub chain_proposal_index: i64,
+    pub choice: u8,
+    pub fragment_id: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl fmt::Display for VoteReceipt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[{}] # {} ({}:{}) -> Choice: {}, Fragment: {}",
+            self.timestamp,
+            self.chain_proposal_id,
+            self.chain_voteplan_id,
+            self.chain_proposal_index,
+            self.choice,
+            self.fragment_id
+        )
+    }
+}
+
+/// Wallet funds compared with fund voting power threshold.
+/// Votes of wallets below threshold are not counted
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct VotingPowerStatus {
+    pub value: u64,
+    pub threshold: u64,
+}
+
+impl VotingPowerStatus {
+    pub fn new(value: u64, threshold: u64) -> Self {
+        Self { value, threshold }
+    }
+
+    pub fn is_above_threshold(&self) -> bool {
+        self.value >= self.threshold
+    }
+
+    /// Positive when wallet is above threshold, negative otherwise
+    pub fn margin(&self) -> i128 {
+        self.value as i128 - self.threshold as i128
+    }
+}
+
+impl fmt::Display for VotingPowerStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = if self.is_above_threshold() {
+            "above"
+        } else {
+            "below"
+        };
+        write!(
+            f,
+            "{} threshold {} by {}",
+            status,
+            self.threshold,
+            self.margin().abs()
+        )
+    }
+}
+
+/// Utxo owned by the wallet, as distributed in block0. It may have been spent since
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Utxo {
+    pub fragment_id: String,
+    pub output_index: u8,
+    pub address: String,
+    pub value: u64,
+}
+
+impl fmt::Display for Utxo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{} -> {} ({})",
+            self.fragment_id, self.output_index, self.value, self.address
+        )
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
 pub struct VoteOptions(pub VoteOptionsMap);
 pub type VoteOptionsMap = HashMap<String, u8>;
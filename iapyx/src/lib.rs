@@ -11,12 +11,22 @@ mod wallet;
 
 pub use crate::wallet::{Error as WalletError, Wallet};
 pub use backend::{
-    Protocol, ProxyClient, WalletBackend, WalletBackendError, WalletBackendSettings,
+    forward_buffered, forward_streaming, forward_websocket, load_root_certificate, read_limited,
+    status_response, Block0Networks, BodyLimits, LimitedBody, Protocol, ProtocolParseError,
+    ProxyClient, ProxyRecorder, ProxyRecorderError, ProxyStats, ProxyStatsSnapshot,
+    RecordedExchange, RecordedRequest, RecordedResponse, StreamingPaths, UpstreamStats,
+    WalletBackend, WalletBackendError, WalletBackendSettings,
+};
+pub use controller::{Controller, ControllerError, FragmentStatusUpdate, FragmentStatusUpdates};
+pub use data::{
+    Fund, Proposal, ProposalsDiff, SimpleVoteStatus, Utxo, VitVersion, VoteReceipt, Voteplan,
+    VotingPowerStatus,
 };
-pub use controller::{Controller, ControllerError};
-pub use data::{Fund, Proposal, SimpleVoteStatus, VitVersion, Voteplan};
 pub use load::{
-    IapyxLoad, IapyxLoadConfig, IapyxLoadError, MultiController, VoteStatusProvider,
-    WalletRequestGen,
+    BulkVoteReport, BulkWalletDriver, BulkWalletDriverError, IapyxLoad, IapyxLoadConfig,
+    IapyxLoadError, MultiController, Throttle, VotePattern, VoteStatusProvider, WalletRequestGen,
+};
+pub use qr::{
+    find_pin, generate_qr_code, get_pin, is_numeric_pin, pin_to_bytes, save_qr_code, PinReadMode,
+    QrCodeOptions, QrErrorCorrection, QrGenerateError, QrReader,
 };
-pub use qr::{get_pin, pin_to_bytes, PinReadMode, QrReader};
@@ -0,0 +1,199 @@
+use super::{MultiController, MultiControllerError};
+use crate::qr::PinReadMode;
+use jormungandr_testing_utils::testing::node::RestSettings;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+use wallet_core::Choice;
+
+/// Votes cast by each wallet: first `proposals_per_wallet` proposals (starting from
+/// proposal shifted by wallet index), with choices taken in turn from `choices`
+#[derive(Debug, Clone)]
+pub struct VotePattern {
+    pub choices: Vec<u8>,
+    pub proposals_per_wallet: usize,
+}
+
+impl Default for VotePattern {
+    fn default() -> Self {
+        Self {
+            choices: vec![1],
+            proposals_per_wallet: 1,
+        }
+    }
+}
+
+/// Limits of votes submission. By default votes are sent one by one, as fast as possible
+#[derive(Debug, Clone)]
+pub struct Throttle {
+    pub requests_per_second: Option<u32>,
+    pub max_concurrency: usize,
+}
+
+impl Default for Throttle {
+    fn default() -> Self {
+        Self {
+            requests_per_second: None,
+            max_concurrency: 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BulkVoteReport {
+    pub wallets: usize,
+    pub sent: usize,
+    pub failed: usize,
+    pub errors: Vec<String>,
+    /// time spent on sending votes
+    pub duration: Duration,
+}
+
+impl BulkVoteReport {
+    /// Achieved number of successfully sent votes per second
+    pub fn throughput(&self) -> f64 {
+        let secs = self.duration.as_secs_f64();
+        if secs == 0.0 {
+            return 0.0;
+        }
+        self.sent as f64 / secs
+    }
+}
+
+/// Recovers all wallets from folder with qr codes (e.g. generated by vitup) and votes
+/// with each of them according to given pattern
+pub struct BulkWalletDriver {
+    controller: MultiController,
+    pattern: VotePattern,
+    throttle: Throttle,
+}
+
+impl BulkWalletDriver {
+    pub fn from_qr_folder<P: AsRef<Path>>(
+        backend_address: &str,
+        qr_codes_folder: P,
+        pin_mode: PinReadMode,
+        backend_settings: RestSettings,
+    ) -> Result<Self, BulkWalletDriverError> {
+        let folder = qr_codes_folder.as_ref();
+        let qr_codes: Vec<PathBuf> = std::fs::read_dir(folder)
+            .map_err(|_| BulkWalletDriverError::CannotReadQrs(folder.to_path_buf()))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("png"))
+            .collect();
+
+        Ok(Self {
+            controller: MultiController::recover_from_qrs(
+                backend_address,
+                &qr_codes,
+                pin_mode,
+                backend_settings,
+            )?,
+            pattern: Default::default(),
+            throttle: Default::default(),
+        })
+    }
+
+    pub fn with_pattern(mut self, pattern: VotePattern) -> Self {
+        self.pattern = pattern;
+        self
+    }
+
+    /// Limits submission rate, so load ramps up gradually instead of spiking
+    pub fn with_throttle(mut self, throttle: Throttle) -> Self {
+        self.throttle = throttle;
+        self
+    }
+
+    /// Builds votes of all wallets and sends them respecting throttle. Failure of single
+    /// vote does not stop others, it is only counted in report
+    pub fn run(&mut self) -> Result<BulkVoteReport, BulkWalletDriverError> {
+        let built = self.build_votes()?;
+        let mut report = built.report;
+        let window = self
+            .throttle
+            .requests_per_second
+            .map(|rps| rps.max(1) as usize)
+            .unwrap_or_else(|| built.fragments.len().max(1));
+
+        let windows = (built.fragments.len() + window - 1) / window;
+        let started = Instant::now();
+        for (idx, chunk) in built.fragments.chunks(window).enumerate() {
+            let window_started = Instant::now();
+            for result in self
+                .controller
+                .backend()
+                .try_send_fragments_concurrently(chunk.to_vec(), self.throttle.max_concurrency)
+            {
+                match result {
+                    Ok(_) => report.sent += 1,
+                    Err(err) => {
+                        report.failed += 1;
+                        report.errors.push(err.to_string());
+                    }
+                }
+            }
+            if self.throttle.requests_per_second.is_some() && idx + 1 < windows {
+                if let Some(rest) = Duration::from_secs(1).checked_sub(window_started.elapsed()) {
+                    std::thread::sleep(rest);
+                }
+            }
+        }
+        report.duration = started.elapsed();
+        Ok(report)
+    }
+
+    pub(super) fn build_votes(&mut self) -> Result<BuiltVotes, BulkWalletDriverError> {
+        if self.pattern.choices.is_empty() {
+            return Err(BulkWalletDriverError::EmptyChoices);
+        }
+        let proposals = self.controller.proposals()?;
+        if proposals.is_empty() {
+            return Err(BulkWalletDriverError::NoProposals);
+        }
+
+        let wallets = self.controller.wallet_count();
+        let mut built = BuiltVotes {
+            fragments: Vec::new(),
+            report: BulkVoteReport {
+                wallets,
+                ..Default::default()
+            },
+        };
+        let mut choices = self.pattern.choices.iter().cycle();
+
+        for wallet_index in 0..wallets {
+            let votes_data = (0..self.pattern.proposals_per_wallet)
+                .map(|vote_index| {
+                    let proposal = &proposals[(wallet_index + vote_index) % proposals.len()];
+                    (proposal, Choice::new(*choices.next().unwrap()))
+                })
+                .collect();
+            match self.controller.build_votes(wallet_index, votes_data) {
+                Ok(fragments) => built.fragments.extend(fragments),
+                Err(err) => {
+                    built.report.failed += self.pattern.proposals_per_wallet;
+                    built.report.errors.push(err.to_string());
+                }
+            }
+        }
+        Ok(built)
+    }
+}
+
+pub(super) struct BuiltVotes {
+    pub fragments: Vec<Vec<u8>>,
+    pub report: BulkVoteReport,
+}
+
+#[derive(Error, Debug)]
+pub enum BulkWalletDriverError {
+    #[error("cannot read folder {0:?}")]
+    CannotReadQrs(PathBuf),
+    #[error("vote pattern does not define any choice")]
+    EmptyChoices,
+    #[error("backend does not expose any proposal")]
+    NoProposals,
+    #[error("internal error")]
+    MultiControllerError(#[from] MultiControllerError),
+}
@@ -1,4 +1,4 @@
-use crate::PinReadMode;
+use crate::{load_root_certificate, PinReadMode, WalletBackendError};
 use jormungandr_testing_utils::testing::node::RestSettings;
 use jortestkit::load::Configuration;
 use std::path::PathBuf;
@@ -12,6 +12,7 @@ pub struct IapyxLoadConfig {
     pub address: String,
     pub debug: bool,
     pub use_https_for_post: bool,
+    pub root_cert_path: Option<PathBuf>,
     pub read_pin_from_filename: bool,
     pub reuse_accounts: bool,
     pub global_pin: String,
@@ -21,12 +22,17 @@ pub struct IapyxLoadConfig {
 }
 
 impl IapyxLoadConfig {
-    pub fn rest_settings(&self) -> RestSettings {
-        RestSettings {
+    pub fn rest_settings(&self) -> Result<RestSettings, WalletBackendError> {
+        Ok(RestSettings {
             enable_debug: self.debug,
             use_https_for_post: self.use_https_for_post,
+            certificate: self
+                .root_cert_path
+                .as_ref()
+                .map(load_root_certificate)
+                .transpose()?,
             ..Default::default()
-        }
+        })
     }
 
     pub fn pin_read_mode(&self) -> PinReadMode {
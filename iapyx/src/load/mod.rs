@@ -1,8 +1,10 @@
+mod bulk;
 mod config;
 mod multi_controller;
 mod request_generators;
 mod status_provider;
 
+pub use bulk::{BulkVoteReport, BulkWalletDriver, BulkWalletDriverError, Throttle, VotePattern};
 pub use config::IapyxLoadConfig;
 use jortestkit::measurement::EfficiencyBenchmarkFinish;
 pub use multi_controller::{MultiController, MultiControllerError};
@@ -23,7 +25,7 @@ impl IapyxLoad {
     pub fn start(self) -> Result<Option<EfficiencyBenchmarkFinish>, IapyxLoadError> {
         let backend = self.config.address.clone();
 
-        let settings = self.config.rest_settings();
+        let settings = self.config.rest_settings()?;
         let pin_read_mode = self.config.pin_read_mode();
 
         let multicontroller = {
@@ -91,4 +93,6 @@ pub enum IapyxLoadError {
     CannotReadQrs(PathBuf),
     #[error("internal error")]
     MultiControllerError(#[from] MultiControllerError),
+    #[error("backend settings error")]
+    BackendSettingsError(#[from] crate::WalletBackendError),
 }
@@ -128,6 +128,30 @@ impl MultiController {
             .map_err(Into::into)
     }
 
+    /// Builds and signs vote fragments of given wallet without sending them. Spending
+    /// counter is taken from backend and incremented for each consecutive vote
+    pub fn build_votes(
+        &mut self,
+        wallet_index: usize,
+        votes_data: Vec<(&Proposal, Choice)>,
+    ) -> Result<Vec<Vec<u8>>, MultiControllerError> {
+        let wallet = self.wallets.get_mut(wallet_index).unwrap();
+        let account_state = self.backend.account_state(wallet.id())?;
+
+        let mut counter = account_state.counter();
+        let mut txs = Vec::new();
+        for (proposal, choice) in votes_data {
+            wallet.set_state(account_state.value().clone().into(), counter);
+            txs.push(
+                wallet
+                    .vote(self.settings.clone(), &proposal.clone().into(), choice)?
+                    .to_vec(),
+            );
+            counter += 1;
+        }
+        Ok(txs)
+    }
+
     pub fn votes_batch(
         &mut self,
         wallet_index: usize,
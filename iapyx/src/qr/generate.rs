@@ -0,0 +1,148 @@
+use chain_crypto::{Ed25519Extended, SecretKey};
+use image::{ImageBuffer, Luma};
+use qrcode::{EcLevel, QrCode};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Default size of single qr module in pixels
+pub const DEFAULT_MODULE_SIZE: u32 = 8;
+
+#[derive(Debug, Error)]
+pub enum QrGenerateError {
+    #[error("cannot encrypt key with pin")]
+    Encryption,
+    #[error("cannot encode qr code")]
+    Encoding(#[from] qrcode::types::QrError),
+    #[error("cannot save qr code")]
+    Image(#[from] image::ImageError),
+    #[error("unknown error correction level '{0}', expected one of: low, medium, quartile, high")]
+    UnknownErrorCorrection(String),
+}
+
+/// Error correction level of generated qr code. Higher level lets code survive damage
+/// (poor print quality, scratches, small labels scanned from distance) at the cost of
+/// denser image: roughly 7% (low), 15% (medium), 25% (quartile) and 30% (high) of data
+/// can be restored. High is used by default, same as `KeyQrCode::generate`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QrErrorCorrection {
+    Low,
+    Medium,
+    Quartile,
+    High,
+}
+
+impl Default for QrErrorCorrection {
+    fn default() -> Self {
+        Self::High
+    }
+}
+
+impl FromStr for QrErrorCorrection {
+    type Err = QrGenerateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "l" | "low" => Ok(Self::Low),
+            "m" | "medium" => Ok(Self::Medium),
+            "q" | "quartile" => Ok(Self::Quartile),
+            "h" | "high" => Ok(Self::High),
+            _ => Err(QrGenerateError::UnknownErrorCorrection(s.to_string())),
+        }
+    }
+}
+
+impl From<QrErrorCorrection> for EcLevel {
+    fn from(level: QrErrorCorrection) -> Self {
+        match level {
+            QrErrorCorrection::Low => EcLevel::L,
+            QrErrorCorrection::Medium => EcLevel::M,
+            QrErrorCorrection::Quartile => EcLevel::Q,
+            QrErrorCorrection::High => EcLevel::H,
+        }
+    }
+}
+
+/// Appearance of generated qr code. Bigger modules are easier to scan from print,
+/// but produce bigger images
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QrCodeOptions {
+    pub error_correction: QrErrorCorrection,
+    pub module_size: u32,
+}
+
+impl Default for QrCodeOptions {
+    fn default() -> Self {
+        Self {
+            error_correction: Default::default(),
+            module_size: DEFAULT_MODULE_SIZE,
+        }
+    }
+}
+
+/// Encrypts key with pin and renders it as qr code readable by `KeyQrCode::decode`
+pub fn generate_qr_code(
+    key: &SecretKey<Ed25519Extended>,
+    pin: &[u8],
+    options: QrCodeOptions,
+) -> Result<ImageBuffer<Luma<u8>, Vec<u8>>, QrGenerateError> {
+    let encrypted = symmetric_cipher::encrypt(pin, key.leak_secret().as_ref(), rand::thread_rng())
+        .map_err(|_| QrGenerateError::Encryption)?;
+    let code = QrCode::with_error_correction_level(
+        hex::encode(encrypted),
+        options.error_correction.into(),
+    )?;
+    Ok(code
+        .render::<Luma<u8>>()
+        .module_dimensions(options.module_size, options.module_size)
+        .build())
+}
+
+pub fn save_qr_code<P: AsRef<Path>>(
+    key: &SecretKey<Ed25519Extended>,
+    pin: &[u8],
+    options: QrCodeOptions,
+    path: P,
+) -> Result<(), QrGenerateError> {
+    generate_qr_code(key, pin, options)?.save(path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pin_to_bytes;
+    use jormungandr_testing_utils::qr_code::KeyQrCode;
+
+    #[test]
+    fn error_correction_from_str() {
+        assert_eq!(
+            "l".parse::<QrErrorCorrection>().unwrap(),
+            QrErrorCorrection::Low
+        );
+        assert_eq!(
+            "Quartile".parse::<QrErrorCorrection>().unwrap(),
+            QrErrorCorrection::Quartile
+        );
+        assert!("x".parse::<QrErrorCorrection>().is_err());
+    }
+
+    #[test]
+    fn generated_qr_code_is_readable() {
+        let key = SecretKey::<Ed25519Extended>::generate(rand::thread_rng());
+        let pin = pin_to_bytes("1234");
+        let options = QrCodeOptions {
+            error_correction: QrErrorCorrection::Low,
+            module_size: 4,
+        };
+        let img = generate_qr_code(&key, &pin, options).unwrap();
+
+        let decoded = KeyQrCode::decode(image::DynamicImage::ImageLuma8(img), &pin).unwrap();
+        assert_eq!(
+            decoded[0].leak_secret().as_ref(),
+            key.leak_secret().as_ref()
+        );
+    }
+}
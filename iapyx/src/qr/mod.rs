@@ -1,3 +1,10 @@
+mod generate;
+
+use chain_crypto::{Ed25519Extended, SecretKey};
+pub use generate::{
+    generate_qr_code, save_qr_code, QrCodeOptions, QrErrorCorrection, QrGenerateError,
+    DEFAULT_MODULE_SIZE,
+};
 use jormungandr_testing_utils::qr_code::KeyQrCode;
 use jormungandr_testing_utils::qr_code::KeyQrCodeError;
 use std::path::Path;
@@ -33,6 +40,8 @@ pub enum PinReadError {
     UnableToReadQr(#[from] std::io::Error),
     #[error("Cannot decode qr from file")]
     UnableToDecodeQr(#[from] KeyQrCodeError),
+    #[error("no qr code found in file {0:?}")]
+    QrNotFound(PathBuf),
     #[error("cannot open image")]
     UnableToOpenImage(#[from] image::ImageError),
 }
@@ -112,6 +121,39 @@ impl QrReader {
     }
 }
 
+/// Tries to decode qr code with each of candidate pins and returns first pin which
+/// decrypts it, together with secret key. Candidates which are not numeric are skipped.
+/// Returns error when qr code itself cannot be decoded, so it is not mistaken for wrong pin
+pub fn find_pin<P: AsRef<Path>>(
+    qr: P,
+    pins: &[String],
+) -> Result<Option<(String, SecretKey<Ed25519Extended>)>, PinReadError> {
+    let img = image::open(qr.as_ref())?;
+    for pin in pins {
+        if !is_numeric_pin(pin) {
+            continue;
+        }
+        match KeyQrCode::decode(img.clone(), &pin_to_bytes(pin)) {
+            Ok(secrets) => {
+                let secret = secrets
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| PinReadError::QrNotFound(qr.as_ref().to_path_buf()))?;
+                return Ok(Some((pin.clone(), secret)));
+            }
+            // wrong pin fails on decryption, any other error means qr code is not decodable
+            Err(KeyQrCodeError::SymmetricCipher(_)) => continue,
+            Err(err) => return Err(err.into()),
+        }
+    }
+    Ok(None)
+}
+
+/// Pin can contain digits only, any other candidate is skipped by `find_pin`
+pub fn is_numeric_pin(pin: &str) -> bool {
+    !pin.is_empty() && pin.chars().all(|c| c.is_ascii_digit())
+}
+
 pub fn pin_to_bytes(pin: &str) -> Vec<u8> {
     pin.chars().map(|x| x.to_digit(10).unwrap() as u8).collect()
 }
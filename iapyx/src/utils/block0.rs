@@ -0,0 +1,24 @@
+use chain_core::mempack::{ReadBuf, Readable};
+use chain_impl_mockchain::block::Block;
+use jormungandr_lib::crypto::hash::Hash;
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Block0Error {
+    #[error("cannot read block0 file")]
+    IoError(#[from] std::io::Error),
+    #[error("cannot decode block0: {0}")]
+    Decode(String),
+}
+
+/// Hash of genesis block, which wallet and backend need to agree on
+pub fn block0_hash(block0: &[u8]) -> Result<Hash, Block0Error> {
+    let block = Block::read(&mut ReadBuf::from(block0))
+        .map_err(|err| Block0Error::Decode(err.to_string()))?;
+    Ok(block.header.hash().into())
+}
+
+pub fn block0_hash_from_file<P: AsRef<Path>>(block0: P) -> Result<Hash, Block0Error> {
+    block0_hash(&std::fs::read(block0)?)
+}
@@ -0,0 +1,67 @@
+use bip39::dictionary::{self, Language};
+use bip39::{Entropy, Mnemonics, Type};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum MnemonicsError {
+    #[error("invalid mnemonics word count: {0}, expected one of 12, 15, 18, 21 or 24")]
+    InvalidWordCount(usize),
+    #[error("word '{word}' at position {position} is not in BIP39 english wordlist")]
+    UnknownWord { word: String, position: usize },
+    #[error("invalid mnemonics checksum")]
+    InvalidChecksum,
+}
+
+/// Checks word count, that each word (numbered from 1) belongs to BIP39 english wordlist
+/// and mnemonics checksum
+pub fn validate_mnemonics(mnemonics: &str) -> Result<(), MnemonicsError> {
+    let words: Vec<&str> = mnemonics.split_whitespace().collect();
+    Type::from_word_count(words.len())
+        .map_err(|_| MnemonicsError::InvalidWordCount(words.len()))?;
+
+    for (idx, word) in words.iter().enumerate() {
+        dictionary::ENGLISH
+            .lookup_mnemonic(word)
+            .map_err(|_| MnemonicsError::UnknownWord {
+                word: word.to_string(),
+                position: idx + 1,
+            })?;
+    }
+
+    let mnemonics = Mnemonics::from_string(&dictionary::ENGLISH, &words.join(" "))
+        .map_err(|_| MnemonicsError::InvalidChecksum)?;
+    Entropy::from_mnemonics(&mnemonics).map_err(|_| MnemonicsError::InvalidChecksum)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_word_position_is_reported() {
+        let mnemonics = "abandon abandon abandon abandon abandon abandon \
+            abandon abandon abandon abandon abandonn about";
+        match validate_mnemonics(mnemonics) {
+            Err(MnemonicsError::UnknownWord { word, position }) => {
+                assert_eq!(word, "abandonn");
+                assert_eq!(position, 11);
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn checksum_is_verified() {
+        let valid = "abandon abandon abandon abandon abandon abandon \
+            abandon abandon abandon abandon abandon about";
+        assert!(validate_mnemonics(valid).is_ok());
+
+        let invalid = "abandon abandon abandon abandon abandon abandon \
+            abandon abandon abandon abandon abandon abandon";
+        assert!(matches!(
+            validate_mnemonics(invalid),
+            Err(MnemonicsError::InvalidChecksum)
+        ));
+    }
+}
@@ -1,3 +1,6 @@
+pub mod block0;
 pub mod datetime;
+pub mod mnemonics;
 pub mod seed;
 pub mod serde;
+pub mod vote_key;
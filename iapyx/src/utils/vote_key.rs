@@ -0,0 +1,53 @@
+use chain_crypto::bech32::Bech32;
+use chain_crypto::{Ed25519, Ed25519Extended, PublicKey, SecretKey};
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum VoteKeyError {
+    #[error("cannot read key file")]
+    IoError(#[from] std::io::Error),
+    #[error(
+        "expected bech32 ed25519 secret or public key (ed25519e_sk, ed25519_sk or ed25519_pk)"
+    )]
+    UnsupportedKey,
+}
+
+/// Public key of catalyst vote key given as bech32 secret (ed25519e_sk, ed25519_sk)
+/// or public key (ed25519_pk), as generated by registration job
+pub fn vote_public_key(key: &str) -> Result<PublicKey<Ed25519>, VoteKeyError> {
+    let key = key.trim();
+    if let Ok(secret) = SecretKey::<Ed25519Extended>::try_from_bech32_str(key) {
+        return Ok(secret.to_public());
+    }
+    if let Ok(secret) = SecretKey::<Ed25519>::try_from_bech32_str(key) {
+        return Ok(secret.to_public());
+    }
+    PublicKey::<Ed25519>::try_from_bech32_str(key).map_err(|_| VoteKeyError::UnsupportedKey)
+}
+
+/// Voting account id (hex of account public key), under which voting power
+/// of registration appears in snapshot
+pub fn vote_account_id(key: &str) -> Result<String, VoteKeyError> {
+    Ok(hex::encode(vote_public_key(key)?.as_ref()))
+}
+
+pub fn vote_account_id_from_file<P: AsRef<Path>>(key: P) -> Result<String, VoteKeyError> {
+    vote_account_id(&std::fs::read_to_string(key)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn secret_and_public_key_give_same_account_id() {
+        let secret = SecretKey::<Ed25519Extended>::generate(rand::thread_rng());
+        let public = secret.to_public();
+        let expected = hex::encode(public.as_ref());
+
+        assert_eq!(vote_account_id(&secret.to_bech32_str()).unwrap(), expected);
+        assert_eq!(vote_account_id(&public.to_bech32_str()).unwrap(), expected);
+        assert!(vote_account_id("not a key").is_err());
+    }
+}
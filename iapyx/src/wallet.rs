@@ -4,6 +4,7 @@ use chain_addr::{AddressReadable, Discrimination};
 use chain_core::property::Deserialize;
 use chain_core::property::Fragment as _;
 use chain_impl_mockchain::{
+    fee::FeeAlgorithm,
     fragment::{Fragment, FragmentId},
     transaction::Input,
 };
@@ -28,10 +29,14 @@ pub enum Error {
     BackendError(#[from] crate::backend::WalletBackendError),
     #[error("cannot send vote")]
     CannotSendVote(String),
+    #[error("cannot send transaction")]
+    CannotSendTransaction(String),
 }
 
 pub struct Wallet {
     inner: Inner,
+    /// transfers sent but not confirmed yet. Wallet core registers only votes as pending
+    pending_transfers: HashSet<FragmentId>,
 }
 
 impl Wallet {
@@ -42,24 +47,30 @@ impl Wallet {
     }
 
     pub fn recover(mnemonics: &str, password: &[u8]) -> Result<Self, Error> {
-        Ok(Self {
-            inner: Inner::recover(mnemonics, password)
-                .map_err(|e| Error::CannotRecover(e.to_string()))?,
-        })
+        Ok(Self::new(
+            Inner::recover(mnemonics, password).map_err(|e| Error::CannotRecover(e.to_string()))?,
+        ))
     }
 
     pub fn recover_from_account(secret_key: &[u8]) -> Result<Self, Error> {
-        Ok(Self {
-            inner: Inner::recover_free_keys(secret_key, &[])
+        Ok(Self::new(
+            Inner::recover_free_keys(secret_key, &[])
                 .map_err(|e| Error::CannotRecover(e.to_string()))?,
-        })
+        ))
     }
 
     pub fn recover_from_utxo(secret_key: &[u8; 64]) -> Result<Self, Error> {
-        Ok(Self {
-            inner: Inner::recover_free_keys(secret_key, &[*secret_key])
+        Ok(Self::new(
+            Inner::recover_free_keys(secret_key, &[*secret_key])
                 .map_err(|e| Error::CannotRecover(e.to_string()))?,
-        })
+        ))
+    }
+
+    fn new(inner: Inner) -> Self {
+        Self {
+            inner,
+            pending_transfers: HashSet::new(),
+        }
     }
 
     pub fn account(&self, discrimination: chain_addr::Discrimination) -> chain_addr::Address {
@@ -100,14 +111,22 @@ impl Wallet {
     }
 
     pub fn confirm_transaction(&mut self, id: FragmentId) {
-        self.inner.confirm_transaction(id);
+        if !self.pending_transfers.remove(&id) {
+            self.inner.confirm_transaction(id);
+        }
     }
 
     pub fn pending_transactions(&self) -> HashSet<FragmentId> {
-        self.inner.pending_transactions()
+        let mut pending = self.inner.pending_transactions();
+        pending.extend(self.pending_transfers.iter().cloned());
+        pending
     }
 
+    /// Transfer spends from account, so there are no utxo inputs to give back
     pub fn remove_pending_transaction(&mut self, id: &FragmentId) -> Option<Vec<Input>> {
+        if self.pending_transfers.remove(id) {
+            return Some(Vec::new());
+        }
         self.inner.remove_pending_transaction(id)
     }
 
@@ -119,6 +138,10 @@ impl Wallet {
         self.inner.set_state(value, counter);
     }
 
+    pub fn spending_counter(&self) -> u32 {
+        self.inner.spending_counter()
+    }
+
     pub fn vote(
         &mut self,
         settings: Settings,
@@ -130,6 +153,47 @@ impl Wallet {
             .map_err(|e| Error::CannotSendVote(e.to_string()))
     }
 
+    /// Signs vote, but leaves wallet state (spending counter, pending transactions)
+    /// as it was before, as if vote was never created
+    pub fn vote_detached(
+        &mut self,
+        settings: Settings,
+        proposal: &Proposal,
+        choice: Choice,
+    ) -> Result<Box<[u8]>, Error> {
+        let value = self.total_value();
+        let counter = self.spending_counter();
+        let fragment = self.vote(settings, proposal, choice)?;
+        if let Ok(vote) = Fragment::deserialize(&fragment[..]) {
+            self.inner.remove_pending_transaction(&vote.id());
+        }
+        self.set_state(value, counter);
+        Ok(fragment)
+    }
+
+    pub fn transfer(
+        &mut self,
+        settings: Settings,
+        address: chain_addr::Address,
+        value: Value,
+    ) -> Result<Box<[u8]>, Error> {
+        let Value(funds) = self.total_value();
+        let counter = self.spending_counter();
+        // single account input and single output
+        let fee = settings.fees.calculate(None, 1, 1).0;
+        let fragment = self
+            .inner
+            .transfer(settings, address, value)
+            .map_err(|e| Error::CannotSendTransaction(e.to_string()))?;
+        let id = Fragment::deserialize(&fragment[..])
+            .map_err(|e| Error::CannotSendTransaction(e.to_string()))?
+            .id();
+
+        self.set_state(Value(funds.saturating_sub(value.0 + fee)), counter + 1);
+        self.pending_transfers.insert(id);
+        Ok(fragment)
+    }
+
     pub fn identifier(&self, discrimination: Discrimination) -> AccountIdentifier {
         let address_readable = match discrimination {
             Discrimination::Test => {
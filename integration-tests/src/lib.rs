@@ -35,27 +35,24 @@ pub enum Vote {
     No = 2,
 }
 
-#[allow(dead_code)]
-pub struct VoteTiming {
-    pub vote_start: u32,
-    pub tally_start: u32,
-    pub tally_end: u32,
+pub use vitup::config::VoteTiming;
+
+pub trait VoteTimingExt {
+    fn wait_for_vote_end(self, explorer: Explorer);
+    fn wait_for_tally_start(self, explorer: Explorer);
+    fn wait_for_tally_end(self, explorer: Explorer);
 }
 
-impl VoteTiming {
-    pub fn new(vote_start: u32, tally_start: u32, tally_end: u32) -> Self {
-        Self {
-            vote_start,
-            tally_start,
-            tally_end,
-        }
+impl VoteTimingExt for VoteTiming {
+    fn wait_for_vote_end(self, explorer: Explorer) {
+        self.wait_for_tally_start(explorer);
     }
 
-    pub fn wait_for_tally_start(self, explorer: Explorer) {
+    fn wait_for_tally_start(self, explorer: Explorer) {
         time::wait_for_epoch(self.tally_start as u64, explorer);
     }
 
-    pub fn wait_for_tally_end(self, explorer: Explorer) {
+    fn wait_for_tally_end(self, explorer: Explorer) {
         time::wait_for_epoch(self.tally_end as u64, explorer);
     }
 }
@@ -1,12 +1,14 @@
 use crate::non_functional::build_load_config;
 use crate::non_functional::private_vote_test_scenario;
 use crate::setup::vitup_setup;
+use crate::{VoteTiming, VoteTimingExt};
 use assert_fs::TempDir;
 use iapyx::{IapyxLoad, Protocol};
 use jormungandr_testing_utils::testing::node::time;
 use jortestkit::measurement::Status;
 use vit_servicing_station_tests::common::data::ArbitraryValidVotingTemplateGenerator;
 use vitup::scenario::network::setup_network;
+use vitup::scenario::spawned::SpawnedNetwork;
 use vitup::setup::start::quick::QuickVitBackendSettingsBuilder;
 
 #[test]
@@ -22,9 +24,7 @@ pub fn load_test_public_100_000_votes() {
     let mut quick_setup = QuickVitBackendSettingsBuilder::new();
     quick_setup
         .initials_count(no_of_wallets, "1234")
-        .vote_start_epoch(vote_timing.vote_start)
-        .tally_start_epoch(vote_timing.tally_start)
-        .tally_end_epoch(vote_timing.tally_end)
+        .vote_timing(vote_timing)
         .slot_duration_in_seconds(2)
         .slots_in_epoch_count(60)
         .proposals_count(300)
@@ -46,6 +46,10 @@ pub fn load_test_public_100_000_votes() {
         version.to_owned(),
     )
     .unwrap();
+    let network = SpawnedNetwork::new(controller, nodes, vit_station, wallet_proxy);
+    let controller = network.controller();
+    let nodes = network.nodes();
+    let wallet_proxy = network.wallet_proxy();
 
     let mut qr_codes_folder = testing_directory.path().to_path_buf();
     qr_codes_folder.push("vit_backend/qr-codes");
@@ -66,16 +70,12 @@ pub fn load_test_public_100_000_votes() {
         .send_public_vote_tally(&mut committee, &vote_plan.into(), nodes.get(0).unwrap())
         .unwrap();
 
-    vit_station.shutdown();
-    wallet_proxy.shutdown();
-
     for node in nodes {
         node.logger()
             .assert_no_errors(&format!("Errors in logs for node: {}", node.alias()));
-        node.shutdown().unwrap();
     }
 
-    controller.finalize();
+    network.shutdown().unwrap();
 }
 
 #[test]
@@ -88,9 +88,7 @@ pub fn load_test_private_pesimistic() {
 
     quick_setup
         .initials_count(no_of_wallets, "1234")
-        .vote_start_epoch(vote_timing.vote_start)
-        .tally_start_epoch(vote_timing.tally_start)
-        .tally_end_epoch(vote_timing.tally_end)
+        .vote_timing(vote_timing)
         .slot_duration_in_seconds(20)
         .slots_in_epoch_count(3)
         .proposals_count(250)
@@ -110,9 +108,7 @@ pub fn load_test_private_optimistic() {
     let mut quick_setup = QuickVitBackendSettingsBuilder::new();
     quick_setup
         .initials_count(no_of_wallets, "1234")
-        .vote_start_epoch(vote_timing.vote_start)
-        .tally_start_epoch(vote_timing.tally_start)
-        .tally_end_epoch(vote_timing.tally_end)
+        .vote_timing(vote_timing)
         .slot_duration_in_seconds(20)
         .slots_in_epoch_count(180)
         .proposals_count(500)
@@ -20,6 +20,7 @@ use std::str::FromStr;
 use vit_servicing_station_tests::common::data::ArbitraryValidVotingTemplateGenerator;
 use vitup::config::VitStartParameters;
 use vitup::scenario::network::setup_network;
+use vitup::scenario::spawned::SpawnedNetwork;
 use vitup::setup::start::quick::QuickVitBackendSettingsBuilder;
 
 #[allow(dead_code)]
@@ -49,6 +50,10 @@ fn private_vote_test_scenario(
         "2.0".to_owned(),
     )
     .unwrap();
+    let network = SpawnedNetwork::new(controller, nodes, vit_station, wallet_proxy);
+    let controller = network.controller();
+    let nodes = network.nodes();
+    let wallet_proxy = network.wallet_proxy();
 
     std::thread::sleep(std::time::Duration::from_secs(10));
 
@@ -125,15 +130,12 @@ fn private_vote_test_scenario(
     let vote_plan_assert: VotePlanStatusAssert = leader_1.vote_plans().unwrap().into();
     vote_plan_assert.assert_all_proposals_are_tallied();
 
-    vit_station.shutdown();
-    wallet_proxy.shutdown();
     for node in nodes {
         node.logger()
             .assert_no_errors(&format!("Errors in logs for node: {}", node.alias()));
-        node.shutdown().unwrap();
     }
 
-    controller.finalize();
+    network.shutdown().unwrap();
 }
 
 #[allow(dead_code)]
@@ -165,6 +167,7 @@ fn build_load_config(
         reuse_accounts: false,
         read_pin_from_filename: true,
         use_https_for_post: false,
+        root_cert_path: None,
         debug: false,
     }
 }
@@ -1,7 +1,7 @@
 use super::Vote;
 use crate::asserts::VotePlanStatusAssert;
 use crate::setup::*;
-use crate::VoteTiming;
+use crate::{VoteTiming, VoteTimingExt};
 use assert_fs::TempDir;
 use chain_impl_mockchain::block::BlockDate;
 use chain_impl_mockchain::key::Hash;
@@ -12,6 +12,7 @@ use std::str::FromStr;
 use vit_servicing_station_tests::common::data::ArbitraryValidVotingTemplateGenerator;
 use vitup::config::{InitialEntry, Initials};
 use vitup::scenario::network::setup_network;
+use vitup::scenario::spawned::SpawnedNetwork;
 use vitup::setup::start::quick::QuickVitBackendSettingsBuilder;
 
 #[test]
@@ -39,9 +40,7 @@ pub fn private_vote_e2e_flow() -> std::result::Result<(), crate::Error> {
                 pin: "1234".to_string(),
             },
         ]))
-        .vote_start_epoch(vote_timing.vote_start)
-        .tally_start_epoch(vote_timing.tally_start)
-        .tally_end_epoch(vote_timing.tally_end)
+        .vote_timing(vote_timing)
         .slot_duration_in_seconds(2)
         .slots_in_epoch_count(60)
         .proposals_count(1)
@@ -61,6 +60,10 @@ pub fn private_vote_e2e_flow() -> std::result::Result<(), crate::Error> {
         "2.0".to_owned(),
     )
     .unwrap();
+    let network = SpawnedNetwork::new(controller, nodes, vit_station, wallet_proxy);
+    let controller = network.controller();
+    let nodes = network.nodes();
+    let wallet_proxy = network.wallet_proxy();
 
     let mut committee = controller.wallet("committee_1").unwrap();
 
@@ -76,7 +79,7 @@ pub fn private_vote_e2e_flow() -> std::result::Result<(), crate::Error> {
 
     // start mainnet wallets
     let mut david = vit_controller
-        .iapyx_wallet_from_qr(&david_qr_code, "1234", &wallet_proxy)
+        .iapyx_wallet_from_qr(&david_qr_code, "1234", wallet_proxy)
         .unwrap();
 
     let fund1_vote_plan = controller.vote_plan(&fund_name).unwrap();
@@ -87,7 +90,7 @@ pub fn private_vote_e2e_flow() -> std::result::Result<(), crate::Error> {
         .unwrap();
 
     let mut edgar = vit_controller
-        .iapyx_wallet_from_qr(&edgar_qr_code, "1234", &wallet_proxy)
+        .iapyx_wallet_from_qr(&edgar_qr_code, "1234", wallet_proxy)
         .unwrap();
 
     edgar
@@ -95,7 +98,7 @@ pub fn private_vote_e2e_flow() -> std::result::Result<(), crate::Error> {
         .unwrap();
 
     let mut filip = vit_controller
-        .iapyx_wallet_from_qr(&filip_qr_code, "1234", &wallet_proxy)
+        .iapyx_wallet_from_qr(&filip_qr_code, "1234", wallet_proxy)
         .unwrap();
 
     filip
@@ -148,11 +151,6 @@ pub fn private_vote_e2e_flow() -> std::result::Result<(), crate::Error> {
     let vote_plan_assert: VotePlanStatusAssert = leader_1.vote_plans().unwrap().into();
     vote_plan_assert.assert_all_proposals_are_tallied();
 
-    vit_station.shutdown();
-    wallet_proxy.shutdown();
-    for node in nodes {
-        node.shutdown()?;
-    }
-    controller.finalize();
+    network.shutdown()?;
     Ok(())
 }
@@ -1,7 +1,7 @@
 use super::Vote;
 use crate::asserts::VotePlanStatusAssert;
 use crate::setup::*;
-use crate::VoteTiming;
+use crate::{VoteTiming, VoteTimingExt};
 use assert_fs::TempDir;
 use chain_impl_mockchain::block::BlockDate;
 use iapyx::Protocol;
@@ -10,6 +10,7 @@ use std::path::Path;
 use vit_servicing_station_tests::common::data::ArbitraryValidVotingTemplateGenerator;
 use vitup::config::{InitialEntry, Initials};
 use vitup::scenario::network::setup_network;
+use vitup::scenario::spawned::SpawnedNetwork;
 use vitup::setup::start::quick::QuickVitBackendSettingsBuilder;
 
 const PIN: &str = "1234";
@@ -38,9 +39,7 @@ pub fn public_vote_multiple_vote_plans() -> std::result::Result<(), crate::Error
                 pin: PIN.to_string(),
             },
         ]))
-        .vote_start_epoch(vote_timing.vote_start)
-        .tally_start_epoch(vote_timing.tally_start)
-        .tally_end_epoch(vote_timing.tally_end)
+        .vote_timing(vote_timing)
         .slot_duration_in_seconds(2)
         .slots_in_epoch_count(30)
         .proposals_count(300)
@@ -61,6 +60,10 @@ pub fn public_vote_multiple_vote_plans() -> std::result::Result<(), crate::Error
         "2.0".to_owned(),
     )
     .unwrap();
+    let network = SpawnedNetwork::new(controller, nodes, vit_station, wallet_proxy);
+    let controller = network.controller();
+    let nodes = network.nodes();
+    let wallet_proxy = network.wallet_proxy();
 
     let leader_1 = &nodes[0];
     let wallet_node = &nodes[4];
@@ -76,7 +79,7 @@ pub fn public_vote_multiple_vote_plans() -> std::result::Result<(), crate::Error
 
     // start mainnet wallets
     let mut david = vit_controller
-        .iapyx_wallet_from_qr(&david_qr_code, PIN, &wallet_proxy)
+        .iapyx_wallet_from_qr(&david_qr_code, PIN, wallet_proxy)
         .unwrap();
 
     let fund1_vote_plan = &controller.vote_plans()[0];
@@ -88,7 +91,7 @@ pub fn public_vote_multiple_vote_plans() -> std::result::Result<(), crate::Error
         .unwrap();
 
     let mut edgar = vit_controller
-        .iapyx_wallet_from_qr(&edgar_qr_code, PIN, &wallet_proxy)
+        .iapyx_wallet_from_qr(&edgar_qr_code, PIN, wallet_proxy)
         .unwrap();
 
     edgar
@@ -96,7 +99,7 @@ pub fn public_vote_multiple_vote_plans() -> std::result::Result<(), crate::Error
         .unwrap();
 
     let mut filip = vit_controller
-        .iapyx_wallet_from_qr(&filip_qr_code, PIN, &wallet_proxy)
+        .iapyx_wallet_from_qr(&filip_qr_code, PIN, wallet_proxy)
         .unwrap();
 
     filip
@@ -126,11 +129,6 @@ pub fn public_vote_multiple_vote_plans() -> std::result::Result<(), crate::Error
     vote_plan_assert.assert_proposal_tally(fund1_vote_plan.id(), 0, vec![0, 10_000, 10_000]);
     vote_plan_assert.assert_proposal_tally(fund2_vote_plan.id(), 0, vec![0, 10_000, 0]);
 
-    vit_station.shutdown();
-    wallet_proxy.shutdown();
-    for node in nodes {
-        node.shutdown()?;
-    }
-    controller.finalize();
+    network.shutdown()?;
     Ok(())
 }
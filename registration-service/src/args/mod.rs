@@ -42,22 +42,32 @@ impl RegistrationServiceCommand {
                     job_result_dir.push(job_id.to_string());
                     std::fs::create_dir_all(job_result_dir.clone())?;
 
-                    let job = VoteRegistrationJobBuilder::new()
-                        .with_jcli(&configuration.jcli)
-                        .with_cardano_cli(&configuration.cardano_cli)
-                        .with_voter_registration(&configuration.voter_registration)
-                        .with_network(configuration.network)
-                        .with_kedqr(&configuration.vit_kedqr)
+                    let job = VoteRegistrationJobBuilder::from_configuration(&configuration)
+                        .with_dry_run(request.dry_run)
                         .with_working_dir(&job_result_dir)
                         .build();
 
-                    control_context.lock().unwrap().run_started().unwrap();
-                    let output_info = job.start(request).unwrap();
-                    control_context
-                        .lock()
-                        .unwrap()
-                        .run_finished(output_info)
-                        .unwrap();
+                    control_context.lock().unwrap().run_started(job_id)?;
+                    let context = control_context.clone();
+                    let phase_context = control_context.clone();
+                    let on_phase = move |update| {
+                        if let Err(err) = phase_context.lock().unwrap().update_phase(job_id, update)
+                        {
+                            eprintln!("cannot update phase of job {}: {:?}", job_id, err);
+                        }
+                    };
+                    tokio::task::spawn_blocking(move || {
+                        match job.start_with_phases(request, on_phase) {
+                            Ok(output_info) => {
+                                context.lock().unwrap().run_finished(job_id, output_info)
+                            }
+                            Err(err) => {
+                                eprintln!("job {} failed: {:?}", job_id, err);
+                                context.lock().unwrap().run_failed(job_id, err.to_string())
+                            }
+                        }
+                    });
+                    continue;
                 }
                 tokio::time::sleep(std::time::Duration::from_secs(5)).await;
             }
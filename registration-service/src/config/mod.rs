@@ -1,7 +1,10 @@
 mod network;
+mod qr;
 
-pub use network::NetworkType;
+pub use network::{NetworkType, NetworkTypeParseError, DEFAULT_TESTNET_MAGIC};
+pub use qr::QrErrorCorrection;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
 use thiserror::Error;
@@ -19,7 +22,45 @@ pub struct Configuration {
     #[serde(rename = "vit-kedqr")]
     pub vit_kedqr: PathBuf,
     pub network: NetworkType,
+    #[serde(rename = "node-socket", default)]
+    pub node_socket: Option<PathBuf>,
+    /// environment variables set for every command spawned by job
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    /// maximum time in seconds of single command spawned by job
+    #[serde(rename = "command-timeout", default)]
+    pub command_timeout: Option<u64>,
+    /// error correction level of generated qr code (passed to vit-kedqr as `--error-correction`):
+    /// one of `low`, `medium`, `quartile` or `high`. Unknown level is rejected when configuration
+    /// is loaded. Higher level survives poor print quality better, but produces denser code
+    #[serde(rename = "qr-error-correction", default)]
+    pub qr_error_correction: Option<QrErrorCorrection>,
+    /// size of qr code module in pixels (passed to vit-kedqr as `--module-size`)
+    #[serde(rename = "qr-module-size", default)]
+    pub qr_module_size: Option<u32>,
     pub token: Option<String>,
+    #[serde(
+        rename = "max-concurrent-jobs",
+        default = "default_max_concurrent_jobs"
+    )]
+    pub max_concurrent_jobs: usize,
+    #[serde(rename = "max-queued-jobs", default = "default_max_queued_jobs")]
+    pub max_queued_jobs: usize,
+    /// number of finished or failed jobs kept for status queries. Oldest ones are evicted first
+    #[serde(rename = "max-completed-jobs", default = "default_max_completed_jobs")]
+    pub max_completed_jobs: usize,
+}
+
+fn default_max_concurrent_jobs() -> usize {
+    1
+}
+
+fn default_max_queued_jobs() -> usize {
+    16
+}
+
+fn default_max_completed_jobs() -> usize {
+    256
 }
 
 pub fn read_config<P: AsRef<Path>>(config: P) -> Result<Configuration, Error> {
@@ -36,3 +77,35 @@ pub enum Error {
     #[error("cannot spawn command")]
     CannotSpawnCommand(#[from] std::io::Error),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn config_with_error_correction(level: &str) -> serde_json::Value {
+        json!({
+            "port": 1234,
+            "result-dir": ".",
+            "jcli": "jcli",
+            "cardano-cli": "cardano-cli",
+            "voter-registration": "voter-registration",
+            "vit-kedqr": "vit-kedqr",
+            "network": "mainnet",
+            "qr-error-correction": level
+        })
+    }
+
+    #[test]
+    fn qr_error_correction_is_validated_on_load() {
+        let configuration: Configuration =
+            serde_json::from_value(config_with_error_correction("quartile")).unwrap();
+        assert_eq!(
+            configuration.qr_error_correction,
+            Some(QrErrorCorrection::Quartile)
+        );
+        assert!(
+            serde_json::from_value::<Configuration>(config_with_error_correction("ultra")).is_err()
+        );
+    }
+}
@@ -1,9 +1,146 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
+/// magic of public cardano testnet, used when no magic is given explicitly
+pub const DEFAULT_TESTNET_MAGIC: u32 = 1097911063;
+
+/// Cardano network. In configuration it is represented as string:
+/// `mainnet`, `testnet` (public testnet) or `testnet:<magic>`
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum NetworkType {
+    Mainnet,
+    Testnet(u32),
+}
+
+impl fmt::Display for NetworkType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Mainnet => write!(f, "mainnet"),
+            Self::Testnet(magic) => write!(f, "testnet:{}", magic),
+        }
+    }
+}
+
+impl FromStr for NetworkType {
+    type Err = NetworkTypeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let input = s.trim().to_lowercase();
+        let mut parts = input.splitn(2, ':');
+        match (parts.next(), parts.next()) {
+            (Some("mainnet"), None) => Ok(Self::Mainnet),
+            (Some("testnet"), None) => Ok(Self::Testnet(DEFAULT_TESTNET_MAGIC)),
+            (Some("testnet"), Some(magic)) => magic
+                .parse()
+                .map(Self::Testnet)
+                .map_err(|_| NetworkTypeParseError::InvalidMagic(magic.to_string())),
+            _ => Err(NetworkTypeParseError::UnknownNetwork(s.to_string())),
+        }
+    }
+}
+
+impl Serialize for NetworkType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for NetworkType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match NetworkTypeRepr::deserialize(deserializer)? {
+            NetworkTypeRepr::Text(text) => text.parse().map_err(serde::de::Error::custom),
+            NetworkTypeRepr::Legacy(LegacyNetworkType::Mainnet) => Ok(Self::Mainnet),
+            NetworkTypeRepr::Legacy(LegacyNetworkType::Testnet(magic)) => Ok(Self::Testnet(magic)),
+        }
+    }
+}
+
+/// Accepts both string representation and old enum one (`{"testnet": <magic>}`),
+/// so existing configurations are still valid
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum NetworkTypeRepr {
+    Text(String),
+    Legacy(LegacyNetworkType),
+}
+
+#[derive(Deserialize)]
+enum LegacyNetworkType {
     #[serde(rename = "mainnet")]
     Mainnet,
     #[serde(rename = "testnet")]
     Testnet(u32),
 }
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum NetworkTypeParseError {
+    #[error("unknown network '{0}', expected: mainnet, testnet or testnet:<magic>")]
+    UnknownNetwork(String),
+    #[error("invalid testnet magic '{0}'")]
+    InvalidMagic(String),
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{NetworkType, NetworkTypeParseError, DEFAULT_TESTNET_MAGIC};
+
+    #[test]
+    pub fn test_parse_mainnet() {
+        assert_eq!("mainnet".parse(), Ok(NetworkType::Mainnet));
+        assert_eq!(NetworkType::Mainnet.to_string(), "mainnet");
+    }
+
+    #[test]
+    pub fn test_parse_testnet_without_magic() {
+        assert_eq!(
+            "testnet".parse(),
+            Ok(NetworkType::Testnet(DEFAULT_TESTNET_MAGIC))
+        );
+    }
+
+    #[test]
+    pub fn test_parse_testnet_with_magic() {
+        assert_eq!("testnet:42".parse(), Ok(NetworkType::Testnet(42)));
+        assert_eq!(NetworkType::Testnet(42).to_string(), "testnet:42");
+    }
+
+    #[test]
+    pub fn test_parse_invalid() {
+        assert_eq!(
+            "preprod".parse::<NetworkType>(),
+            Err(NetworkTypeParseError::UnknownNetwork("preprod".to_string()))
+        );
+        assert_eq!(
+            "testnet:abc".parse::<NetworkType>(),
+            Err(NetworkTypeParseError::InvalidMagic("abc".to_string()))
+        );
+    }
+
+    #[test]
+    pub fn test_serde_round_trip() {
+        for network in &[NetworkType::Mainnet, NetworkType::Testnet(42)] {
+            let json = serde_json::to_string(network).unwrap();
+            assert_eq!(
+                &serde_json::from_str::<NetworkType>(&json).unwrap(),
+                network
+            );
+
+            let yaml = serde_yaml::to_string(network).unwrap();
+            assert_eq!(
+                &serde_yaml::from_str::<NetworkType>(&yaml).unwrap(),
+                network
+            );
+        }
+    }
+
+    #[test]
+    pub fn test_deserialize_legacy_format() {
+        assert_eq!(
+            serde_json::from_str::<NetworkType>(r#"{"testnet": 42}"#).unwrap(),
+            NetworkType::Testnet(42)
+        );
+    }
+}
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Error correction level of qr code generated by vit-kedqr. Higher level survives
+/// poor print quality better, at the cost of denser code
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QrErrorCorrection {
+    Low,
+    Medium,
+    Quartile,
+    High,
+}
+
+impl fmt::Display for QrErrorCorrection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Low => write!(f, "low"),
+            Self::Medium => write!(f, "medium"),
+            Self::Quartile => write!(f, "quartile"),
+            Self::High => write!(f, "high"),
+        }
+    }
+}
@@ -1,24 +1,33 @@
 pub type ContextLock = Arc<Mutex<Context>>;
 use crate::config::Configuration;
-use crate::job::JobOutputInfo;
+use crate::job::{JobOutputInfo, JobPhase, PhaseUpdate};
 use crate::request::Request;
 use crate::rest::ServerStopper;
 use chrono::{NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
 use std::fmt;
 use std::net::SocketAddr;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 
+/// How long idempotency key points to job it was used to create
+pub const IDEMPOTENCY_KEY_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
 pub struct Context {
     server_stopper: Option<ServerStopper>,
     config: Configuration,
     working_dir: PathBuf,
     address: SocketAddr,
-    state: State,
+    jobs: HashMap<Uuid, State>,
+    queue: VecDeque<Uuid>,
+    running: usize,
+    completed: VecDeque<Uuid>,
+    idempotency_keys: HashMap<String, (Uuid, Instant)>,
 }
 
 impl Context {
@@ -28,7 +37,11 @@ impl Context {
             address: ([0, 0, 0, 0], config.port).into(),
             config,
             working_dir: working_dir.as_ref().to_path_buf(),
-            state: State::Idle,
+            jobs: HashMap::new(),
+            queue: VecDeque::new(),
+            running: 0,
+            completed: VecDeque::new(),
+            idempotency_keys: HashMap::new(),
         }
     }
 
@@ -40,77 +53,175 @@ impl Context {
         &self.server_stopper
     }
 
+    /// Puts request in queue. Request is rejected when queue is already full,
+    /// so client can retry later instead of overloading host
     pub fn new_run(&mut self, request: Request) -> Result<Uuid, Error> {
-        match self.state {
-            State::Idle | State::Finished { .. } => {
-                let id = Uuid::new_v4();
-                self.state = State::RequestToStart {
-                    job_id: id,
-                    request,
-                };
-                Ok(id)
-            }
-            _ => Err(Error::RegistrationInProgress),
+        if self.queue.len() >= self.config.max_queued_jobs {
+            return Err(Error::QueueFull(self.queue.len()));
+        }
+        let id = Uuid::new_v4();
+        self.jobs.insert(
+            id,
+            State::RequestToStart {
+                job_id: id,
+                request,
+            },
+        );
+        self.queue.push_back(id);
+        Ok(id)
+    }
+
+    /// Same as `new_run`, but when request with the same idempotency key was already
+    /// accepted (and key did not expire yet) returns id of existing job instead of starting new one
+    pub fn new_run_with_idempotency_key(
+        &mut self,
+        request: Request,
+        idempotency_key: Option<String>,
+    ) -> Result<Uuid, Error> {
+        let key = match idempotency_key {
+            Some(key) => key,
+            None => return self.new_run(request),
+        };
+
+        self.idempotency_keys
+            .retain(|_, (_, created)| created.elapsed() < IDEMPOTENCY_KEY_TTL);
+
+        if let Some((id, _)) = self.idempotency_keys.get(&key) {
+            return Ok(*id);
+        }
+
+        let id = self.new_run(request)?;
+        self.idempotency_keys.insert(key, (id, Instant::now()));
+        Ok(id)
+    }
+
+    /// Takes next request from queue if there is free worker slot
+    pub fn next_request_to_start(&mut self) -> Option<(Uuid, Request)> {
+        if self.running >= self.config.max_concurrent_jobs {
+            return None;
+        }
+        let job_id = self.queue.front().copied()?;
+        match self.jobs.get(&job_id) {
+            Some(State::RequestToStart { request, .. }) => Some((job_id, request.clone())),
+            _ => None,
         }
     }
 
-    pub fn run_started(&mut self) -> Result<(), Error> {
-        match &self.state {
-            State::RequestToStart { job_id, request } => {
-                self.state = State::Running {
+    pub fn run_started(&mut self, id: Uuid) -> Result<(), Error> {
+        match self.jobs.get(&id) {
+            Some(State::RequestToStart { job_id, request }) => {
+                let state = State::Running {
                     job_id: *job_id,
                     start: Utc::now().naive_utc(),
                     request: request.clone(),
+                    current_phase: None,
+                    phases: Vec::new(),
                 };
+                self.jobs.insert(id, state);
+                self.queue.retain(|queued| *queued != id);
+                self.running += 1;
                 Ok(())
             }
             _ => Err(Error::NoRequestToStart),
         }
     }
 
-    pub fn run_finished(&mut self, info: JobOutputInfo) -> Result<(), Error> {
-        match &self.state {
-            State::Running {
+    /// Records phase change of running job, so it is visible in job status
+    pub fn update_phase(&mut self, id: Uuid, update: PhaseUpdate) -> Result<(), Error> {
+        match self.jobs.get_mut(&id) {
+            Some(State::Running {
+                current_phase,
+                phases,
+                ..
+            }) => {
+                match update {
+                    PhaseUpdate::Started(name) => *current_phase = Some(name),
+                    PhaseUpdate::Finished(phase) => {
+                        *current_phase = None;
+                        phases.push(phase);
+                    }
+                }
+                Ok(())
+            }
+            _ => Err(Error::RegistrationNotStarted),
+        }
+    }
+
+    pub fn run_finished(&mut self, id: Uuid, info: JobOutputInfo) -> Result<(), Error> {
+        match self.jobs.get(&id) {
+            Some(State::Running {
                 job_id,
                 start,
                 request,
-            } => {
-                self.state = State::Finished {
+                ..
+            }) => {
+                let state = State::Finished {
                     job_id: *job_id,
                     start: *start,
                     end: Utc::now().naive_utc(),
                     request: request.clone(),
                     info,
                 };
+                self.jobs.insert(id, state);
+                self.run_completed(id);
                 Ok(())
             }
             _ => Err(Error::RegistrationNotStarted),
         }
     }
 
-    pub fn status_by_id(&self, id: Uuid) -> Result<State, Error> {
-        match self.state {
-            State::Idle => Err(Error::NoJobRun),
-            State::RequestToStart { .. } => Ok(self.state.clone()),
-            State::Running { job_id, .. } => {
-                if job_id == id {
-                    Ok(self.state.clone())
-                } else {
-                    Err(Error::JobNotFound)
-                }
+    /// Marks job which failed before producing output, keeping failure reason for status queries
+    pub fn run_failed<S: Into<String>>(&mut self, id: Uuid, reason: S) -> Result<(), Error> {
+        match self.jobs.get(&id) {
+            Some(State::Running {
+                job_id,
+                start,
+                request,
+                current_phase,
+                phases,
+            }) => {
+                let state = State::Failed {
+                    job_id: *job_id,
+                    start: *start,
+                    end: Utc::now().naive_utc(),
+                    request: request.clone(),
+                    reason: reason.into(),
+                    failed_phase: current_phase.clone(),
+                    phases: phases.clone(),
+                };
+                self.jobs.insert(id, state);
+                self.run_completed(id);
+                Ok(())
             }
-            State::Finished { job_id, .. } => {
-                if job_id == id {
-                    Ok(self.state.clone())
-                } else {
-                    Err(Error::JobNotFound)
-                }
+            _ => Err(Error::RegistrationNotStarted),
+        }
+    }
+
+    /// Releases worker slot and evicts oldest completed jobs over the limit
+    fn run_completed(&mut self, id: Uuid) {
+        self.running -= 1;
+        self.completed.push_back(id);
+        while self.completed.len() > self.config.max_completed_jobs {
+            if let Some(evicted) = self.completed.pop_front() {
+                self.jobs.remove(&evicted);
             }
         }
     }
 
-    pub fn state(&self) -> &State {
-        &self.state
+    pub fn status_by_id(&self, id: Uuid) -> Result<State, Error> {
+        if self.jobs.is_empty() {
+            return Err(Error::NoJobRun);
+        }
+        self.jobs.get(&id).cloned().ok_or(Error::JobNotFound)
+    }
+
+    pub fn queue_status(&self) -> QueueStatus {
+        QueueStatus {
+            queued: self.queue.len(),
+            running: self.running,
+            max_queued_jobs: self.config.max_queued_jobs,
+            max_concurrent_jobs: self.config.max_concurrent_jobs,
+        }
     }
 
     pub fn address(&self) -> &SocketAddr {
@@ -134,6 +245,14 @@ impl Context {
     }
 }
 
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug)]
+pub struct QueueStatus {
+    pub queued: usize,
+    pub running: usize,
+    pub max_queued_jobs: usize,
+    pub max_concurrent_jobs: usize,
+}
+
 #[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug)]
 pub enum State {
     Idle,
@@ -145,6 +264,12 @@ pub enum State {
         job_id: Uuid,
         start: NaiveDateTime,
         request: Request,
+        /// phase in progress, if any
+        #[serde(default)]
+        current_phase: Option<String>,
+        /// phases already finished, in order of execution
+        #[serde(default)]
+        phases: Vec<JobPhase>,
     },
     Finished {
         job_id: Uuid,
@@ -153,14 +278,26 @@ pub enum State {
         request: Request,
         info: JobOutputInfo,
     },
+    Failed {
+        job_id: Uuid,
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+        request: Request,
+        reason: String,
+        /// phase which was in progress when job failed
+        #[serde(default)]
+        failed_phase: Option<String>,
+        #[serde(default)]
+        phases: Vec<JobPhase>,
+    },
 }
 
 use thiserror::Error;
 
 #[derive(Debug, Error, Deserialize, Serialize)]
 pub enum Error {
-    #[error("job is in progress.")]
-    RegistrationInProgress,
+    #[error("job queue is full ({0} jobs waiting), try again later")]
+    QueueFull(usize),
     #[error("job hasn't been started")]
     RegistrationNotStarted,
     #[error("no request to start")]
@@ -176,3 +313,111 @@ impl fmt::Display for State {
         write!(f, "{:?}", self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(max_completed_jobs: usize) -> Context {
+        let mut config: Configuration = serde_json::from_str(
+            r#"{
+                "port": 0,
+                "result-dir": ".",
+                "jcli": "jcli",
+                "cardano-cli": "cardano-cli",
+                "voter-registration": "voter-registration",
+                "vit-kedqr": "vit-kedqr",
+                "network": "mainnet"
+            }"#,
+        )
+        .unwrap();
+        config.max_completed_jobs = max_completed_jobs;
+        Context::new(config, ".")
+    }
+
+    fn request() -> Request {
+        Request {
+            payment_skey: "5820aa".to_string(),
+            payment_vkey: "5820bb".to_string(),
+            stake_skey: "5820cc".to_string(),
+            stake_vkey: "5820dd".to_string(),
+            dry_run: true,
+            key_formats: Default::default(),
+        }
+    }
+
+    fn start(context: &mut Context) -> Uuid {
+        let id = context.new_run(request()).unwrap();
+        context.run_started(id).unwrap();
+        id
+    }
+
+    #[test]
+    fn failed_job_keeps_reason() {
+        let mut context = context(1);
+        let id = start(&mut context);
+        context.run_failed(id, "cardano-cli failed").unwrap();
+
+        match context.status_by_id(id).unwrap() {
+            State::Failed { reason, .. } => assert_eq!(reason, "cardano-cli failed"),
+            other => panic!("unexpected state: {:?}", other),
+        }
+        assert_eq!(context.queue_status().running, 0);
+    }
+
+    #[test]
+    fn phases_are_visible_while_running_and_after_failure() {
+        let mut context = context(1);
+        let id = start(&mut context);
+        let start = Utc::now().naive_utc();
+        context
+            .update_phase(id, PhaseUpdate::Started("keys".to_string()))
+            .unwrap();
+        context
+            .update_phase(id, PhaseUpdate::Finished(JobPhase::finished("keys", start)))
+            .unwrap();
+        context
+            .update_phase(id, PhaseUpdate::Started("utxo_query".to_string()))
+            .unwrap();
+
+        match context.status_by_id(id).unwrap() {
+            State::Running {
+                current_phase,
+                phases,
+                ..
+            } => {
+                assert_eq!(current_phase, Some("utxo_query".to_string()));
+                assert_eq!(phases.len(), 1);
+            }
+            other => panic!("unexpected state: {:?}", other),
+        }
+
+        context.run_failed(id, "node unavailable").unwrap();
+        match context.status_by_id(id).unwrap() {
+            State::Failed {
+                failed_phase,
+                phases,
+                ..
+            } => {
+                assert_eq!(failed_phase, Some("utxo_query".to_string()));
+                assert_eq!(phases[0].name, "keys");
+            }
+            other => panic!("unexpected state: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn oldest_completed_jobs_are_evicted() {
+        let mut context = context(1);
+        let first = start(&mut context);
+        context.run_failed(first, "first").unwrap();
+        let second = start(&mut context);
+        context.run_failed(second, "second").unwrap();
+
+        assert!(matches!(
+            context.status_by_id(first),
+            Err(Error::JobNotFound)
+        ));
+        assert!(context.status_by_id(second).is_ok());
+    }
+}
@@ -1,7 +1,9 @@
+use crate::job::artifacts;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 use std::path::PathBuf;
 use thiserror::Error;
+use uuid::Uuid;
 use walkdir::WalkDir;
 
 #[derive(Serialize, Deserialize)]
@@ -32,6 +34,32 @@ impl FolderDump {
 pub enum Error {
     #[error("root folder does not exist yet. try to start backend")]
     RootFolderDoesNotExist(#[from] walkdir::Error),
+    #[error("artifact '{0}' is not available for download")]
+    ArtifactNotAllowed(String),
+    #[error("artifact '{0}' not found")]
+    ArtifactNotFound(String),
+    #[error("cannot read artifact")]
+    CannotReadArtifact(#[from] std::io::Error),
+}
+
+/// Reads job artifact from its result directory. Only names listed in `job::artifacts`
+/// are served, so request cannot reach any other file
+pub fn read_artifact<P: AsRef<Path>>(
+    root: P,
+    job_id: Uuid,
+    name: &str,
+) -> Result<(Vec<u8>, &'static str), Error> {
+    let content_type = artifacts()
+        .iter()
+        .find(|(artifact, _)| artifact == name)
+        .map(|(_, content_type)| *content_type)
+        .ok_or_else(|| Error::ArtifactNotAllowed(name.to_string()))?;
+
+    let path = root.as_ref().join(job_id.to_string()).join(name);
+    if !path.exists() {
+        return Err(Error::ArtifactNotFound(name.to_string()));
+    }
+    Ok((std::fs::read(path)?, content_type))
 }
 
 pub fn dump_json<P: AsRef<Path>>(root: P) -> Result<FolderDump, Error> {
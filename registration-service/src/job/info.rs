@@ -1,7 +1,52 @@
+use chrono::{NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
 pub struct JobOutputInfo {
     pub slot_no: u64,
     pub funds: u64,
+    pub registration: VoterRegistrationOutput,
+    /// Registration transaction was prepared but not submitted
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Timings of job steps in order of execution
+    #[serde(default)]
+    pub phases: Vec<JobPhase>,
+}
+
+/// Funds available on payment address, used to check wallet before registration
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+pub struct FundsInfo {
+    pub address: String,
+    pub funds: u64,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+pub struct JobPhase {
+    pub name: String,
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+    pub duration_ms: i64,
+}
+
+impl JobPhase {
+    /// Phase which started at given time and ends now
+    pub fn finished<S: Into<String>>(name: S, start: NaiveDateTime) -> Self {
+        let end = Utc::now().naive_utc();
+        Self {
+            name: name.into(),
+            start,
+            end,
+            duration_ms: (end - start).num_milliseconds(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+pub struct VoterRegistrationOutput {
+    pub vote_public_key: String,
+    pub stake_public_key: String,
+    pub rewards_address: String,
+    pub slot_no: u64,
+    pub signature: String,
 }
@@ -1,31 +1,78 @@
 mod info;
 
-use crate::config::NetworkType;
+use crate::config::{Configuration, NetworkType, QrErrorCorrection};
 use crate::request::Request;
 use crate::utils::CommandExt as _;
-pub use info::JobOutputInfo;
-use jormungandr_integration_tests::common::jcli::JCli;
+use assert_fs::TempDir;
+use chrono::{NaiveDateTime, Utc};
+pub use info::{FundsInfo, JobOutputInfo, JobPhase, VoterRegistrationOutput};
 use jortestkit::prelude::read_file;
 use jortestkit::prelude::ProcessOutput;
 use serde::{Deserialize, Serialize};
-use std::io::Write;
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
-use std::process::Command;
-use std::process::ExitStatus;
+use std::process::{Child, Command, ExitStatus, Output, Stdio};
 use std::str::FromStr;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 
 const PIN: &str = "1234";
+pub const DEFAULT_COMMAND_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+/// vit-kedqr flags which are not supported by older releases
+const QR_ERROR_CORRECTION_FLAG: &str = "--error-correction";
+const QR_MODULE_SIZE_FLAG: &str = "--module-size";
+const COMMAND_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Files produced by job which are safe to share with client, together with their content types.
+/// Signing keys are deliberately not listed
+pub fn artifacts() -> [(String, &'static str); 4] {
+    [
+        ("catalyst-vote.pkey".to_string(), "text/plain"),
+        ("payment.addr".to_string(), "text/plain"),
+        ("vote-registration.tx".to_string(), "application/json"),
+        (qr_code_file_name(), "image/png"),
+    ]
+}
+
+fn qr_code_file_name() -> String {
+    format!("qrcode_pin_{}.png", PIN)
+}
 
 pub struct VoteRegistrationJobBuilder {
     job: VoteRegistrationJob,
+    keep_artifacts: bool,
 }
 
 impl VoteRegistrationJobBuilder {
     pub fn new() -> Self {
         Self {
             job: Default::default(),
+            keep_artifacts: false,
+        }
+    }
+
+    /// Builder with executables, network and command settings taken from service configuration
+    pub fn from_configuration(configuration: &Configuration) -> Self {
+        let mut builder = Self::new()
+            .with_jcli(&configuration.jcli)
+            .with_cardano_cli(&configuration.cardano_cli)
+            .with_voter_registration(&configuration.voter_registration)
+            .with_network(configuration.network)
+            .with_kedqr(&configuration.vit_kedqr)
+            .with_envs(configuration.env.clone());
+        if let Some(command_timeout) = configuration.command_timeout {
+            builder = builder.with_command_timeout(Some(Duration::from_secs(command_timeout)));
+        }
+        if let Some(node_socket) = &configuration.node_socket {
+            builder = builder.with_node_socket(node_socket);
         }
+        builder.with_qr_code_options(
+            configuration.qr_error_correction,
+            configuration.qr_module_size,
+        )
     }
 
     pub fn with_jcli<P: AsRef<Path>>(mut self, jcli: P) -> Self {
@@ -53,12 +100,74 @@ impl VoteRegistrationJobBuilder {
         self
     }
 
+    /// Socket of cardano node used by commands which talk to node. Without it
+    /// `CARDANO_NODE_SOCKET_PATH` is inherited from environment
+    pub fn with_node_socket<P: AsRef<Path>>(mut self, node_socket: P) -> Self {
+        self.job.node_socket = Some(node_socket.as_ref().to_path_buf());
+        self
+    }
+
+    /// Maximum time single external command can run. `None` disables timeout
+    pub fn with_command_timeout(mut self, command_timeout: Option<Duration>) -> Self {
+        self.job.command_timeout = command_timeout;
+        self
+    }
+
+    /// Environment variable set for every spawned command
+    pub fn with_env<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.job.env.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn with_envs(mut self, env: HashMap<String, String>) -> Self {
+        self.job.env.extend(env);
+        self
+    }
+
     pub fn with_working_dir<P: AsRef<Path>>(mut self, working_dir: P) -> Self {
         self.job.working_dir = working_dir.as_ref().to_path_buf();
+        self.job.temp_dir = None;
         self
     }
 
-    pub fn build(self) -> VoteRegistrationJob {
+    /// Job works in temporary directory, which is removed together with job
+    /// (unless artifacts are kept). Use `VoteRegistrationJob::working_dir` to inspect
+    /// artifacts before job is dropped
+    pub fn with_temp_working_dir(mut self) -> Self {
+        let temp_dir = TempDir::new().expect("cannot create temporary working directory");
+        self.job.working_dir = temp_dir.path().to_path_buf();
+        self.job.temp_dir = Some(temp_dir);
+        self
+    }
+
+    /// Error correction level and module size passed to vit-kedqr. When not set
+    /// vit-kedqr defaults are used
+    pub fn with_qr_code_options(
+        mut self,
+        error_correction: Option<QrErrorCorrection>,
+        module_size: Option<u32>,
+    ) -> Self {
+        self.job.qr_error_correction = error_correction;
+        self.job.qr_module_size = module_size;
+        self
+    }
+
+    /// Produce all artifacts without submitting registration transaction
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.job.dry_run = dry_run;
+        self
+    }
+
+    /// Do not remove temporary working directory when job is dropped
+    pub fn keep_artifacts(mut self, keep_artifacts: bool) -> Self {
+        self.keep_artifacts = keep_artifacts;
+        self
+    }
+
+    pub fn build(mut self) -> VoteRegistrationJob {
+        if self.keep_artifacts {
+            self.job.temp_dir = self.job.temp_dir.map(TempDir::into_persistent);
+        }
         self.job
     }
 }
@@ -69,7 +178,14 @@ pub struct VoteRegistrationJob {
     voter_registration: PathBuf,
     vit_kedqr: PathBuf,
     network: NetworkType,
+    node_socket: Option<PathBuf>,
+    env: HashMap<String, String>,
+    command_timeout: Option<Duration>,
+    qr_error_correction: Option<QrErrorCorrection>,
+    qr_module_size: Option<u32>,
+    dry_run: bool,
     working_dir: PathBuf,
+    temp_dir: Option<TempDir>,
 }
 
 impl Default for VoteRegistrationJob {
@@ -80,18 +196,72 @@ impl Default for VoteRegistrationJob {
             voter_registration: PathBuf::from_str("voter-registration").unwrap(),
             vit_kedqr: PathBuf::from_str("vit-kedqr").unwrap(),
             network: NetworkType::Mainnet,
+            node_socket: None,
+            env: HashMap::new(),
+            command_timeout: Some(DEFAULT_COMMAND_TIMEOUT),
+            qr_error_correction: None,
+            qr_module_size: None,
+            dry_run: false,
             working_dir: PathBuf::from_str(".").unwrap(),
+            temp_dir: None,
         }
     }
 }
 
 impl VoteRegistrationJob {
+    pub fn working_dir(&self) -> &Path {
+        &self.working_dir
+    }
+
+    /// Command with job environment applied
+    fn command<P: AsRef<OsStr>>(&self, program: P) -> Command {
+        let mut command = Command::new(program);
+        command.envs(&self.env).env_node_socket(&self.node_socket);
+        command
+    }
+
+    /// Runs command with inherited output. Command is killed when it does not finish within timeout
+    fn status(&self, command: &mut Command) -> Result<ExitStatus, Error> {
+        let mut child = command.spawn()?;
+        wait_with_timeout(&mut child, self.command_timeout, command)
+    }
+
+    /// Runs command and collects its output. Command is killed when it does not finish within timeout
+    fn output(&self, command: &mut Command) -> Result<Output, Error> {
+        let mut child = command
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        let stdout = read_in_background(child.stdout.take());
+        let stderr = read_in_background(child.stderr.take());
+        let status = wait_with_timeout(&mut child, self.command_timeout, command)?;
+        Ok(Output {
+            status,
+            stdout: stdout.join().unwrap_or_default(),
+            stderr: stderr.join().unwrap_or_default(),
+        })
+    }
+
+    /// Runs `jcli key` subcommand and returns its trimmed stdout
+    fn jcli_key(&self, args: &[&OsStr]) -> Result<String, Error> {
+        let mut command = self.command(&self.jcli);
+        command.arg("key").args(args);
+        let output = self.output(&mut command)?;
+        if !output.status.success() {
+            return Err(Error::JcliFailed {
+                command: format!("{:?}", command),
+                stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            });
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
     pub fn generate_payment_address<P: AsRef<Path>, Q: AsRef<Path>>(
         &self,
         verification_key: P,
         output: Q,
     ) -> Result<ExitStatus, Error> {
-        let mut command = Command::new(&self.cardano_cli);
+        let mut command = self.command(&self.cardano_cli);
         command
             .arg("address")
             .arg("build")
@@ -101,10 +271,76 @@ impl VoteRegistrationJob {
             .arg(output.as_ref())
             .arg_network(self.network);
         println!("generate payment addres: {:?}", command);
-        command.status().map_err(Into::into)
+        self.status(&mut command)
+    }
+
+    fn check_node_socket(&self) -> Result<(), Error> {
+        if let Some(node_socket) = &self.node_socket {
+            if !node_socket.exists() {
+                return Err(Error::NodeSocketNotFound(node_socket.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that vit-kedqr accepts configured qr code flags, so job fails before
+    /// registration is submitted instead of at the very end
+    fn check_vit_kedqr_flags(&self) -> Result<(), Error> {
+        let mut flags = Vec::new();
+        if self.qr_error_correction.is_some() {
+            flags.push(QR_ERROR_CORRECTION_FLAG);
+        }
+        if self.qr_module_size.is_some() {
+            flags.push(QR_MODULE_SIZE_FLAG);
+        }
+        if flags.is_empty() {
+            return Ok(());
+        }
+
+        let mut command = self.command(&self.vit_kedqr);
+        command.arg("--help");
+        let help = String::from_utf8_lossy(&self.output(&mut command)?.stdout).into_owned();
+        match flags.into_iter().find(|flag| !help.contains(flag)) {
+            Some(flag) => Err(Error::UnsupportedVitKedqrFlag(flag.to_string())),
+            None => Ok(()),
+        }
+    }
+
+    /// Queries utxo of payment address and returns its funds, without any registration
+    pub fn query_funds(&self, payment_address: &str) -> Result<u64, Error> {
+        self.check_node_socket()?;
+
+        let mut command = self.command(&self.cardano_cli);
+        command
+            .arg("query")
+            .arg("utxo")
+            .arg("--mary-era")
+            .arg_network(self.network)
+            .arg("--address")
+            .arg(payment_address);
+
+        println!("Running cardano_cli: {:?}", command);
+        let funds = get_funds(self.output(&mut command)?.as_multi_line())?;
+        println!("cardano_cli finished");
+        Ok(funds)
     }
 
     pub fn start(&self, request: Request) -> Result<JobOutputInfo, Error> {
+        self.start_with_phases(request, |_| ())
+    }
+
+    /// Same as `start`, but reports each phase as it starts and ends, so progress
+    /// of running job can be observed
+    pub fn start_with_phases<F: FnMut(PhaseUpdate)>(
+        &self,
+        request: Request,
+        on_phase: F,
+    ) -> Result<JobOutputInfo, Error> {
+        self.check_node_socket()?;
+        self.check_vit_kedqr_flags()?;
+
+        let mut phases = Phases::new(on_phase);
+        phases.begin("keys");
         println!("saving payment.skey...");
         let payment_skey = CardanoKeyTemplate::payment_signing_key(request.payment_skey);
         let payment_skey_path = Path::new(&self.working_dir).join("payment.skey");
@@ -130,41 +366,43 @@ impl VoteRegistrationJob {
         println!("stake.vkey saved");
 
         println!("saving catalyst-vote.skey...");
-        let jcli = JCli::new(self.jcli.clone());
-        let private_key = jcli.key().generate_default();
+        let private_key = self.jcli_key(&[
+            OsStr::new("generate"),
+            OsStr::new("--type"),
+            OsStr::new("ed25519Extended"),
+        ])?;
         let private_key_path = Path::new(&self.working_dir).join("catalyst-vote.skey");
         write_content(&private_key, &private_key_path)?;
         println!("catalyst-vote.skey saved");
 
         println!("saving catalyst-vote.pkey...");
-        let public_key = jcli.key().convert_to_public_string(&private_key);
+        let public_key = self.jcli_key(&[
+            OsStr::new("to-public"),
+            OsStr::new("--input"),
+            private_key_path.as_os_str(),
+        ])?;
         let public_key_path = Path::new(&self.working_dir).join("catalyst-vote.pkey");
         write_content(&public_key, &public_key_path)?;
         println!("catalyst-vote.pkey saved");
+        phases.finish();
 
+        phases.begin("payment_address");
         println!("saving payment.addr...");
         let payment_address_path = Path::new(&self.working_dir).join("payment.addr");
         self.generate_payment_address(&payment_vkey_path, &payment_address_path)?;
         println!("payment.addr saved");
+        phases.finish();
 
         let payment_address = read_file(&payment_address_path);
 
-        let mut command = Command::new(&self.cardano_cli);
-        command
-            .arg("query")
-            .arg("utxo")
-            .arg("--mary-era")
-            .arg_network(self.network)
-            .arg("--address")
-            .arg(&payment_address);
-
-        println!("Running cardano_cli: {:?}", command);
-        let funds = get_funds(command.output()?.as_multi_line())?;
-        println!("cardano_cli finished");
+        phases.begin("utxo_query");
+        let funds = self.query_funds(&payment_address)?;
+        phases.finish();
 
         let vote_registration_path = Path::new(&self.working_dir).join("vote-registration.tx");
 
-        let mut command = Command::new(&self.voter_registration);
+        phases.begin("signing");
+        let mut command = self.command(&self.voter_registration);
         command
             .arg("--payment-signing-key")
             .arg(&payment_skey_path)
@@ -182,25 +420,34 @@ impl VoteRegistrationJob {
             .arg(&vote_registration_path);
 
         println!("Running voter-registration: {:?}", command);
-        let slot_no = get_slot_no(command.output()?.as_multi_line())?;
+        let registration =
+            get_voter_registration_output(self.output(&mut command)?.as_multi_line())?;
         println!("voter-registration finished");
+        phases.finish();
+
+        if self.dry_run {
+            println!("dry run: skipping submission of vote-registration.tx");
+        } else {
+            phases.begin("submission");
+            let mut command = self.command(&self.cardano_cli);
+            command
+                .arg("transaction")
+                .arg("submit")
+                .arg("--cardano-mode")
+                .arg_network(self.network)
+                .arg("--tx-file")
+                .arg(&vote_registration_path);
+
+            println!("Running cardano_cli: {:?}", command);
+            self.status(&mut command)?;
+            println!("cardano_cli finished");
+            phases.finish();
+        }
 
-        let mut command = Command::new(&self.cardano_cli);
-        command
-            .arg("transaction")
-            .arg("submit")
-            .arg("--cardano-mode")
-            .arg_network(self.network)
-            .arg("--tx-file")
-            .arg(&vote_registration_path);
-
-        println!("Running cardano_cli: {:?}", command);
-        command.status()?;
-        println!("cardano_cli finished");
-
-        let qrcode = Path::new(&self.working_dir).join(format!("qrcode_pin_{}.png", PIN));
+        phases.begin("qr_code");
+        let qrcode = Path::new(&self.working_dir).join(qr_code_file_name());
 
-        let mut command = Command::new(&self.vit_kedqr);
+        let mut command = self.command(&self.vit_kedqr);
         command
             .arg("--pin")
             .arg(PIN)
@@ -208,11 +455,69 @@ impl VoteRegistrationJob {
             .arg(private_key_path)
             .arg("--output")
             .arg(qrcode);
+        if let Some(error_correction) = self.qr_error_correction {
+            command
+                .arg(QR_ERROR_CORRECTION_FLAG)
+                .arg(error_correction.to_string());
+        }
+        if let Some(module_size) = self.qr_module_size {
+            command
+                .arg(QR_MODULE_SIZE_FLAG)
+                .arg(module_size.to_string());
+        }
         println!("Running vit-kedqr: {:?}", command);
-        command.status()?;
+        self.status(&mut command)?;
         println!("vit-kedqr finished");
+        phases.finish();
+
+        Ok(JobOutputInfo {
+            slot_no: registration.slot_no,
+            funds,
+            registration,
+            dry_run: self.dry_run,
+            phases: phases.into_finished(),
+        })
+    }
+}
+
+/// Change of job phase reported while job runs
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum PhaseUpdate {
+    Started(String),
+    Finished(JobPhase),
+}
 
-        Ok(JobOutputInfo { slot_no, funds })
+/// Collects timings of job phases and passes each change to listener
+struct Phases<F: FnMut(PhaseUpdate)> {
+    finished: Vec<JobPhase>,
+    current: Option<(String, NaiveDateTime)>,
+    listener: F,
+}
+
+impl<F: FnMut(PhaseUpdate)> Phases<F> {
+    fn new(listener: F) -> Self {
+        Self {
+            finished: Vec::new(),
+            current: None,
+            listener,
+        }
+    }
+
+    fn begin(&mut self, name: &str) {
+        self.current = Some((name.to_string(), Utc::now().naive_utc()));
+        (self.listener)(PhaseUpdate::Started(name.to_string()));
+    }
+
+    fn finish(&mut self) {
+        if let Some((name, start)) = self.current.take() {
+            let phase = JobPhase::finished(name, start);
+            self.finished.push(phase.clone());
+            (self.listener)(PhaseUpdate::Finished(phase));
+        }
+    }
+
+    fn into_finished(self) -> Vec<JobPhase> {
+        self.finished
     }
 }
 
@@ -257,12 +562,57 @@ impl CardanoKeyTemplate {
         }
     }
 
+    pub fn key_type(&self) -> &str {
+        &self.r#type
+    }
+
+    pub fn cbor_hex(&self) -> &str {
+        &self.cbor_hex
+    }
+
     pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
         let content = serde_json::to_string(&self)?;
         write_content(&content, path)
     }
 }
 
+fn wait_with_timeout(
+    child: &mut Child,
+    timeout: Option<Duration>,
+    command: &Command,
+) -> Result<ExitStatus, Error> {
+    let timeout = match timeout {
+        Some(timeout) => timeout,
+        None => return child.wait().map_err(Into::into),
+    };
+
+    let started = Instant::now();
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(status);
+        }
+        if started.elapsed() >= timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(Error::CommandTimedOut {
+                command: format!("{:?}", command),
+                timeout,
+            });
+        }
+        std::thread::sleep(COMMAND_POLL_INTERVAL);
+    }
+}
+
+fn read_in_background<R: Read + Send + 'static>(reader: Option<R>) -> JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut content = Vec::new();
+        if let Some(mut reader) = reader {
+            let _ = reader.read_to_end(&mut content);
+        }
+        content
+    })
+}
+
 fn write_content<P: AsRef<Path>>(content: &str, path: P) -> Result<(), Error> {
     let mut file = std::fs::File::create(&path)?;
     file.write_all(content.as_bytes())?;
@@ -281,15 +631,26 @@ pub enum Error {
     CannotParseVoterRegistrationOutput(Vec<String>),
     #[error("cannot parse cardano cli output: {0:?}")]
     CannotParseCardanoCliOutput(Vec<String>),
+    #[error("cardano node socket does not exist: {0:?}")]
+    NodeSocketNotFound(PathBuf),
+    #[error("command did not finish within {} s: {command}", .timeout.as_secs())]
+    CommandTimedOut { command: String, timeout: Duration },
+    #[error("jcli failed: {command}: {stderr}")]
+    JcliFailed { command: String, stderr: String },
+    #[error("vit-kedqr does not support {0}, upgrade it or remove corresponding qr code option")]
+    UnsupportedVitKedqrFlag(String),
 }
 
 /// Supported output: https://docs.cardano.org/projects/cardano-node/en/latest/reference/shelley-genesis.html?highlight=funds#submitting-the-signed-transaction
 ///                             TxHash                                 TxIx        Lovelace
 /// ----------------------------------------------------------------------------------------
 /// d17b4303135a76574f18b28fda25bc82cf29c72eb52e12ad317319714a5aafdb     0         500000000
+/// Any lines before the header separator or after the first row are ignored.
 pub fn get_funds(output: Vec<String>) -> Result<u64, Error> {
     output
-        .get(2)
+        .iter()
+        .skip_while(|x| !is_table_separator(x))
+        .nth(1)
         .ok_or_else(|| Error::CannotParseCardanoCliOutput(output.clone()))?
         .split_whitespace()
         .nth(2)
@@ -298,28 +659,50 @@ pub fn get_funds(output: Vec<String>) -> Result<u64, Error> {
         .map_err(|_| Error::CannotParseCardanoCliOutput(output.clone()))
 }
 
+fn is_table_separator(line: &str) -> bool {
+    let line = line.trim();
+    !line.is_empty() && line.chars().all(|c| c == '-')
+}
+
 /// Supported output:
 /// Vote public key used        (hex): c6b6d184ea26781f00b9034ec0ba974f2f833788ce2e24cc37e9e8f41131e1fa
 /// Stake public key used       (hex): e542b6a0ced80e1ab5bda70311bf643b9011ee04411737f3e0136825ef47f2d8
 /// Rewards address used        (hex): 60170bc7c5218b7dcce40e5a232bcf01799cf55587131170f40ab6c541
 /// Slot registered:                   25398498
 /// Vote registration signature (hex): e5cc2e1a9344794cbad76bb65d485776aa560baca6133cdfe77827b15dd0e4c883c32e7177dc15d55e34f79df7ffaebca4d271271c6615b0dacc90e36fb22f03
+/// Fields are looked up by their label, so any additional log lines are ignored.
+pub fn get_voter_registration_output(
+    output: Vec<String>,
+) -> Result<VoterRegistrationOutput, Error> {
+    let field = |label: &str| -> Result<String, Error> {
+        output
+            .iter()
+            .find(|x| x.trim_start().starts_with(label))
+            .and_then(|x| x.split(':').nth(1))
+            .and_then(|x| x.split_whitespace().next())
+            .map(ToString::to_string)
+            .ok_or_else(|| Error::CannotParseVoterRegistrationOutput(output.clone()))
+    };
+
+    Ok(VoterRegistrationOutput {
+        vote_public_key: field("Vote public key used")?,
+        stake_public_key: field("Stake public key used")?,
+        rewards_address: field("Rewards address used")?,
+        slot_no: field("Slot registered")?
+            .parse()
+            .map_err(|_| Error::CannotParseVoterRegistrationOutput(output.clone()))?,
+        signature: field("Vote registration signature")?,
+    })
+}
+
 pub fn get_slot_no(output: Vec<String>) -> Result<u64, Error> {
-    output
-        .iter()
-        .find(|x| x.contains("Slot registered"))
-        .ok_or_else(|| Error::CannotParseVoterRegistrationOutput(output.clone()))?
-        .split_whitespace()
-        .nth(2)
-        .ok_or_else(|| Error::CannotParseVoterRegistrationOutput(output.clone()))?
-        .parse()
-        .map_err(|_| Error::CannotParseVoterRegistrationOutput(output.clone()))
+    get_voter_registration_output(output).map(|registration| registration.slot_no)
 }
 
 #[cfg(test)]
 mod tests {
 
-    use super::{get_funds, get_slot_no};
+    use super::{get_funds, get_slot_no, get_voter_registration_output};
 
     #[test]
     pub fn test_funds_extraction() {
@@ -343,4 +726,63 @@ mod tests {
 
         assert_eq!(get_slot_no(content).unwrap(), 25398498);
     }
+
+    #[test]
+    pub fn test_voter_registration_output_extraction() {
+        let content = vec![
+            "Vote public key used        (hex): c6b6d184ea26781f00b9034ec0ba974f2f833788ce2e24cc37e9e8f41131e1fa".to_string(),
+            "Stake public key used       (hex): e542b6a0ced80e1ab5bda70311bf643b9011ee04411737f3e0136825ef47f2d8".to_string(),
+            "Rewards address used        (hex): 60170bc7c5218b7dcce40e5a232bcf01799cf55587131170f40ab6c541".to_string(),
+            "Slot registered:                   25398498".to_string(),
+            "Vote registration signature (hex): e5cc2e1a9344794cbad76bb65d485776aa560baca6133cdfe77827b15dd0e4c883c32e7177dc15d55e34f79df7ffaebca4d271271c6615b0dacc90e36fb22f03".to_string()
+        ];
+
+        let registration = get_voter_registration_output(content).unwrap();
+        assert_eq!(
+            registration.vote_public_key,
+            "c6b6d184ea26781f00b9034ec0ba974f2f833788ce2e24cc37e9e8f41131e1fa"
+        );
+        assert_eq!(
+            registration.stake_public_key,
+            "e542b6a0ced80e1ab5bda70311bf643b9011ee04411737f3e0136825ef47f2d8"
+        );
+        assert_eq!(
+            registration.rewards_address,
+            "60170bc7c5218b7dcce40e5a232bcf01799cf55587131170f40ab6c541"
+        );
+        assert_eq!(registration.slot_no, 25398498);
+        assert_eq!(
+            registration.signature,
+            "e5cc2e1a9344794cbad76bb65d485776aa560baca6133cdfe77827b15dd0e4c883c32e7177dc15d55e34f79df7ffaebca4d271271c6615b0dacc90e36fb22f03"
+        );
+    }
+
+    #[test]
+    pub fn test_funds_extraction_with_noise() {
+        let content = vec![
+            "Warning: the --mary-era flag is deprecated".to_string(),
+            "Querying node at /tmp/node.socket".to_string(),
+            "    TxHash                                 TxIx        Lovelace".to_string(),
+            "----------------------------------------------------------------------------------------".to_string(),
+            "d17b4303135a76574f18b28fda25bc82cf29c72eb52e12ad317319714a5aafdb     0         500000000".to_string(),
+            "Done".to_string(),
+        ];
+        assert_eq!(get_funds(content).unwrap(), 500000000);
+    }
+
+    #[test]
+    pub fn test_voter_registration_output_extraction_with_noise() {
+        let content = vec![
+            "voter-registration v0.1.0".to_string(),
+            "[Info] Slot registered will be taken from the node tip".to_string(),
+            "Vote public key used        (hex): c6b6d184ea26781f00b9034ec0ba974f2f833788ce2e24cc37e9e8f41131e1fa".to_string(),
+            "Stake public key used       (hex): e542b6a0ced80e1ab5bda70311bf643b9011ee04411737f3e0136825ef47f2d8".to_string(),
+            "Rewards address used        (hex): 60170bc7c5218b7dcce40e5a232bcf01799cf55587131170f40ab6c541".to_string(),
+            "Slot registered:                   25398498".to_string(),
+            "Vote registration signature (hex): e5cc2e1a9344794cbad76bb65d485776aa560baca6133cdfe77827b15dd0e4c883c32e7177dc15d55e34f79df7ffaebca4d271271c6615b0dacc90e36fb22f03".to_string(),
+            "Transaction written to vote-registration.tx".to_string(),
+        ];
+
+        assert_eq!(get_slot_no(content).unwrap(), 25398498);
+    }
 }
@@ -1,9 +1,212 @@
+use crate::job::CardanoKeyTemplate;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use thiserror::Error;
 
+/// Upper bound for single hex encoded key. Cardano extended keys in cbor
+/// take 260 characters, so anything much bigger is not a key
+pub const MAX_KEY_HEX_LENGTH: usize = 512;
+
+/// Each key can be sent either as inline hex (`"payment_skey": "5820..."`) or as content
+/// of cardano-cli key file (`"payment_skey": {"type": ..., "description": ..., "cborHex": ...}`).
+/// Both forms are normalized to hex and form used for each key is kept in `key_formats`
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[serde(try_from = "RequestRepr")]
 pub struct Request {
     pub payment_skey: String,
     pub payment_vkey: String,
     pub stake_skey: String,
     pub stake_vkey: String,
+    /// Job runs whole pipeline but does not submit registration transaction.
+    /// Set by `dry_run` query parameter of `/api/job/new`
+    pub dry_run: bool,
+    pub key_formats: BTreeMap<String, KeyFormat>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyFormat {
+    Hex,
+    CardanoKeyFile,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum KeyInput {
+    Hex(String),
+    CardanoKeyFile(CardanoKeyTemplate),
+}
+
+#[derive(Deserialize)]
+struct RequestRepr {
+    payment_skey: Option<KeyInput>,
+    payment_vkey: Option<KeyInput>,
+    stake_skey: Option<KeyInput>,
+    stake_vkey: Option<KeyInput>,
+    #[serde(default)]
+    dry_run: bool,
+}
+
+impl TryFrom<RequestRepr> for Request {
+    type Error = String;
+
+    fn try_from(repr: RequestRepr) -> Result<Self, Self::Error> {
+        let mut key_formats = BTreeMap::new();
+        let mut normalize =
+            |name: &str, input: Option<KeyInput>, expected: CardanoKeyTemplate| match input {
+                None => Ok(String::new()),
+                Some(KeyInput::Hex(hex)) => {
+                    key_formats.insert(name.to_string(), KeyFormat::Hex);
+                    Ok(hex)
+                }
+                Some(KeyInput::CardanoKeyFile(template)) => {
+                    if template.key_type() != expected.key_type() {
+                        return Err(format!(
+                            "{}: expected key file of type '{}', got '{}'",
+                            name,
+                            expected.key_type(),
+                            template.key_type()
+                        ));
+                    }
+                    key_formats.insert(name.to_string(), KeyFormat::CardanoKeyFile);
+                    Ok(template.cbor_hex().to_string())
+                }
+            };
+
+        let payment_skey = normalize(
+            "payment_skey",
+            repr.payment_skey,
+            CardanoKeyTemplate::payment_signing_key(String::new()),
+        )?;
+        let payment_vkey = normalize(
+            "payment_vkey",
+            repr.payment_vkey,
+            CardanoKeyTemplate::payment_verification_key(String::new()),
+        )?;
+        let stake_skey = normalize(
+            "stake_skey",
+            repr.stake_skey,
+            CardanoKeyTemplate::stake_signing_key(String::new()),
+        )?;
+        let stake_vkey = normalize(
+            "stake_vkey",
+            repr.stake_vkey,
+            CardanoKeyTemplate::stake_verification_key(String::new()),
+        )?;
+
+        Ok(Self {
+            payment_skey,
+            payment_vkey,
+            stake_skey,
+            stake_vkey,
+            dry_run: repr.dry_run,
+            key_formats,
+        })
+    }
+}
+
+impl Request {
+    /// Checks that all keys are present, are not oversized and are hex encoded,
+    /// so malformed request is rejected before any job is started
+    pub fn validate(&self) -> Result<(), Error> {
+        let fields = [
+            ("payment_skey", &self.payment_skey),
+            ("payment_vkey", &self.payment_vkey),
+            ("stake_skey", &self.stake_skey),
+            ("stake_vkey", &self.stake_vkey),
+        ];
+
+        let invalid: Vec<String> = fields
+            .iter()
+            .filter_map(|(name, value)| {
+                if value.is_empty() {
+                    Some(format!("{}: missing", name))
+                } else if value.len() > MAX_KEY_HEX_LENGTH {
+                    Some(format!(
+                        "{}: too long ({} > {} characters)",
+                        name,
+                        value.len(),
+                        MAX_KEY_HEX_LENGTH
+                    ))
+                } else if value.len() % 2 != 0 || !value.chars().all(|c| c.is_ascii_hexdigit()) {
+                    Some(format!("{}: not a hex string", name))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        if invalid.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::InvalidFields(invalid))
+        }
+    }
+}
+
+/// Upper bound for bech32 encoded cardano address
+pub const MAX_ADDRESS_LENGTH: usize = 128;
+
+/// Checks that payment address looks like bech32 address before it is passed to cardano-cli
+pub fn validate_address(address: &str) -> Result<(), Error> {
+    if address.is_empty()
+        || address.len() > MAX_ADDRESS_LENGTH
+        || !address
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        return Err(Error::InvalidFields(vec![format!(
+            "address: not a bech32 address: {}",
+            address
+        )]));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Error, Deserialize, Serialize)]
+pub enum Error {
+    #[error("invalid request: {}", .0.join(", "))]
+    InvalidFields(Vec<String>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_are_accepted_as_hex_or_key_files() {
+        let request: Request = serde_json::from_value(serde_json::json!({
+            "payment_skey": {
+                "type": "PaymentSigningKeyShelley_ed25519",
+                "description": "Payment Signing Key",
+                "cborHex": "5820aa"
+            },
+            "payment_vkey": "5820bb",
+            "stake_skey": "5820cc",
+            "stake_vkey": "5820dd",
+        }))
+        .unwrap();
+
+        assert_eq!(request.payment_skey, "5820aa");
+        assert_eq!(request.payment_vkey, "5820bb");
+        assert_eq!(
+            request.key_formats["payment_skey"],
+            KeyFormat::CardanoKeyFile
+        );
+        assert_eq!(request.key_formats["payment_vkey"], KeyFormat::Hex);
+        assert!(request.validate().is_ok());
+    }
+
+    #[test]
+    fn key_file_of_wrong_type_is_rejected() {
+        let result: Result<Request, _> = serde_json::from_value(serde_json::json!({
+            "payment_skey": {
+                "type": "StakeSigningKeyShelley_ed25519",
+                "description": "Stake Signing Key",
+                "cborHex": "5820aa"
+            },
+        }));
+        assert!(result.is_err());
+    }
 }
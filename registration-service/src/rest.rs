@@ -1,24 +1,40 @@
 use crate::context::{Context, ContextLock};
 use crate::file_lister;
-use crate::request::Request;
+use crate::job::{FundsInfo, VoteRegistrationJobBuilder};
+use crate::request::{validate_address, Request};
 use futures::FutureExt;
 use futures::{channel::mpsc, StreamExt};
 use jortestkit::web::api_token::TokenError;
 use jortestkit::web::api_token::{APIToken, APITokenManager, API_TOKEN_HEADER};
+use serde::Deserialize;
 use std::convert::Infallible;
 use std::sync::Arc;
 use thiserror::Error;
 use uuid::Uuid;
 use warp::{http::StatusCode, reject::Reject, Filter, Rejection, Reply};
 
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+/// Query parameters of `/api/job/new`
+#[derive(Debug, Default, Deserialize)]
+pub struct NewJobQuery {
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
 impl Reject for file_lister::Error {}
 impl Reject for crate::context::Error {}
+impl Reject for crate::request::Error {}
 
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("cannot parse uuid")]
     CannotParseUuid(#[from] uuid::Error),
+    #[error("unsupported content type '{0}', expected 'application/json'")]
+    UnsupportedContentType(String),
+    #[error("cannot query funds: {0}")]
+    FundsQueryFailed(String),
 }
 
 impl Reject for Error {}
@@ -33,7 +49,18 @@ impl ServerStopper {
 }
 
 fn job_prameters_json_body() -> impl Filter<Extract = (Request,), Error = warp::Rejection> + Clone {
-    warp::body::content_length_limit(1024 * 16).and(warp::body::json())
+    warp::header::optional::<String>("content-type")
+        .and_then(|content_type: Option<String>| async move {
+            match content_type {
+                Some(content_type) if content_type.starts_with("application/json") => Ok(()),
+                other => Err(warp::reject::custom(Error::UnsupportedContentType(
+                    other.unwrap_or_default(),
+                ))),
+            }
+        })
+        .untuple_one()
+        .and(warp::body::content_length_limit(1024 * 16))
+        .and(warp::body::json())
 }
 
 pub async fn start_rest_server(context: ContextLock) {
@@ -60,7 +87,12 @@ pub async fn start_rest_server(context: ContextLock) {
             .and(with_context.clone())
             .and_then(files_handler);
 
-        root.and(get.or(list)).boxed()
+        let artifact = warp::path!(String / String)
+            .and(warp::get())
+            .and(with_context.clone())
+            .and_then(artifact_handler);
+
+        root.and(get.or(list).or(artifact)).boxed()
     };
 
     let health = warp::path!("health")
@@ -74,6 +106,8 @@ pub async fn start_rest_server(context: ContextLock) {
         let new = warp::path!("new")
             .and(warp::post())
             .and(job_prameters_json_body())
+            .and(warp::query::<NewJobQuery>())
+            .and(warp::header::optional::<String>(IDEMPOTENCY_KEY_HEADER))
             .and(with_context.clone())
             .and_then(job_new_handler)
             .boxed();
@@ -84,6 +118,18 @@ pub async fn start_rest_server(context: ContextLock) {
             .and_then(job_status_handler)
             .boxed();
 
+        let funds = warp::path!("funds" / String)
+            .and(warp::get())
+            .and(with_context.clone())
+            .and_then(funds_handler)
+            .boxed();
+
+        let queue = warp::path!("queue")
+            .and(warp::get())
+            .and(with_context.clone())
+            .and_then(job_queue_handler)
+            .boxed();
+
         let api_token_filter = if is_token_enabled {
             warp::header::header(API_TOKEN_HEADER)
                 .and(with_context.clone())
@@ -96,7 +142,7 @@ pub async fn start_rest_server(context: ContextLock) {
         };
 
         root.and(api_token_filter)
-            .and(files.or(status).or(new))
+            .and(files.or(status).or(queue).or(funds).or(new))
             .boxed()
     };
     let api = root.and(health.or(job)).recover(report_invalid).boxed();
@@ -114,14 +160,54 @@ pub async fn job_status_handler(id: String, context: ContextLock) -> Result<impl
 }
 
 pub async fn job_new_handler(
-    request: Request,
+    mut request: Request,
+    query: NewJobQuery,
+    idempotency_key: Option<String>,
     context: ContextLock,
 ) -> Result<impl Reply, Rejection> {
+    request.validate()?;
+    request.dry_run = query.dry_run;
     let mut context_lock = context.lock().unwrap();
-    let id = context_lock.new_run(request)?;
+    let id = context_lock.new_run_with_idempotency_key(request, idempotency_key)?;
     Ok(id).map(|r| warp::reply::json(&r))
 }
 
+/// Runs only utxo query for payment address, so client can check wallet is funded
+/// before requesting registration
+pub async fn funds_handler(address: String, context: ContextLock) -> Result<impl Reply, Rejection> {
+    validate_address(&address)?;
+    let configuration = context.lock().unwrap().config().clone();
+    let job = VoteRegistrationJobBuilder::from_configuration(&configuration).build();
+
+    let query_address = address.clone();
+    let funds = tokio::task::spawn_blocking(move || job.query_funds(&query_address))
+        .await
+        .map_err(|err| Error::FundsQueryFailed(err.to_string()))?
+        .map_err(|err| Error::FundsQueryFailed(err.to_string()))?;
+    Ok(warp::reply::json(&FundsInfo { address, funds }))
+}
+
+pub async fn job_queue_handler(context: ContextLock) -> Result<impl Reply, Rejection> {
+    let context_lock = context.lock().unwrap();
+    Ok(warp::reply::json(&context_lock.queue_status()))
+}
+
+pub async fn artifact_handler(
+    id: String,
+    name: String,
+    context: ContextLock,
+) -> Result<impl Reply, Rejection> {
+    let uuid = Uuid::parse_str(&id).map_err(Error::CannotParseUuid)?;
+    let context_lock = context.lock().unwrap();
+    let (content, content_type) =
+        file_lister::read_artifact(context_lock.working_directory(), uuid, &name)?;
+    Ok(warp::reply::with_header(
+        content,
+        "content-type",
+        content_type,
+    ))
+}
+
 pub async fn health_handler() -> Result<impl Reply, Rejection> {
     Ok(warp::reply())
 }
@@ -133,10 +219,32 @@ pub async fn files_handler(context: ContextLock) -> Result<impl Reply, Rejection
 
 async fn report_invalid(r: Rejection) -> Result<impl Reply, Infallible> {
     if let Some(e) = r.find::<file_lister::Error>() {
+        let code = match e {
+            file_lister::Error::ArtifactNotFound(_) => StatusCode::NOT_FOUND,
+            _ => StatusCode::BAD_REQUEST,
+        };
+        Ok(warp::reply::with_status(e.to_string(), code))
+    } else if let Some(e @ Error::UnsupportedContentType(_)) = r.find::<Error>() {
+        Ok(warp::reply::with_status(
+            e.to_string(),
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+        ))
+    } else if let Some(e @ Error::FundsQueryFailed(_)) = r.find::<Error>() {
+        Ok(warp::reply::with_status(
+            e.to_string(),
+            StatusCode::BAD_GATEWAY,
+        ))
+    } else if let Some(e) = r.find::<crate::request::Error>() {
         Ok(warp::reply::with_status(
             e.to_string(),
             StatusCode::BAD_REQUEST,
         ))
+    } else if let Some(e) = r.find::<crate::context::Error>() {
+        let code = match e {
+            crate::context::Error::QueueFull(_) => StatusCode::TOO_MANY_REQUESTS,
+            _ => StatusCode::BAD_REQUEST,
+        };
+        Ok(warp::reply::with_status(e.to_string(), code))
     } else {
         Ok(warp::reply::with_status(
             format!("internal error: {:?}", r),
@@ -163,3 +271,106 @@ pub async fn authorize_token(
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Configuration;
+    use std::sync::Mutex;
+
+    fn context() -> ContextLock {
+        let config: Configuration = serde_json::from_str(
+            r#"{
+                "port": 0,
+                "result-dir": ".",
+                "jcli": "jcli",
+                "cardano-cli": "cardano-cli",
+                "voter-registration": "voter-registration",
+                "vit-kedqr": "vit-kedqr",
+                "network": "mainnet"
+            }"#,
+        )
+        .unwrap();
+        Arc::new(Mutex::new(Context::new(config, ".")))
+    }
+
+    fn new_job_filter(
+        context: ContextLock,
+    ) -> impl Filter<Extract = (impl Reply,), Error = Infallible> + Clone {
+        let with_context = warp::any().map(move || context.clone());
+        warp::path!("new")
+            .and(warp::post())
+            .and(job_prameters_json_body())
+            .and(warp::query::<NewJobQuery>())
+            .and(warp::header::optional::<String>(IDEMPOTENCY_KEY_HEADER))
+            .and(with_context)
+            .and_then(job_new_handler)
+            .recover(report_invalid)
+    }
+
+    #[tokio::test]
+    async fn new_job_without_stake_key_is_rejected() {
+        let context = context();
+        let filter = new_job_filter(context.clone());
+
+        let response = warp::test::request()
+            .method("POST")
+            .path("/new")
+            .json(&serde_json::json!({
+                "payment_skey": "5820aa",
+                "payment_vkey": "5820bb",
+                "stake_vkey": "5820cc",
+            }))
+            .reply(&filter)
+            .await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = String::from_utf8_lossy(response.body());
+        assert!(body.contains("stake_skey: missing"), "{}", body);
+        assert_eq!(context.lock().unwrap().queue_status().queued, 0);
+    }
+
+    #[tokio::test]
+    async fn dry_run_query_is_stored_in_request() {
+        let context = context();
+        let filter = new_job_filter(context.clone());
+
+        let response = warp::test::request()
+            .method("POST")
+            .path("/new?dry_run=true")
+            .json(&serde_json::json!({
+                "payment_skey": "5820aa",
+                "payment_vkey": "5820bb",
+                "stake_skey": "5820cc",
+                "stake_vkey": "5820dd",
+            }))
+            .reply(&filter)
+            .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let id: Uuid = serde_json::from_slice(response.body()).unwrap();
+        match context.lock().unwrap().status_by_id(id).unwrap() {
+            crate::context::State::RequestToStart { request, .. } => assert!(request.dry_run),
+            other => panic!("unexpected state: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn funds_of_malformed_address_are_not_queried() {
+        let context = context();
+        let with_context = warp::any().map(move || context.clone());
+        let filter = warp::path!("funds" / String)
+            .and(warp::get())
+            .and(with_context)
+            .and_then(funds_handler)
+            .recover(report_invalid);
+
+        let response = warp::test::request()
+            .method("GET")
+            .path("/funds/addr1;rm")
+            .reply(&filter)
+            .await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
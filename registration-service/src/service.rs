@@ -1,4 +1,4 @@
-use crate::context::{ContextLock, State};
+use crate::context::ContextLock;
 use crate::request::Request;
 use crate::rest::start_rest_server;
 use tokio::runtime::{Handle, Runtime};
@@ -38,9 +38,6 @@ impl ManagerService {
     }
 
     pub fn request_to_start(&self) -> Option<(Uuid, Request)> {
-        match self.context.lock().unwrap().state() {
-            State::RequestToStart { job_id, request } => Some((*job_id, request.clone())),
-            _ => None,
-        }
+        self.context.lock().unwrap().next_request_to_start()
     }
 }
@@ -1,8 +1,12 @@
 use crate::config::NetworkType;
+use std::path::PathBuf;
 use std::process::Command;
 
+pub const CARDANO_NODE_SOCKET_PATH: &str = "CARDANO_NODE_SOCKET_PATH";
+
 pub trait CommandExt {
     fn arg_network(&mut self, network: NetworkType) -> &mut Self;
+    fn env_node_socket(&mut self, node_socket: &Option<PathBuf>) -> &mut Self;
 }
 
 impl CommandExt for Command {
@@ -12,4 +16,11 @@ impl CommandExt for Command {
             NetworkType::Testnet(magic) => self.arg("--testnet-magic").arg(magic.to_string()),
         }
     }
+
+    fn env_node_socket(&mut self, node_socket: &Option<PathBuf>) -> &mut Self {
+        match node_socket {
+            Some(node_socket) => self.env(CARDANO_NODE_SOCKET_PATH, node_socket),
+            None => self,
+        }
+    }
 }
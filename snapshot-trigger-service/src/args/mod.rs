@@ -1,6 +1,6 @@
 use crate::{
-    config::{read_config, Configuration},
-    context::State,
+    config::{read_config, Configuration, TAG_FILE},
+    context::{Progress, State},
     service::ManagerService,
     Context,
 };
@@ -45,9 +45,17 @@ impl TriggerServiceCommand {
                     job_result_dir.push(job_id.to_string());
                     std::fs::create_dir_all(job_result_dir.clone())?;
 
+                    if let Some(tag) = &params.tag {
+                        std::fs::write(job_result_dir.join(TAG_FILE), tag)?;
+                    }
+
                     let mut child = configuration.spawn_command(job_id, params).unwrap();
 
                     control_context.lock().unwrap().run_started().unwrap();
+                    control_context
+                        .lock()
+                        .unwrap()
+                        .update_progress(Progress::phase("generating snapshot"))?;
 
                     child.wait().unwrap();
                     control_context.lock().unwrap().run_finished().unwrap();
@@ -1,6 +1,8 @@
 use crate::client::rest::SnapshotRestClient;
 use crate::config::JobParameters;
 use crate::context::State;
+use std::collections::HashSet;
+use std::time::Duration;
 use structopt::StructOpt;
 use thiserror::Error;
 
@@ -61,6 +63,8 @@ pub enum JobCommand {
     New(NewJobCommand),
     /// get job status
     Status(StatusCommand),
+    /// wait until job is finished, printing its progress
+    Wait(WaitCommand),
 }
 
 impl JobCommand {
@@ -74,6 +78,10 @@ impl JobCommand {
                 println!("{:?}", status_command.exec(rest)?);
                 Ok(())
             }
+            Self::Wait(wait_command) => {
+                println!("{:?}", wait_command.exec(rest)?);
+                Ok(())
+            }
         }
     }
 }
@@ -91,6 +99,32 @@ impl StatusCommand {
     }
 }
 
+#[derive(StructOpt, Debug)]
+pub struct WaitCommand {
+    /// job id
+    #[structopt(short, long)]
+    job_id: String,
+
+    /// interval between status checks in seconds
+    #[structopt(short, long, default_value = "10")]
+    interval: u64,
+
+    /// timeout in seconds
+    #[structopt(long, default_value = "3600")]
+    timeout: u64,
+}
+
+impl WaitCommand {
+    pub fn exec(self, rest: SnapshotRestClient) -> Result<State, Error> {
+        rest.wait_for_completion(
+            self.job_id,
+            Duration::from_secs(self.interval),
+            Duration::from_secs(self.timeout),
+        )
+        .map_err(Into::into)
+    }
+}
+
 #[derive(StructOpt, Debug)]
 pub struct NewJobCommand {
     /// slot no
@@ -100,6 +134,10 @@ pub struct NewJobCommand {
     /// threshold
     #[structopt(short, long)]
     threshold: u64,
+
+    /// human readable label of job
+    #[structopt(long)]
+    tag: Option<String>,
 }
 
 impl NewJobCommand {
@@ -107,6 +145,7 @@ impl NewJobCommand {
         let params = JobParameters {
             slot_no: self.slot_no,
             threshold: self.threshold,
+            tag: self.tag,
         };
         rest.job_new(params).map_err(Into::into)
     }
@@ -114,17 +153,51 @@ impl NewJobCommand {
 
 #[derive(StructOpt, Debug)]
 pub enum FilesCommand {
-    List,
+    List(ListFilesCommand),
 }
 
 impl FilesCommand {
     pub fn exec(self, rest: SnapshotRestClient) -> Result<(), Error> {
         match self {
-            Self::List => {
-                println!("{}", serde_json::to_string_pretty(&rest.list_files()?)?);
-                Ok(())
-            }
+            Self::List(list_files_command) => list_files_command.exec(rest),
+        }
+    }
+}
+
+#[derive(StructOpt, Debug)]
+pub struct ListFilesCommand {
+    /// show only files of jobs which id starts with prefix
+    #[structopt(long)]
+    prefix: Option<String>,
+
+    /// sort files from most recently modified
+    #[structopt(long)]
+    latest_first: bool,
+}
+
+impl ListFilesCommand {
+    pub fn exec(self, rest: SnapshotRestClient) -> Result<(), Error> {
+        let folder_dump = rest.list_files()?;
+        if self.prefix.is_none() && !self.latest_first {
+            println!("{}", serde_json::to_string_pretty(&folder_dump)?);
+            return Ok(());
+        }
+
+        let mut entries = if self.latest_first {
+            folder_dump.sorted_by_recency()
+        } else {
+            folder_dump.content().iter().collect()
+        };
+
+        if let Some(prefix) = &self.prefix {
+            let filtered: HashSet<&String> = folder_dump
+                .filter_by_job_prefix(prefix)
+                .into_iter()
+                .collect();
+            entries.retain(|entry| filtered.contains(entry));
         }
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+        Ok(())
     }
 }
 
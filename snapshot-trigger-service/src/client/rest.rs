@@ -1,14 +1,19 @@
-use crate::config::JobParameters;
+use crate::config::{JobParameters, JobParametersError};
 use crate::context::State;
-use crate::file_lister::FolderDump;
+use crate::file_lister::{checksum, FolderDump};
 use reqwest::blocking::Response;
+use reqwest::header::{CONTENT_RANGE, RANGE};
+use reqwest::StatusCode;
+use std::fs::OpenOptions;
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 use thiserror::Error;
 
 pub struct SnapshotRestClient {
     token: Option<String>,
     address: String,
+    verify_checksum: bool,
 }
 
 impl SnapshotRestClient {
@@ -16,6 +21,7 @@ impl SnapshotRestClient {
         Self {
             token: Some(token),
             address,
+            verify_checksum: true,
         }
     }
 
@@ -23,9 +29,15 @@ impl SnapshotRestClient {
         Self {
             token: None,
             address,
+            verify_checksum: true,
         }
     }
 
+    /// Skips SHA-256 verification of downloaded files, which can take a while for big snapshots
+    pub fn disable_checksum_verification(&mut self) {
+        self.verify_checksum = false;
+    }
+
     pub fn token(&self) -> &Option<String> {
         &self.token
     }
@@ -84,20 +96,81 @@ impl SnapshotRestClient {
         self.download(format!("{}/snapshot.json", id.into()), output)
     }
 
+    /// Downloads file into output. Content is written to `{output}.part` first and moved
+    /// to output once complete, so existing output is always replaced. If partial file
+    /// is left by interrupted download, download is resumed from its end using range
+    /// request. When server does not support ranges, whole file is downloaded again
     pub fn download<S: Into<String>, P: AsRef<Path>>(
         &self,
         sub_location: S,
         output: P,
     ) -> Result<(), Error> {
-        let content = self.get(format!("api/job/files/get/{}", sub_location.into()))?;
-        let mut file = std::fs::File::create(&output)?;
-        file.write_all(content.as_bytes())?;
+        let sub_location = sub_location.into();
+        self.download_content(&sub_location, &output)?;
+        if self.verify_checksum {
+            self.verify(&sub_location, &output)?;
+        }
+        Ok(())
+    }
+
+    /// Compares SHA-256 of local file with checksum calculated by service
+    pub fn verify<P: AsRef<Path>>(&self, sub_location: &str, output: P) -> Result<(), Error> {
+        let expected: String =
+            serde_json::from_str(&self.get(format!("api/job/files/checksum/{}", sub_location))?)?;
+        let actual = checksum(&output)?;
+        if expected != actual {
+            return Err(Error::ChecksumMismatch {
+                file: sub_location.to_string(),
+                expected,
+                actual,
+            });
+        }
+        Ok(())
+    }
+
+    fn download_content<P: AsRef<Path>>(&self, sub_location: &str, output: P) -> Result<(), Error> {
+        let output = output.as_ref();
+        let partial = partial_path(output);
+        let path = self.path(format!("api/job/files/get/{}", sub_location));
+        let offset = std::fs::metadata(&partial).map(|md| md.len()).unwrap_or(0);
+
+        println!("Calling: {}", path);
+        let client = reqwest::blocking::Client::new();
+        let mut request = self.set_header(client.get(&path));
+        if offset > 0 {
+            request = request.header(RANGE, format!("bytes={}-", offset));
+        }
+        let mut response = request.send()?;
+
+        if response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+            // partial file contains whole content only if its size matches remote one,
+            // otherwise it is stale and download starts from scratch
+            if content_range_total(&response) == Some(offset) {
+                std::fs::rename(&partial, output)?;
+                return Ok(());
+            }
+            println!("Discarding invalid partial download: {:?}", partial);
+            std::fs::remove_file(&partial)?;
+            return self.download_content(sub_location, output);
+        }
+        response.error_for_status_ref()?;
+
+        let mut file = if response.status() == StatusCode::PARTIAL_CONTENT {
+            println!("Resuming download from byte: {}", offset);
+            OpenOptions::new().append(true).open(&partial)?
+        } else {
+            std::fs::File::create(&partial)?
+        };
+        response.copy_to(&mut file)?;
+        file.flush()?;
+        std::fs::rename(&partial, output)?;
         Ok(())
     }
 
     pub fn job_new(&self, params: JobParameters) -> Result<String, Error> {
+        params.validate()?;
         let client = reqwest::blocking::Client::new();
-        let request = self.set_header(client.post("api/job/new"));
+        let request = self.set_header(client.post(&self.path("api/job/new")));
         request.json(&params).send()?.text().map_err(Into::into)
     }
 
@@ -106,16 +179,59 @@ impl SnapshotRestClient {
         serde_yaml::from_str(&content).map_err(Into::into)
     }
 
+    /// Polls job status until job is finished, printing its progress meanwhile
+    pub fn wait_for_completion<S: Into<String>>(
+        &self,
+        id: S,
+        interval: Duration,
+        timeout: Duration,
+    ) -> Result<State, Error> {
+        let id = id.into();
+        let start = Instant::now();
+        loop {
+            let state = self.job_status(&id)?;
+            match &state {
+                State::Finished { .. } => return Ok(state),
+                State::Running { progress, .. } => match progress {
+                    Some(progress) => println!("job {}: {}", id, progress),
+                    None => println!("job {}: running", id),
+                },
+                _ => println!("job {}: waiting to start", id),
+            }
+
+            if start.elapsed() > timeout {
+                return Err(Error::WaitForCompletionTimeout(id, timeout));
+            }
+            std::thread::sleep(interval);
+        }
+    }
+
     pub fn is_up(&self) -> bool {
         if let Ok(path) = self.get("api/health") {
             if let Ok(response) = reqwest::blocking::get(&path) {
-                return response.status() == reqwest::StatusCode::OK;
+                return response.status() == StatusCode::OK;
             }
         }
         false
     }
 }
 
+/// Sidecar file keeping content of download in progress
+fn partial_path(output: &Path) -> PathBuf {
+    let mut file_name = output.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".part");
+    output.with_file_name(file_name)
+}
+
+/// Complete length from `Content-Range` header (e.g. `bytes */1024`)
+fn content_range_total(response: &Response) -> Option<u64> {
+    parse_content_range_total(response.headers().get(CONTENT_RANGE)?.to_str().ok()?)
+}
+
+fn parse_content_range_total(content_range: &str) -> Option<u64> {
+    content_range.rsplit('/').next()?.trim().parse().ok()
+}
+
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("internal rest error")]
@@ -126,4 +242,34 @@ pub enum Error {
     SerdeYamlError(#[from] serde_yaml::Error),
     #[error("io error")]
     IoError(#[from] std::io::Error),
+    #[error("invalid job parameters")]
+    InvalidJobParameters(#[from] JobParametersError),
+    #[error("checksum mismatch for {file}: expected {expected}, got {actual}")]
+    ChecksumMismatch {
+        file: String,
+        expected: String,
+        actual: String,
+    },
+    #[error("job {0} was not finished within {1:?}")]
+    WaitForCompletionTimeout(String, Duration),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn partial_download_is_kept_next_to_output() {
+        assert_eq!(
+            partial_path(Path::new("snapshots/snapshot.json")),
+            PathBuf::from("snapshots/snapshot.json.part")
+        );
+    }
+
+    #[test]
+    fn content_range_total_length() {
+        assert_eq!(parse_content_range_total("bytes */1024"), Some(1024));
+        assert_eq!(parse_content_range_total("bytes 0-99/1024"), Some(1024));
+        assert_eq!(parse_content_range_total("bytes */*"), None);
+    }
 }
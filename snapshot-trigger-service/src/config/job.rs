@@ -1,8 +1,122 @@
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
+/// total supply of ada expressed in lovelace. Threshold above it would filter out every voter
+pub const MAX_LOVELACE_SUPPLY: u64 = 45_000_000_000_000_000;
+
+/// file in job result directory which keeps job tag
+pub const TAG_FILE: &str = "tag";
+pub const MAX_TAG_LENGTH: usize = 128;
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
 pub struct JobParameters {
     #[serde(rename = "slot-no")]
     pub slot_no: Option<u64>,
     pub threshold: u64,
+    /// human readable label, which helps to find job without tracking its id
+    #[serde(default)]
+    pub tag: Option<String>,
+}
+
+impl JobParameters {
+    pub fn validate(&self) -> Result<(), JobParametersError> {
+        if self.slot_no == Some(0) {
+            return Err(JobParametersError::InvalidSlotNo);
+        }
+        if self.threshold == 0 {
+            return Err(JobParametersError::ZeroThreshold);
+        }
+        if self.threshold > MAX_LOVELACE_SUPPLY {
+            return Err(JobParametersError::ThresholdAboveSupply(self.threshold));
+        }
+        if let Some(tag) = &self.tag {
+            if tag.trim().is_empty() || tag.len() > MAX_TAG_LENGTH || tag.contains('\n') {
+                return Err(JobParametersError::InvalidTag(tag.clone()));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum JobParametersError {
+    #[error("slot no should be greater than 0")]
+    InvalidSlotNo,
+    #[error("threshold should be greater than 0")]
+    ZeroThreshold,
+    #[error("threshold {0} is above total lovelace supply ({max})", max = MAX_LOVELACE_SUPPLY)]
+    ThresholdAboveSupply(u64),
+    #[error("tag '{0}' should be non empty, single line and at most {max} characters long", max = MAX_TAG_LENGTH)]
+    InvalidTag(String),
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{JobParameters, JobParametersError, MAX_LOVELACE_SUPPLY};
+
+    #[test]
+    pub fn test_valid_parameters() {
+        let params = JobParameters {
+            slot_no: Some(25398498),
+            threshold: 1,
+            tag: None,
+        };
+        assert_eq!(params.validate(), Ok(()));
+
+        let params = JobParameters {
+            slot_no: None,
+            threshold: MAX_LOVELACE_SUPPLY,
+            tag: Some("nightly preprod snapshot".to_string()),
+        };
+        assert_eq!(params.validate(), Ok(()));
+    }
+
+    #[test]
+    pub fn test_zero_slot_no() {
+        let params = JobParameters {
+            slot_no: Some(0),
+            threshold: 1,
+            tag: None,
+        };
+        assert_eq!(params.validate(), Err(JobParametersError::InvalidSlotNo));
+    }
+
+    #[test]
+    pub fn test_zero_threshold() {
+        let params = JobParameters {
+            slot_no: None,
+            threshold: 0,
+            tag: None,
+        };
+        assert_eq!(params.validate(), Err(JobParametersError::ZeroThreshold));
+    }
+
+    #[test]
+    pub fn test_threshold_above_supply() {
+        let params = JobParameters {
+            slot_no: None,
+            threshold: MAX_LOVELACE_SUPPLY + 1,
+            tag: None,
+        };
+        assert_eq!(
+            params.validate(),
+            Err(JobParametersError::ThresholdAboveSupply(
+                MAX_LOVELACE_SUPPLY + 1
+            ))
+        );
+    }
+
+    #[test]
+    pub fn test_invalid_tag() {
+        let params = JobParameters {
+            slot_no: None,
+            threshold: 1,
+            tag: Some(" ".to_string()),
+        };
+        assert_eq!(
+            params.validate(),
+            Err(JobParametersError::InvalidTag(" ".to_string()))
+        );
+    }
 }
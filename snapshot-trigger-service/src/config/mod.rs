@@ -1,6 +1,6 @@
 mod job;
 
-pub use job::JobParameters;
+pub use job::{JobParameters, JobParametersError, MAX_LOVELACE_SUPPLY, MAX_TAG_LENGTH, TAG_FILE};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 use std::path::PathBuf;
@@ -54,12 +54,13 @@ impl Context {
     }
 
     pub fn run_started(&mut self) -> Result<(), Error> {
-        match self.state {
+        match &self.state {
             State::RequestToStart { job_id, parameters } => {
                 self.state = State::Running {
-                    job_id,
+                    job_id: *job_id,
                     start: Utc::now().naive_utc(),
-                    parameters,
+                    parameters: parameters.clone(),
+                    progress: None,
                 };
                 Ok(())
             }
@@ -68,17 +69,18 @@ impl Context {
     }
 
     pub fn run_finished(&mut self) -> Result<(), Error> {
-        match self.state {
+        match &self.state {
             State::Running {
                 job_id,
                 start,
                 parameters,
+                ..
             } => {
                 self.state = State::Finished {
-                    job_id,
-                    start,
+                    job_id: *job_id,
+                    start: *start,
                     end: Utc::now().naive_utc(),
-                    parameters,
+                    parameters: parameters.clone(),
                 };
                 Ok(())
             }
@@ -86,20 +88,30 @@ impl Context {
         }
     }
 
+    pub fn update_progress(&mut self, new_progress: Progress) -> Result<(), Error> {
+        match &mut self.state {
+            State::Running { progress, .. } => {
+                *progress = Some(new_progress);
+                Ok(())
+            }
+            _ => Err(Error::SnaphotNotStarted),
+        }
+    }
+
     pub fn status_by_id(&self, id: Uuid) -> Result<State, Error> {
         match self.state {
             State::Idle => Err(Error::NoJobRun),
-            State::RequestToStart { .. } => Ok(self.state),
+            State::RequestToStart { .. } => Ok(self.state.clone()),
             State::Running { job_id, .. } => {
                 if job_id == id {
-                    Ok(self.state)
+                    Ok(self.state.clone())
                 } else {
                     Err(Error::JobNotFound)
                 }
             }
             State::Finished { job_id, .. } => {
                 if job_id == id {
-                    Ok(self.state)
+                    Ok(self.state.clone())
                 } else {
                     Err(Error::JobNotFound)
                 }
@@ -132,7 +144,7 @@ impl Context {
     }
 }
 
-#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug)]
 pub enum State {
     Idle,
     RequestToStart {
@@ -143,6 +155,8 @@ pub enum State {
         job_id: Uuid,
         start: NaiveDateTime,
         parameters: JobParameters,
+        #[serde(default)]
+        progress: Option<Progress>,
     },
     Finished {
         job_id: Uuid,
@@ -152,6 +166,27 @@ pub enum State {
     },
 }
 
+/// Progress of running job. Snapshot is generated by external command which does
+/// not report processed records, so only phase of job is known
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug)]
+pub struct Progress {
+    pub phase: String,
+}
+
+impl Progress {
+    pub fn phase<S: Into<String>>(phase: S) -> Self {
+        Self {
+            phase: phase.into(),
+        }
+    }
+}
+
+impl fmt::Display for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.phase)
+    }
+}
+
 use thiserror::Error;
 
 #[derive(Debug, Error, Deserialize, Serialize)]
@@ -1,12 +1,21 @@
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
+use std::path::{Component, Path};
+use std::time::{SystemTime, UNIX_EPOCH};
 use thiserror::Error;
 use walkdir::WalkDir;
 
 #[derive(Serialize, Deserialize)]
 pub struct FolderDump {
     content: Vec<String>,
+    /// job id -> job tag
+    #[serde(default)]
+    tags: BTreeMap<String, String>,
+    /// entry -> last modification time (seconds since unix epoch)
+    #[serde(default)]
+    modified: BTreeMap<String, u64>,
     #[serde(skip_serializing)]
     root: PathBuf,
 }
@@ -15,15 +24,60 @@ impl FolderDump {
     pub fn new<P: AsRef<Path>>(root: P) -> Self {
         Self {
             content: Vec::new(),
+            tags: BTreeMap::new(),
+            modified: BTreeMap::new(),
             root: root.as_ref().to_path_buf(),
         }
     }
 
     pub fn push<S: Into<String>>(&mut self, data: S) {
-        let item = data.into();
+        let item = self.relative(data.into());
+        self.content.push(item);
+    }
+
+    pub fn push_with_modified<S: Into<String>>(&mut self, data: S, modified: SystemTime) {
+        let item = self.relative(data.into());
+        if let Ok(since_epoch) = modified.duration_since(UNIX_EPOCH) {
+            self.modified.insert(item.clone(), since_epoch.as_secs());
+        }
+        self.content.push(item);
+    }
+
+    fn relative(&self, item: String) -> String {
         let root_file_name = format!("{}", self.root.display());
+        item.replace(&root_file_name, "").replace("\\", "/")
+    }
+
+    /// Entries which belong to jobs with id starting with given prefix
+    pub fn filter_by_job_prefix(&self, prefix: &str) -> Vec<&String> {
         self.content
-            .push(item.replace(&root_file_name, "").replace("\\", "/"));
+            .iter()
+            .filter(|entry| entry.trim_start_matches('/').starts_with(prefix))
+            .collect()
+    }
+
+    /// Entries sorted from most recently modified. Entries without
+    /// modification time are put at the end
+    pub fn sorted_by_recency(&self) -> Vec<&String> {
+        let mut entries: Vec<&String> = self.content.iter().collect();
+        entries.sort_by(|left, right| self.modified.get(*right).cmp(&self.modified.get(*left)));
+        entries
+    }
+
+    pub fn modified(&self, entry: &str) -> Option<u64> {
+        self.modified.get(entry).copied()
+    }
+
+    pub fn content(&self) -> &[String] {
+        &self.content
+    }
+
+    pub fn tags(&self) -> &BTreeMap<String, String> {
+        &self.tags
+    }
+
+    pub fn add_tag<S: Into<String>, T: Into<String>>(&mut self, job_id: S, tag: T) {
+        self.tags.insert(job_id.into(), tag.into());
     }
 }
 
@@ -32,6 +86,36 @@ impl FolderDump {
 pub enum Error {
     #[error("root folder does not exist yet. try to start backend")]
     RootFolderDoesNotExist(#[from] walkdir::Error),
+    #[error("path '{0}' points outside of jobs directory")]
+    PathTraversal(String),
+}
+
+/// Joins client provided sub path onto root directory. Only plain relative paths are
+/// accepted and (for already existing files) resolved path must stay within root,
+/// so neither `..`, absolute paths nor symlinks can be used to escape it
+pub fn resolve_within<P: AsRef<Path>>(root: P, sub_path: &str) -> Result<PathBuf, Error> {
+    let root = root.as_ref();
+    let relative = Path::new(sub_path);
+    let is_plain = relative
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)));
+    if !is_plain || sub_path.contains('\\') {
+        return Err(Error::PathTraversal(sub_path.to_string()));
+    }
+
+    let path = root.join(relative);
+    if path.exists() {
+        let canonical_root = root
+            .canonicalize()
+            .map_err(|_| Error::PathTraversal(sub_path.to_string()))?;
+        let canonical_path = path
+            .canonicalize()
+            .map_err(|_| Error::PathTraversal(sub_path.to_string()))?;
+        if !canonical_path.starts_with(canonical_root) {
+            return Err(Error::PathTraversal(sub_path.to_string()));
+        }
+    }
+    Ok(path)
 }
 
 pub fn dump_json<P: AsRef<Path>>(root: P) -> Result<FolderDump, Error> {
@@ -42,8 +126,71 @@ pub fn dump_json<P: AsRef<Path>>(root: P) -> Result<FolderDump, Error> {
         let entry = entry?;
         let md = std::fs::metadata(entry.path()).unwrap();
         if !md.is_dir() {
-            data.push(format!("{}", entry.path().display()));
+            let path = format!("{}", entry.path().display());
+            match md.modified() {
+                Ok(modified) => data.push_with_modified(path, modified),
+                Err(_) => data.push(path),
+            }
         }
     }
     Ok(data)
 }
+
+/// Hex encoded SHA-256 of file content
+pub fn checksum<P: AsRef<Path>>(path: P) -> Result<String, std::io::Error> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)?;
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Reads tags of all jobs, which were started with one. Tag is kept in `tag_file` inside
+/// job result directory named after job id
+pub fn read_tags<P: AsRef<Path>>(root: P, tag_file: &str) -> BTreeMap<String, String> {
+    let mut tags = BTreeMap::new();
+    let entries = match std::fs::read_dir(root) {
+        Ok(entries) => entries,
+        Err(_) => return tags,
+    };
+
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        if let Ok(tag) = std::fs::read_to_string(entry.path().join(tag_file)) {
+            tags.insert(entry.file_name().to_string_lossy().to_string(), tag);
+        }
+    }
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_within_accepts_plain_relative_path() {
+        let root = Path::new("jobs");
+        assert_eq!(
+            resolve_within(root, "job_id/snapshot.json").unwrap(),
+            root.join("job_id").join("snapshot.json")
+        );
+    }
+
+    #[test]
+    fn resolve_within_rejects_parent_dir() {
+        assert!(matches!(
+            resolve_within("jobs", "../config.yaml"),
+            Err(Error::PathTraversal(_))
+        ));
+        assert!(matches!(
+            resolve_within("jobs", "job_id/../../config.yaml"),
+            Err(Error::PathTraversal(_))
+        ));
+    }
+
+    #[test]
+    fn resolve_within_rejects_absolute_path() {
+        assert!(matches!(
+            resolve_within("jobs", "/etc/passwd"),
+            Err(Error::PathTraversal(_))
+        ));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::config::JobParameters;
+use crate::config::{JobParameters, TAG_FILE};
 use crate::context::{Context, ContextLock};
 use crate::file_lister;
 use futures::FutureExt;
@@ -21,6 +21,8 @@ pub enum Error {
     CannotParseUuid(#[from] uuid::Error),
     #[error("cannot parse token")]
     CannotParseToken,
+    #[error("cannot calculate checksum")]
+    CannotCalculateChecksum,
 }
 
 impl Reject for Error {}
@@ -57,13 +59,23 @@ pub async fn start_rest_server(context: ContextLock) {
     let files = {
         let root = warp::path!("files" / ..).boxed();
 
-        let get = warp::path("get").and(warp::fs::dir(working_dir));
+        let get = warp::path("get")
+            .and(warp::path::peek())
+            .and(with_context.clone())
+            .and_then(path_guard)
+            .untuple_one()
+            .and(warp::fs::dir(working_dir.clone()));
+        let checksum = warp::path("checksum")
+            .and(warp::path::tail())
+            .and(warp::get())
+            .and(with_context.clone())
+            .and_then(checksum_handler);
         let list = warp::path!("list")
             .and(warp::get())
             .and(with_context.clone())
             .and_then(files_handler);
 
-        root.and(get.or(list)).boxed()
+        root.and(get.or(list).or(checksum)).boxed()
     };
 
     let health = warp::path!("health")
@@ -132,7 +144,29 @@ pub async fn health_handler() -> Result<impl Reply, Rejection> {
 pub async fn files_handler(context: ContextLock) -> Result<impl Reply, Rejection> {
     let context_lock = context.lock().unwrap();
     println!("Reading files from: {:?}", context_lock.working_directory());
-    Ok(file_lister::dump_json(context_lock.working_directory())?).map(|r| warp::reply::json(&r))
+    let mut folder_dump = file_lister::dump_json(context_lock.working_directory())?;
+    for (job_id, tag) in file_lister::read_tags(context_lock.working_directory(), TAG_FILE) {
+        folder_dump.add_tag(job_id, tag);
+    }
+    Ok(folder_dump).map(|r| warp::reply::json(&r))
+}
+
+/// Checksum of any file served by `files/get`, addressed by the same sub location
+pub async fn checksum_handler(
+    sub_location: warp::path::Tail,
+    context: ContextLock,
+) -> Result<impl Reply, Rejection> {
+    let path = file_lister::resolve_within(
+        context.lock().unwrap().working_directory(),
+        sub_location.as_str(),
+    )?;
+    let checksum = file_lister::checksum(&path).map_err(|_| Error::CannotCalculateChecksum)?;
+    Ok(checksum).map(|r| warp::reply::json(&r))
+}
+
+async fn path_guard(path: warp::path::Peek, context: ContextLock) -> Result<(), Rejection> {
+    file_lister::resolve_within(context.lock().unwrap().working_directory(), path.as_str())?;
+    Ok(())
 }
 
 async fn report_invalid(r: Rejection) -> Result<impl Reply, Infallible> {
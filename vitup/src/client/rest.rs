@@ -1,6 +1,6 @@
 use crate::config::VitStartParameters;
 use crate::manager::file_lister::FolderDump;
-use crate::manager::State;
+use crate::manager::{Lifecycle, State};
 use reqwest::blocking::Response;
 use thiserror::Error;
 
@@ -171,6 +171,11 @@ impl VitupAdminRestClient {
         let text = self.inner.get("status")?;
         serde_json::from_str(&text).map_err(Into::into)
     }
+
+    pub fn lifecycle(&self) -> Result<Lifecycle, Error> {
+        let text = self.inner.get("control/status")?;
+        serde_json::from_str(&text).map_err(Into::into)
+    }
 }
 
 #[derive(Error, Debug)]
@@ -0,0 +1,76 @@
+use crate::error::ErrorKind;
+use crate::Result;
+use chain_addr::Discrimination;
+use chain_impl_mockchain::chaintypes::ConsensusVersion;
+use chain_impl_mockchain::fee::LinearFee;
+use jormungandr_lib::interfaces::BlockchainConfiguration;
+use serde_json::Value;
+
+/// Applies user defined overrides on top of generated blockchain configuration.
+/// Overrides use the same format as `blockchain_configuration` section of genesis file
+/// and take precedence over any parameter set by builder (slot duration, fees, discrimination etc.).
+/// Nested objects are merged field by field, any other value replaces generated one
+pub fn override_blockchain_configuration(
+    config: &BlockchainConfiguration,
+    overrides: &Value,
+) -> Result<BlockchainConfiguration> {
+    let invalid = |err: serde_json::Error| ErrorKind::InvalidBlockchainOverride(err.to_string());
+    let mut value = serde_json::to_value(config).map_err(invalid)?;
+    merge(&mut value, overrides);
+    Ok(serde_json::from_value(value).map_err(invalid)?)
+}
+
+/// Discrimination and fees which end up in genesis once overrides are applied.
+/// Wallets and fee validation have to use these, otherwise they disagree with block0
+pub fn override_wallet_parameters(
+    discrimination: Discrimination,
+    fees: LinearFee,
+    overrides: &Value,
+) -> Result<(Discrimination, LinearFee)> {
+    let config = BlockchainConfiguration::new(discrimination, ConsensusVersion::Bft, fees);
+    let config = override_blockchain_configuration(&config, overrides)?;
+    Ok((config.discrimination, config.linear_fees))
+}
+
+fn merge(base: &mut Value, overrides: &Value) {
+    match (base, overrides) {
+        (Value::Object(base), Value::Object(overrides)) => {
+            for (key, value) in overrides {
+                merge(base.entry(key.clone()).or_insert(Value::Null), value);
+            }
+        }
+        (base, overrides) => *base = overrides.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::merge;
+    use serde_json::json;
+
+    #[test]
+    fn overrides_replace_only_defined_fields() {
+        let mut base = json!({
+            "slot_duration": 20,
+            "linear_fees": { "constant": 0, "coefficient": 0 },
+            "consensus_leader_ids": ["a", "b"]
+        });
+        merge(
+            &mut base,
+            &json!({
+                "linear_fees": { "constant": 10 },
+                "consensus_leader_ids": ["c"],
+                "tx_max_expiry_epochs": 2
+            }),
+        );
+        assert_eq!(
+            base,
+            json!({
+                "slot_duration": 20,
+                "linear_fees": { "constant": 10, "coefficient": 0 },
+                "consensus_leader_ids": ["c"],
+                "tx_max_expiry_epochs": 2
+            })
+        );
+    }
+}
@@ -1,7 +1,12 @@
 use super::initials::Initials;
+use super::VoteTiming;
+use crate::error::ErrorKind;
+use crate::Result;
+use chain_addr::Discrimination;
 use chrono::NaiveDateTime;
 use iapyx::Protocol;
 use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
 use std::time::Duration;
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -9,6 +14,9 @@ pub struct VitStartParameters {
     pub initials: Option<Initials>,
     #[serde(default = "Protocol::http")]
     pub protocol: Protocol,
+    /// address discrimination used for block0, wallet templates and initials addresses
+    #[serde(with = "DiscriminationDef", default = "default_discrimination")]
+    pub discrimination: Discrimination,
     pub vote_start: u64,
     pub vote_tally: u64,
     pub tally_end: u64,
@@ -17,6 +25,13 @@ pub struct VitStartParameters {
     pub tally_end_timestamp: Option<NaiveDateTime>,
     pub next_vote_start_time: Option<NaiveDateTime>,
     pub refresh_time: Option<NaiveDateTime>,
+    /// genesis date of block0. Current time is used if not defined
+    #[serde(default)]
+    pub block0_date: Option<NaiveDateTime>,
+    /// fields of block0 blockchain configuration which override generated ones.
+    /// See `override_blockchain_configuration` for precedence rules
+    #[serde(default)]
+    pub blockchain_override: Option<serde_json::Value>,
     pub proposals: u32,
     pub challenges: usize,
     pub slot_duration: u8,
@@ -25,9 +40,36 @@ pub struct VitStartParameters {
     pub fund_name: String,
     pub fund_id: i32,
     pub private: bool,
+    /// number of committee members (n). Each member gets own committee wallet and private vote keys
+    #[serde(default = "default_committee_members")]
+    pub committee_members: usize,
+    /// number of members (m) required to decrypt private tally. Defaults to all members.
+    /// When defined, member keys are generated by vitup (see `SeededCommittee`)
+    #[serde(default)]
+    pub committee_threshold: Option<usize>,
+    /// hex encoded 32 bytes seed of committee member keys of private vote plans. Keys are
+    /// generated randomly if not defined. Seeded keys are for tests only: anyone who knows
+    /// the seed can decrypt all votes
+    #[serde(default)]
+    pub committee_seed: Option<String>,
     pub version: String,
 }
 
+fn default_committee_members() -> usize {
+    1
+}
+
+fn default_discrimination() -> Discrimination {
+    Discrimination::Production
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(remote = "Discrimination", rename_all = "lowercase")]
+enum DiscriminationDef {
+    Production,
+    Test,
+}
+
 impl VitStartParameters {
     pub fn calculate_vote_duration(&self) -> Duration {
         let duration_as_secs = (self.vote_tally - self.vote_start)
@@ -36,12 +78,30 @@ impl VitStartParameters {
 
         Duration::from_secs(duration_as_secs)
     }
+
+    pub fn vote_timing(&self) -> Result<VoteTiming> {
+        let epoch = |name: &str, value: u64| {
+            u32::try_from(value).map_err(|_| {
+                ErrorKind::InvalidVoteTiming(format!("{} epoch {} exceeds u32 range", name, value))
+            })
+        };
+        Ok(VoteTiming::new(
+            epoch("vote start", self.vote_start)?,
+            epoch("vote tally", self.vote_tally)?,
+            epoch("tally end", self.tally_end)?,
+        ))
+    }
+
+    pub fn committee_threshold(&self) -> usize {
+        self.committee_threshold.unwrap_or(self.committee_members)
+    }
 }
 
 impl Default for VitStartParameters {
     fn default() -> Self {
         Self {
             protocol: Protocol::Http,
+            discrimination: default_discrimination(),
             initials: Default::default(),
             vote_start: 1,
             vote_tally: 2,
@@ -56,8 +116,13 @@ impl Default for VitStartParameters {
             tally_end_timestamp: None,
             next_vote_start_time: None,
             refresh_time: None,
+            block0_date: None,
+            blockchain_override: None,
             fund_name: "fund_3".to_owned(),
             private: false,
+            committee_members: default_committee_members(),
+            committee_threshold: None,
+            committee_seed: None,
             fund_id: 1,
             version: "2.0".to_string(),
         }
@@ -0,0 +1,37 @@
+use crate::error::ErrorKind;
+use crate::Result;
+use chain_impl_mockchain::fee::LinearFee;
+
+/// Cost of single vote cast transaction sent from account wallet.
+///
+/// Vote cast has one input and no outputs, so it costs `constant + coefficient`
+/// plus certificate part: `per_vote_certificate_fees.certificate_vote_cast` if defined,
+/// otherwise generic `certificate` fee. `per_certificate_fees` (pool registration and
+/// delegation) and `certificate_vote_plan` do not affect voting wallets.
+///
+/// Returns `None` on overflow
+pub fn vote_cast_fee(fees: &LinearFee) -> Option<u64> {
+    let certificate = fees
+        .per_vote_certificate_fees
+        .certificate_vote_cast
+        .map(|fee| fee.get())
+        .unwrap_or(fees.certificate);
+    fees.constant
+        .checked_add(fees.coefficient)?
+        .checked_add(certificate)
+}
+
+/// Checks that voting wallets can afford to vote. Wallets generated above threshold
+/// can hold as little as `voting_power + 1`, so vote cast cannot cost more than threshold
+pub fn validate_fees(fees: &LinearFee, voting_power: u64) -> Result<()> {
+    let vote_cast_fee = vote_cast_fee(fees)
+        .ok_or_else(|| ErrorKind::InvalidFees("vote cast fee calculation overflows".to_string()))?;
+
+    if vote_cast_fee > voting_power {
+        bail!(ErrorKind::InvalidFees(format!(
+            "vote cast costs {} which is more than voting power threshold {}",
+            vote_cast_fee, voting_power
+        )));
+    }
+    Ok(())
+}
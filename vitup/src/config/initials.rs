@@ -1,9 +1,15 @@
-use chain_addr::Discrimination;
+use crate::error::ErrorKind;
+use crate::Result;
+use chain_addr::{Address, AddressReadable, Discrimination, Kind};
+use chain_crypto::bech32::Bech32;
+use chain_crypto::{Ed25519Extended, SecretKey};
 use chain_impl_mockchain::value::Value;
 use jormungandr_testing_utils::testing::network_builder::{ExternalWalletTemplate, WalletTemplate};
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::path::Path;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Initials(pub Vec<Initial>);
 
@@ -22,6 +28,14 @@ pub enum Initial {
         zero_funds: usize,
         pin: String,
     },
+    /// Wallet with known secret key (bech32 encoded `ed25519e_sk`), so test can
+    /// recover it directly. Must be listed before `Wallet`, which has subset of its fields
+    KnownKey {
+        name: String,
+        secret_key: String,
+        funds: usize,
+        pin: String,
+    },
     Wallet {
         name: String,
         funds: usize,
@@ -35,6 +49,30 @@ pub enum Initial {
 
 pub const GRACE_VALUE: u64 = 1;
 
+/// What to do when merged initials contain named wallet (or external address)
+/// which is already defined
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeConflictPolicy {
+    KeepExisting,
+    Overwrite,
+    Fail,
+}
+
+/// Summary of `Initials::merge`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MergeReport {
+    /// named wallets or external addresses which were not defined before
+    pub added: Vec<String>,
+    /// named wallets or external addresses which replaced existing definition
+    pub overwritten: Vec<String>,
+    /// named wallets or external addresses which were ignored as already defined
+    pub skipped: Vec<String>,
+    /// number of count-based entries summed with existing entry of the same kind and pin
+    pub summed_buckets: usize,
+    /// number of count-based entries appended as new
+    pub added_buckets: usize,
+}
+
 impl Default for Initials {
     fn default() -> Self {
         let initials: Vec<Initial> = std::iter::from_fn(|| {
@@ -49,6 +87,155 @@ impl Default for Initials {
     }
 }
 
+/// Account address of wallet with given bech32 encoded secret key
+pub fn known_key_address(secret_key: &str, discrimination: Discrimination) -> Result<String> {
+    let secret_key: SecretKey<Ed25519Extended> = Bech32::try_from_bech32_str(secret_key)
+        .map_err(|err| ErrorKind::InvalidInitials(format!("invalid secret key: {}", err)))?;
+    let address = Address(discrimination, Kind::Account(secret_key.to_public()));
+    Ok(AddressReadable::from_address(address_prefix(discrimination), &address).to_string())
+}
+
+/// Single row of snapshot file. Snapshot has the same layout as `initial` section
+/// of genesis file, limited to `fund` entries:
+///
+/// ```json
+/// { "initial": [ { "fund": [ { "address": "ca1q5...", "value": 10006 } ] } ] }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotEntry {
+    pub address: String,
+    pub value: u64,
+}
+
+/// Summary of `Initials::from_snapshot_entries`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SnapshotReport {
+    /// sum of funds of all imported wallets
+    pub total_funds: u64,
+    /// addresses of entries which stake rounded down to zero funds
+    pub zero_funds: Vec<String>,
+}
+
+/// Sum of stake of all snapshot rows, which must fit in u64 to be put in block0
+pub fn snapshot_total_value(entries: &[SnapshotEntry]) -> Result<u64> {
+    entries.iter().try_fold(0u64, |total, entry| {
+        total.checked_add(entry.value).ok_or_else(|| {
+            ErrorKind::InvalidSnapshot("total stake exceeds u64 range".to_string()).into()
+        })
+    })
+}
+
+/// Reads all rows of snapshot file. Every malformed row is reported (with its
+/// position, e.g. `initial[0].fund[3]`), not only the first one
+pub fn read_snapshot<P: AsRef<Path>>(snapshot: P) -> Result<Vec<SnapshotEntry>> {
+    parse_snapshot(&std::fs::read_to_string(snapshot)?)
+}
+
+pub fn parse_snapshot(content: &str) -> Result<Vec<SnapshotEntry>> {
+    let value: serde_json::Value = serde_json::from_str(content)
+        .map_err(|err| ErrorKind::InvalidSnapshot(format!("not a valid json: {}", err)))?;
+    let initials = value["initial"]
+        .as_array()
+        .ok_or_else(|| ErrorKind::InvalidSnapshot("missing 'initial' array".to_string()))?;
+
+    let mut entries = Vec::new();
+    let mut errors = Vec::new();
+    for (initial_index, initial) in initials.iter().enumerate() {
+        let funds = match initial["fund"].as_array() {
+            Some(funds) => funds,
+            None => {
+                errors.push(format!("initial[{}]: expected 'fund' entry", initial_index));
+                continue;
+            }
+        };
+        for (fund_index, fund) in funds.iter().enumerate() {
+            match parse_snapshot_entry(fund) {
+                Ok(entry) => entries.push(entry),
+                Err(reason) => errors.push(format!(
+                    "initial[{}].fund[{}]: {}",
+                    initial_index, fund_index, reason
+                )),
+            }
+        }
+    }
+
+    if !errors.is_empty() {
+        bail!(ErrorKind::InvalidSnapshot(errors.join(", ")));
+    }
+    Ok(entries)
+}
+
+fn parse_snapshot_entry(fund: &serde_json::Value) -> std::result::Result<SnapshotEntry, String> {
+    let address = fund["address"]
+        .as_str()
+        .ok_or_else(|| "missing 'address'".to_string())?;
+    AddressReadable::from_string_anyprefix(address)
+        .map_err(|err| format!("invalid address '{}': {}", address, err))?;
+    let value = fund["value"]
+        .as_u64()
+        .ok_or_else(|| "missing or invalid 'value', expected unsigned integer".to_string())?;
+    Ok(SnapshotEntry {
+        address: address.to_string(),
+        value,
+    })
+}
+
+fn address_prefix(discrimination: Discrimination) -> &'static str {
+    match discrimination {
+        Discrimination::Production => "ca",
+        Discrimination::Test => "ta",
+    }
+}
+
+impl Initial {
+    /// Identity of single wallet entry: name of named wallet or external address
+    fn key(&self) -> Option<String> {
+        match self {
+            Self::Wallet { name, .. } | Self::KnownKey { name, .. } => Some(name.clone()),
+            Self::External { address, .. } => Some(address.clone()),
+            _ => None,
+        }
+    }
+
+    fn bucket_count(&self) -> usize {
+        match self {
+            Self::AboveThreshold {
+                above_threshold, ..
+            } => *above_threshold,
+            Self::BelowThreshold {
+                below_threshold, ..
+            } => *below_threshold,
+            Self::ZeroFunds { zero_funds, .. } => *zero_funds,
+            _ => 0,
+        }
+    }
+
+    fn add_count(&mut self, count: usize) {
+        match self {
+            Self::AboveThreshold {
+                above_threshold, ..
+            } => *above_threshold += count,
+            Self::BelowThreshold {
+                below_threshold, ..
+            } => *below_threshold += count,
+            Self::ZeroFunds { zero_funds, .. } => *zero_funds += count,
+            _ => {}
+        }
+    }
+
+    /// Checks if both entries are count-based entries of the same kind and pin
+    fn is_bucket_of(&self, other: &Initial) -> bool {
+        match (self, other) {
+            (Self::AboveThreshold { pin, .. }, Self::AboveThreshold { pin: other_pin, .. })
+            | (Self::BelowThreshold { pin, .. }, Self::BelowThreshold { pin: other_pin, .. })
+            | (Self::ZeroFunds { pin, .. }, Self::ZeroFunds { pin: other_pin, .. }) => {
+                pin == other_pin
+            }
+            _ => false,
+        }
+    }
+}
+
 impl Initials {
     pub fn zero_funds_count(&self) -> usize {
         for initial in self.0.iter() {
@@ -79,6 +266,18 @@ impl Initials {
         sum
     }
 
+    /// Total number of wallets which will be created in block0, including external ones
+    pub fn wallet_count(&self) -> usize {
+        self.count()
+            + self
+                .0
+                .iter()
+                .filter(|initial| {
+                    matches!(initial, Initial::External { .. } | Initial::KnownKey { .. })
+                })
+                .count()
+    }
+
     pub fn zero_funds_pin(&self) -> Option<String> {
         for initial in self.0.iter() {
             if let Initial::ZeroFunds { zero_funds: _, pin } = initial {
@@ -88,6 +287,162 @@ impl Initials {
         None
     }
 
+    /// Splits `total` wallets into above threshold, below threshold and zero funds buckets
+    /// according to given percentages, which must sum to 100. Counts are rounded using
+    /// largest remainder, so buckets always sum exactly to `total`
+    pub fn from_distribution(
+        total: usize,
+        above_threshold_percent: u32,
+        below_threshold_percent: u32,
+        zero_funds_percent: u32,
+        pin: &str,
+    ) -> Result<Initials> {
+        let percents = [
+            above_threshold_percent,
+            below_threshold_percent,
+            zero_funds_percent,
+        ];
+        let sum: u32 = percents.iter().sum();
+        if sum != 100 {
+            bail!(ErrorKind::InvalidInitials(format!(
+                "distribution percentages should sum to 100, got {}",
+                sum
+            )));
+        }
+
+        let mut counts: Vec<usize> = percents
+            .iter()
+            .map(|percent| total * *percent as usize / 100)
+            .collect();
+        let mut remainders: Vec<(usize, usize)> = percents
+            .iter()
+            .enumerate()
+            .map(|(index, percent)| (total * *percent as usize % 100, index))
+            .collect();
+        remainders.sort_by(|a, b| b.cmp(a));
+
+        let missing = total - counts.iter().sum::<usize>();
+        for (_, index) in remainders.into_iter().take(missing) {
+            counts[index] += 1;
+        }
+
+        Ok(Self(vec![
+            Initial::AboveThreshold {
+                above_threshold: counts[0],
+                pin: pin.to_string(),
+            },
+            Initial::BelowThreshold {
+                below_threshold: counts[1],
+                pin: pin.to_string(),
+            },
+            Initial::ZeroFunds {
+                zero_funds: counts[2],
+                pin: pin.to_string(),
+            },
+        ]))
+    }
+
+    /// Merges other initials into this one. Named wallets are identified by name and external
+    /// ones by address; duplicates are resolved according to `policy`. Count-based entries
+    /// (above/below threshold, zero funds) with the same pin are summed. When merge fails
+    /// initials are left untouched
+    pub fn merge(&mut self, other: Initials, policy: MergeConflictPolicy) -> Result<MergeReport> {
+        let mut report = MergeReport::default();
+        let mut merged = self.0.clone();
+
+        for initial in other.0 {
+            if let Some(existing) = merged
+                .iter_mut()
+                .find(|existing| existing.is_bucket_of(&initial))
+            {
+                existing.add_count(initial.bucket_count());
+                report.summed_buckets += 1;
+                continue;
+            }
+
+            let key = match initial.key() {
+                Some(key) => key,
+                None => {
+                    merged.push(initial);
+                    report.added_buckets += 1;
+                    continue;
+                }
+            };
+
+            match merged
+                .iter()
+                .position(|existing| existing.key().as_ref() == Some(&key))
+            {
+                None => {
+                    merged.push(initial);
+                    report.added.push(key);
+                }
+                Some(index) => match policy {
+                    MergeConflictPolicy::KeepExisting => report.skipped.push(key),
+                    MergeConflictPolicy::Overwrite => {
+                        merged[index] = initial;
+                        report.overwritten.push(key);
+                    }
+                    MergeConflictPolicy::Fail => {
+                        bail!(ErrorKind::InvalidInitials(format!(
+                            "'{}' is defined in both initials",
+                            key
+                        )))
+                    }
+                },
+            }
+        }
+        self.0 = merged;
+        Ok(report)
+    }
+
+    /// External wallets funded as in given snapshot file (see `SnapshotEntry` for format)
+    pub fn from_snapshot<P: AsRef<Path>>(snapshot: P) -> Result<Initials> {
+        Self::from_snapshot_with_stake_divisor(snapshot, 1).map(|(initials, _)| initials)
+    }
+
+    /// Same as `from_snapshot`, but funds of each wallet are stake divided by `stake_divisor`
+    /// (e.g. 1_000_000 to turn lovelace into ada). Entries which end up with zero funds
+    /// cannot vote, they are listed in returned report
+    pub fn from_snapshot_with_stake_divisor<P: AsRef<Path>>(
+        snapshot: P,
+        stake_divisor: u64,
+    ) -> Result<(Initials, SnapshotReport)> {
+        Self::from_snapshot_entries(read_snapshot(snapshot)?, stake_divisor)
+    }
+
+    /// Wallet funds are proportional to stake of snapshot entries, so generated backend
+    /// reflects real voting power distribution
+    pub fn from_snapshot_entries(
+        entries: Vec<SnapshotEntry>,
+        stake_divisor: u64,
+    ) -> Result<(Initials, SnapshotReport)> {
+        if stake_divisor == 0 {
+            bail!(ErrorKind::InvalidSnapshot(
+                "stake divisor cannot be zero".to_string()
+            ));
+        }
+
+        let mut report = SnapshotReport::default();
+        let mut initials = Vec::new();
+        for entry in entries {
+            let funds = entry.value / stake_divisor;
+            report.total_funds = report.total_funds.checked_add(funds).ok_or_else(|| {
+                ErrorKind::InvalidSnapshot("total funds exceed u64 range".to_string())
+            })?;
+            if funds == 0 {
+                report.zero_funds.push(entry.address.clone());
+            }
+            initials.push(Initial::External {
+                address: entry.address,
+                funds: usize::try_from(funds).map_err(|_| {
+                    ErrorKind::InvalidSnapshot(format!("funds {} exceed usize range", funds))
+                })?,
+            });
+        }
+        Ok((Self(initials), report))
+    }
+
     pub fn new_above_threshold(count: usize, pin: &str) -> Initials {
         Self(vec![Initial::AboveThreshold {
             above_threshold: count,
@@ -95,18 +450,60 @@ impl Initials {
         }])
     }
 
-    pub fn external_templates(&self) -> Vec<ExternalWalletTemplate> {
+    /// Checks that external addresses use the same discrimination (and matching
+    /// `ca`/`ta` prefix) as the blockchain they are going to be funded on
+    pub fn validate_addresses(&self, discrimination: Discrimination) -> Result<()> {
+        let expected_prefix = address_prefix(discrimination);
+        for initial in self.0.iter() {
+            if let Initial::External { address, .. } = initial {
+                let readable = AddressReadable::from_string_anyprefix(address).map_err(|err| {
+                    ErrorKind::InvalidInitials(format!("invalid address '{}': {}", address, err))
+                })?;
+                if readable.get_prefix() != expected_prefix
+                    || readable.to_address().discrimination() != discrimination
+                {
+                    bail!(ErrorKind::InvalidInitials(format!(
+                        "address '{}' does not match {:?} discrimination (expected '{}' prefix)",
+                        address, discrimination, expected_prefix
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Templates of wallets funded on given address: external ones and the ones
+    /// with known secret key
+    pub fn external_templates(
+        &self,
+        discrimination: Discrimination,
+    ) -> Result<Vec<ExternalWalletTemplate>> {
         let mut templates = Vec::new();
         for (index, initial) in self.0.iter().enumerate() {
-            if let Initial::External { address, funds } = initial {
-                templates.push(ExternalWalletTemplate::new(
-                    format!("wallet_{}", index + 1),
-                    Value(*funds as u64),
-                    address.to_string(),
-                ));
+            match initial {
+                Initial::External { address, funds } => {
+                    templates.push(ExternalWalletTemplate::new(
+                        format!("wallet_{}", index + 1),
+                        Value(*funds as u64),
+                        address.to_string(),
+                    ));
+                }
+                Initial::KnownKey {
+                    name,
+                    secret_key,
+                    funds,
+                    ..
+                } => {
+                    templates.push(ExternalWalletTemplate::new(
+                        format!("wallet_{}", name),
+                        Value(*funds as u64),
+                        known_key_address(secret_key, discrimination)?,
+                    ));
+                }
+                _ => {}
             }
         }
-        templates
+        Ok(templates)
     }
 
     pub fn templates(
@@ -179,3 +576,129 @@ impl Initials {
         templates
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distribution_buckets_sum_to_total() {
+        let initials = Initials::from_distribution(7, 60, 30, 10, "1234").unwrap();
+        assert_eq!(initials.count(), 7);
+        assert_eq!(initials.zero_funds_count(), 1);
+
+        let initials = Initials::from_distribution(1000, 60, 30, 10, "1234").unwrap();
+        assert_eq!(initials.count(), 1000);
+        assert_eq!(initials.zero_funds_count(), 100);
+    }
+
+    #[test]
+    fn merge_sums_buckets_and_dedupes_wallets() {
+        let wallet = |name: &str, funds: usize| Initial::Wallet {
+            name: name.to_string(),
+            funds,
+            pin: "1234".to_string(),
+        };
+        let mut base = Initials(vec![
+            Initial::AboveThreshold {
+                above_threshold: 10,
+                pin: "1234".to_string(),
+            },
+            wallet("alice", 100),
+        ]);
+        let other = Initials(vec![
+            Initial::AboveThreshold {
+                above_threshold: 5,
+                pin: "1234".to_string(),
+            },
+            wallet("alice", 200),
+            wallet("bob", 300),
+        ]);
+
+        let report = base
+            .merge(other.clone(), MergeConflictPolicy::KeepExisting)
+            .unwrap();
+        assert_eq!(base.count(), 17);
+        assert_eq!(report.summed_buckets, 1);
+        assert_eq!(report.added, vec!["bob".to_string()]);
+        assert_eq!(report.skipped, vec!["alice".to_string()]);
+
+        assert!(base.merge(other, MergeConflictPolicy::Fail).is_err());
+        assert_eq!(base.count(), 17);
+    }
+
+    #[test]
+    fn distribution_percentages_must_sum_to_100() {
+        assert!(Initials::from_distribution(10, 60, 30, 20, "1234").is_err());
+    }
+
+    #[test]
+    fn external_address_must_match_discrimination() {
+        let secret_key = SecretKey::<Ed25519Extended>::generate(rand::thread_rng());
+        let address =
+            known_key_address(&secret_key.to_bech32_str(), Discrimination::Production).unwrap();
+        let initials = Initials(vec![Initial::External { address, funds: 1 }]);
+
+        assert!(initials
+            .validate_addresses(Discrimination::Production)
+            .is_ok());
+        assert!(initials.validate_addresses(Discrimination::Test).is_err());
+    }
+
+    #[test]
+    fn snapshot_rows_are_converted_to_external_wallets() {
+        let secret_key = SecretKey::<Ed25519Extended>::generate(rand::thread_rng());
+        let address =
+            known_key_address(&secret_key.to_bech32_str(), Discrimination::Production).unwrap();
+        let snapshot = format!(
+            r#"{{ "initial": [ {{ "fund": [ {{ "address": "{}", "value": 10006 }} ] }} ] }}"#,
+            address
+        );
+
+        let (initials, report) =
+            Initials::from_snapshot_entries(parse_snapshot(&snapshot).unwrap(), 1).unwrap();
+        assert_eq!(initials.wallet_count(), 1);
+        assert_eq!(report.total_funds, 10006);
+        assert!(matches!(
+            &initials.0[0],
+            Initial::External { address: external, funds: 10006 } if external == &address
+        ));
+    }
+
+    #[test]
+    fn snapshot_funds_are_proportional_to_stake() {
+        let entry = |address: &str, value: u64| SnapshotEntry {
+            address: address.to_string(),
+            value,
+        };
+        let (initials, report) = Initials::from_snapshot_entries(
+            vec![
+                entry("a", 5_000_000),
+                entry("b", 1_000_000),
+                entry("c", 999),
+            ],
+            1_000_000,
+        )
+        .unwrap();
+
+        assert!(matches!(initials.0[0], Initial::External { funds: 5, .. }));
+        assert!(matches!(initials.0[1], Initial::External { funds: 1, .. }));
+        assert_eq!(report.total_funds, 6);
+        assert_eq!(report.zero_funds, vec!["c".to_string()]);
+
+        assert!(Initials::from_snapshot_entries(vec![entry("a", 1)], 0).is_err());
+        assert!(snapshot_total_value(&[entry("a", u64::MAX), entry("b", 1)]).is_err());
+    }
+
+    #[test]
+    fn snapshot_reports_every_malformed_row() {
+        let snapshot = r#"{ "initial": [ { "fund": [
+            { "address": "not_an_address", "value": 1 },
+            { "value": 2 }
+        ] } ] }"#;
+
+        let error = parse_snapshot(snapshot).unwrap_err().to_string();
+        assert!(error.contains("initial[0].fund[0]"));
+        assert!(error.contains("initial[0].fund[1]"));
+    }
+}
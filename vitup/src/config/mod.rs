@@ -1,21 +1,36 @@
+mod blockchain;
 mod env;
+mod fees;
 mod initials;
+mod vote_timing;
 
+pub use blockchain::{override_blockchain_configuration, override_wallet_parameters};
 pub use env::VitStartParameters;
-pub use initials::{Initial as InitialEntry, Initials};
+pub use fees::{validate_fees, vote_cast_fee};
+pub use initials::{
+    parse_snapshot, read_snapshot, snapshot_total_value, Initial as InitialEntry, Initials,
+    MergeConflictPolicy, MergeReport, SnapshotEntry, SnapshotReport,
+};
+pub use vote_timing::VoteTiming;
 
 use chain_impl_mockchain::fee::LinearFee;
 use jormungandr_lib::interfaces::{CommitteeIdDef, ConsensusLeaderId, LinearFeeDef};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct DataGenerationConfig {
     #[serde(default)]
     pub consensus_leader_ids: Vec<ConsensusLeaderId>,
+    /// full fee settings, including `per_certificate_fees` and `per_vote_certificate_fees`.
+    /// See `vote_cast_fee` for fields which affect cost of voting
     #[serde(with = "LinearFeeDef")]
     pub linear_fees: LinearFee,
     #[serde(default)]
     pub committees: Vec<CommitteeIdDef>,
+    /// log level overrides for particular nodes (alias -> level), e.g. `{"Leader1": "debug"}`
+    #[serde(default)]
+    pub log_levels: HashMap<String, String>,
     #[serde(flatten)]
     pub params: VitStartParameters,
 }
@@ -26,6 +41,7 @@ impl Default for DataGenerationConfig {
             consensus_leader_ids: Vec::new(),
             linear_fees: LinearFee::new(0, 0, 0),
             committees: Vec::new(),
+            log_levels: HashMap::new(),
             params: Default::default(),
         }
     }
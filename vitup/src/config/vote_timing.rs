@@ -0,0 +1,38 @@
+use crate::error::ErrorKind;
+use crate::Result;
+use serde::{Deserialize, Serialize};
+
+/// Epochs of vote lifecycle: voting starts at `vote_start`, tally can be sent
+/// from `tally_start` until `tally_end`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VoteTiming {
+    pub vote_start: u32,
+    pub tally_start: u32,
+    pub tally_end: u32,
+}
+
+impl VoteTiming {
+    pub fn new(vote_start: u32, tally_start: u32, tally_end: u32) -> Self {
+        Self {
+            vote_start,
+            tally_start,
+            tally_end,
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.vote_start >= self.tally_start {
+            bail!(ErrorKind::InvalidVoteTiming(format!(
+                "vote start epoch ({}) must be before tally start epoch ({})",
+                self.vote_start, self.tally_start
+            )));
+        }
+        if self.tally_start >= self.tally_end {
+            bail!(ErrorKind::InvalidVoteTiming(format!(
+                "tally start epoch ({}) must be before tally end epoch ({})",
+                self.tally_start, self.tally_end
+            )));
+        }
+        Ok(())
+    }
+}
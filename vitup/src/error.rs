@@ -29,12 +29,14 @@ error_chain! {
         MockError(crate::mock::Error);
         ClientRestError(crate::client::rest::Error);
         WalletBackendError(iapyx::WalletBackendError);
+        IapyxControllerError(iapyx::ControllerError);
         Block0ConfigurationError(Block0ConfigurationError);
         VitServerBootstrapperError(ServerBootstrapperError);
         VitRestError(vit_servicing_station_tests::common::clients::RestError);
         ChainAddressError(chain_addr::Error);
         ChainBech32Error(chain_crypto::bech32::Error);
         GlobError(glob::GlobError);
+        CsvError(csv::Error);
     }
 
     errors {
@@ -57,11 +59,166 @@ error_chain! {
             display("proxy with alias: {} not found", alias),
         }
 
+        NodeNotFound(alias: String) {
+            description("node not found"),
+            display("node with alias: {} not found", alias),
+        }
+
+        VotePlanNotFound(id: String) {
+            description("vote plan not found"),
+            display("vote plan: {} not found", id),
+        }
+
+        PrivateVotePlanNotFound(alias: String) {
+            description("private vote plan not found"),
+            display("no private committee data for vote plan: {}", alias),
+        }
+
+        CommitteeWalletNotFound(alias: String) {
+            description("committee wallet not found"),
+            display("committee wallet: {} not found", alias),
+        }
+
+        ElectionKeyEncodingFailed(vote_plan: String, reason: String) {
+            description("cannot encode election public key"),
+            display("cannot encode election public key of vote plan '{}' as base32: {}", vote_plan, reason),
+        }
+
+        InvalidCommittee(reason: String) {
+            description("invalid committee configuration"),
+            display("invalid committee configuration: {}", reason),
+        }
+
+        InvalidMemberSecretKey(details: String) {
+            description("invalid committee member secret key"),
+            display("invalid committee member secret key: {}", details),
+        }
+
+        NotEnoughCommitteeMembers(available: usize, threshold: usize) {
+            description("not enough committee members"),
+            display("only {} committee members available, while threshold is {}", available, threshold),
+        }
+
+        TallyNotEncrypted(proposal: usize) {
+            description("proposal tally is not encrypted"),
+            display("tally of proposal with index {} is not encrypted yet", proposal),
+        }
+
+        NodeStartupFailed(alias: String, logs: String) {
+            description("node startup failed"),
+            display("node '{}' failed to start, see logs in: {}", alias, logs),
+        }
+
+        StationStartupFailed(logs: String) {
+            description("vit station startup failed"),
+            display("vit station failed to start, see logs in: {}", logs),
+        }
+
+        ProxyStartupFailed(logs: String) {
+            description("wallet proxy startup failed"),
+            display("wallet proxy failed to start, see logs in: {}", logs),
+        }
+
         EnvironmentIsDown {
             description("environment is down"),
             display("environment is down"),
         }
 
+        CsvMissingColumns(file: String, columns: Vec<String>) {
+            description("missing required columns in csv file"),
+            display("file '{}' is missing required columns: {:?}", file, columns),
+        }
+
+        CsvRowParseError(file: String, row: usize, reason: String) {
+            description("cannot parse csv row"),
+            display("cannot parse row {} of file '{}': {}", row, file, reason),
+        }
+
+        InvalidTemplate(file: String, entry: usize, field: String, problem: String) {
+            description("invalid external template"),
+            display("invalid template file '{}', entry: {}, field: '{}': {}", file, entry, field, problem),
+        }
+
+        DeploymentFileMissing(file: String) {
+            description("deployment file is missing"),
+            display("cannot resume deployment, file is missing: {}", file),
+        }
+
+        DeploymentGenesisMismatch(file: String) {
+            description("rebuilt genesis differs from saved one"),
+            display("cannot resume deployment, genesis rebuilt from saved seed and parameters differs from '{}'", file),
+        }
+
+        DeploymentChecksumMismatch(file: String, expected: String, actual: String) {
+            description("deployment file was modified"),
+            display("cannot resume deployment, checksum mismatch for '{}': expected {}, got {}", file, expected, actual),
+        }
+
+        DeploymentStateCorrupted(details: String) {
+            description("deployment state is corrupted"),
+            display("cannot resume deployment, deployment state is corrupted: {}", details),
+        }
+
+        InvalidLogLevel(alias: String, reason: String) {
+            description("invalid node log level override"),
+            display("invalid log level override for node '{}': {}", alias, reason),
+        }
+
+        InvalidFees(reason: String) {
+            description("invalid fees configuration"),
+            display("invalid fees configuration: {}", reason),
+        }
+
+        InvalidInitials(reason: String) {
+            description("invalid initials"),
+            display("invalid initials: {}", reason),
+        }
+
+        InvalidBlockchainOverride(reason: String) {
+            description("invalid blockchain configuration override"),
+            display("invalid blockchain configuration override: {}", reason),
+        }
+
+        InvalidVoteTiming(reason: String) {
+            description("invalid vote timing"),
+            display("invalid vote timing: {}", reason),
+        }
+
+        QrCodeMismatch(file: String, reason: String) {
+            description("qr code does not match block0"),
+            display("qr code verification failed for '{}': {}", file, reason),
+        }
+
+        CertificateGenerationFailed(reason: String) {
+            description("cannot generate certificate"),
+            display("cannot generate self-signed certificate: {}", reason),
+        }
+
+        QrCodeGenerationFailed(reason: String) {
+            description("cannot generate qr code"),
+            display("cannot generate qr code: {}", reason),
+        }
+
+        InvalidBlock0(reason: String) {
+            description("invalid block0"),
+            display("cannot read block0: {}", reason),
+        }
+
+        InvalidVoteKey(reason: String) {
+            description("invalid vote key"),
+            display("cannot derive account id from vote key: {}", reason),
+        }
+
+        InvalidBlock0Date(date: String, reason: String) {
+            description("invalid block0 date"),
+            display("cannot parse block0 date '{}', expected format '%Y-%m-%d %H:%M:%S': {}", date, reason),
+        }
+
+        InvalidSnapshot(reason: String) {
+            description("invalid snapshot"),
+            display("invalid snapshot: {}", reason),
+        }
+
         SnapshotIntialReadError {
             description("wrong format for snapshot data"),
             display("wrong format for snapshot data"),
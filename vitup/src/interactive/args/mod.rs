@@ -1,2 +1,3 @@
 pub mod describe;
 pub mod show;
+pub mod tally;
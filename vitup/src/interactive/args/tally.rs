@@ -0,0 +1,42 @@
+use crate::error::ErrorKind;
+use crate::interactive::VitInteractiveCommandExec;
+use crate::scenario::tally::tally_vote_plan;
+use crate::Result;
+use structopt::StructOpt;
+
+/// Tallies vote plan with committee wallet. For private vote plans decryption
+/// shares are generated and private tally is sent as well
+#[derive(StructOpt, Debug)]
+pub struct Tally {
+    /// vote plan alias (fund name)
+    #[structopt(short = "v", long = "vote-plan")]
+    pub vote_plan: String,
+
+    /// committee wallet alias
+    #[structopt(short = "c", long = "committee", default_value = "committee_1")]
+    pub committee: String,
+
+    /// node alias to which tally fragments are sent
+    #[structopt(short = "n", long = "node", default_value = "Wallet_Node")]
+    pub node: String,
+}
+
+impl Tally {
+    pub fn exec(&self, command: &mut VitInteractiveCommandExec) -> Result<()> {
+        let node = command
+            .controller
+            .nodes()
+            .iter()
+            .find(|node| *node.alias() == self.node)
+            .ok_or_else(|| ErrorKind::NodeNotFound(self.node.clone()))?;
+
+        let vote_plan_status = tally_vote_plan(
+            command.controller.controller(),
+            &self.committee,
+            &self.vote_plan,
+            node,
+        )?;
+        println!("{}", serde_json::to_string_pretty(&vote_plan_status)?);
+        Ok(())
+    }
+}
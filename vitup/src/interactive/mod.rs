@@ -1,7 +1,7 @@
 mod args;
 mod controller;
 
-pub use args::{describe, show};
+pub use args::{describe, show, tally};
 
 pub use controller::VitUserInteractionController;
 use jormungandr_scenario_tests::interactive::{
@@ -46,16 +46,25 @@ impl<'a> InteractiveCommandExec for VitInteractiveCommandExec {
             Ok(interactive) => {
                 if let Err(err) = {
                     match interactive {
-                        VitInteractiveCommand::Show(show) => show.exec(self),
+                        VitInteractiveCommand::Show(show) => {
+                            show.exec(self).map_err(|err| err.to_string())
+                        }
                         VitInteractiveCommand::Exit => Ok(()),
-                        VitInteractiveCommand::Describe(describe) => describe.exec(self),
-                        VitInteractiveCommand::Send(send) => send.exec(self.controller_mut()),
-                        VitInteractiveCommand::Explorer(explorer) => {
-                            explorer.exec(self.controller_mut())
+                        VitInteractiveCommand::Describe(describe) => {
+                            describe.exec(self).map_err(|err| err.to_string())
+                        }
+                        VitInteractiveCommand::Send(send) => send
+                            .exec(self.controller_mut())
+                            .map_err(|err| err.to_string()),
+                        VitInteractiveCommand::Explorer(explorer) => explorer
+                            .exec(self.controller_mut())
+                            .map_err(|err| err.to_string()),
+                        VitInteractiveCommand::Tally(tally) => {
+                            tally.exec(self).map_err(|err| err.to_string())
                         }
                     }
                 } {
-                    console.format_error(InteractiveCommandError::UserError(err.to_string()));
+                    console.format_error(InteractiveCommandError::UserError(err));
                 }
             }
             Err(err) => console.show_help(InteractiveCommandError::UserError(err.to_string())),
@@ -77,4 +86,6 @@ pub enum VitInteractiveCommand {
     Describe(describe::Describe),
     /// send fragments
     Send(send::Send),
+    /// tally vote plan
+    Tally(tally::Tally),
 }
@@ -1,6 +1,7 @@
 pub type ControlContextLock = Arc<Mutex<ControlContext>>;
 use crate::config::VitStartParameters;
 use crate::manager::ServerStopper;
+use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::net::SocketAddr;
@@ -8,6 +9,7 @@ use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::Mutex;
+use thiserror::Error;
 
 pub struct ControlContext {
     server_stopper: Option<ServerStopper>,
@@ -17,6 +19,7 @@ pub struct ControlContext {
     state: State,
     should_stop: bool,
     should_start: bool,
+    vote_timestamps_update: Option<VoteTimestamps>,
     api_token: Option<String>,
 }
 
@@ -34,6 +37,7 @@ impl ControlContext {
             state: State::Idle,
             should_stop: false,
             should_start: false,
+            vote_timestamps_update: None,
             api_token,
         }
     }
@@ -90,6 +94,65 @@ impl ControlContext {
         self.should_stop = true;
     }
 
+    /// Requests backend start. Allowed only when backend is idle and no start is pending
+    pub fn request_start(&mut self) -> Result<Lifecycle, LifecycleError> {
+        if self.state != State::Idle || self.should_start {
+            return Err(LifecycleError::WrongState {
+                current: self.state,
+                expected: State::Idle,
+            });
+        }
+        self.start();
+        Ok(self.lifecycle())
+    }
+
+    /// Requests backend stop. Allowed only when backend is running
+    pub fn request_stop(&mut self) -> Result<Lifecycle, LifecycleError> {
+        if self.state != State::Running {
+            return Err(LifecycleError::WrongState {
+                current: self.state,
+                expected: State::Running,
+            });
+        }
+        self.stop();
+        Ok(self.lifecycle())
+    }
+
+    /// Requests update of vote timestamps of running backend. Vit station is regenerated
+    /// with new timestamps by service loop
+    pub fn request_vote_timestamps_update(
+        &mut self,
+        timestamps: VoteTimestamps,
+    ) -> Result<Lifecycle, LifecycleError> {
+        if self.state != State::Running {
+            return Err(LifecycleError::WrongState {
+                current: self.state,
+                expected: State::Running,
+            });
+        }
+        timestamps.validate()?;
+        self.setup.vote_start_timestamp = Some(timestamps.vote_start);
+        self.setup.tally_start_timestamp = Some(timestamps.tally_start);
+        self.setup.tally_end_timestamp = Some(timestamps.tally_end);
+        if timestamps.next_vote_start.is_some() {
+            self.setup.next_vote_start_time = timestamps.next_vote_start;
+        }
+        self.vote_timestamps_update = Some(timestamps);
+        Ok(self.lifecycle())
+    }
+
+    pub fn take_vote_timestamps_update(&mut self) -> Option<VoteTimestamps> {
+        self.vote_timestamps_update.take()
+    }
+
+    pub fn lifecycle(&self) -> Lifecycle {
+        Lifecycle {
+            state: self.state,
+            start_requested: self.should_start,
+            stop_requested: self.should_stop,
+        }
+    }
+
     pub fn api_token(&self) -> Option<String> {
         self.api_token.clone()
     }
@@ -104,6 +167,55 @@ impl ControlContext {
     }
 }
 
+/// Current state of managed backend together with requests which were not handled yet
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Lifecycle {
+    pub state: State,
+    pub start_requested: bool,
+    pub stop_requested: bool,
+}
+
+/// New vote timestamps for vit station of running backend
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
+pub struct VoteTimestamps {
+    pub vote_start: NaiveDateTime,
+    pub tally_start: NaiveDateTime,
+    pub tally_end: NaiveDateTime,
+    #[serde(default)]
+    pub next_vote_start: Option<NaiveDateTime>,
+}
+
+impl VoteTimestamps {
+    pub fn validate(&self) -> Result<(), LifecycleError> {
+        if self.vote_start >= self.tally_start {
+            return Err(LifecycleError::InvalidVoteTimestamps(
+                "vote start must be before tally start".to_string(),
+            ));
+        }
+        if self.tally_start >= self.tally_end {
+            return Err(LifecycleError::InvalidVoteTimestamps(
+                "tally start must be before tally end".to_string(),
+            ));
+        }
+        if let Some(next_vote_start) = self.next_vote_start {
+            if next_vote_start <= self.tally_end {
+                return Err(LifecycleError::InvalidVoteTimestamps(
+                    "next vote start must be after tally end".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum LifecycleError {
+    #[error("wrong state for operation ('{current}'), please wait until state is '{expected}'")]
+    WrongState { current: State, expected: State },
+    #[error("invalid vote timestamps: {0}")]
+    InvalidVoteTimestamps(String),
+}
+
 #[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
 pub enum State {
     Idle,
@@ -3,7 +3,9 @@ pub mod file_lister;
 mod rest;
 mod service;
 
-pub use context::{ControlContext, ControlContextLock, State};
+pub use context::{
+    ControlContext, ControlContextLock, Lifecycle, LifecycleError, State, VoteTimestamps,
+};
 pub use jortestkit::web::api_token::*;
-pub use rest::{start_rest_server, ServerStopper};
+pub use rest::{start_rest_server, ErrorMessage, ServerStopper};
 pub use service::ManagerService;
@@ -1,5 +1,5 @@
 use super::file_lister;
-use super::State;
+use super::{LifecycleError, VoteTimestamps};
 use crate::config::VitStartParameters;
 use crate::manager::{
     APIToken, APITokenManager, ControlContext, ControlContextLock, API_TOKEN_HEADER,
@@ -7,12 +7,21 @@ use crate::manager::{
 use futures::FutureExt;
 use futures::{channel::mpsc, StreamExt};
 use jortestkit::web::api_token::TokenError;
+use serde::{Deserialize, Serialize};
 use std::convert::Infallible;
 use std::sync::Arc;
 use warp::http::StatusCode;
 use warp::reject::Reject;
 use warp::{Filter, Rejection, Reply};
 impl Reject for file_lister::Error {}
+impl Reject for LifecycleError {}
+
+/// Body of every error response returned by manager
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorMessage {
+    pub code: u16,
+    pub message: String,
+}
 
 #[derive(Clone)]
 pub struct ServerStopper(mpsc::Sender<()>);
@@ -80,7 +89,22 @@ pub async fn start_rest_server(context: ControlContextLock) {
             warp::any().boxed()
         };
 
-        root.and(api_token_filter).and(start.or(stop)).boxed()
+        let vote_timing = warp::path!("vote-timing")
+            .and(warp::post())
+            .and(with_context.clone())
+            .and(warp::body::json())
+            .and_then(vote_timing_handler)
+            .boxed();
+
+        let lifecycle = warp::path!("status")
+            .and(warp::get())
+            .and(with_context.clone())
+            .and_then(lifecycle_handler)
+            .boxed();
+
+        root.and(api_token_filter)
+            .and(start.or(stop).or(vote_timing).or(lifecycle))
+            .boxed()
     };
 
     let status = warp::path!("status")
@@ -106,48 +130,32 @@ pub async fn start_handler(
     parameters: VitStartParameters,
 ) -> Result<impl Reply, Rejection> {
     let mut context_lock = context.lock().unwrap();
+    let lifecycle = context_lock.request_start()?;
     context_lock.set_parameters(parameters);
-    let state = context_lock.state();
-    if *state == State::Idle {
-        context_lock.start();
-        return Ok("start event received".to_owned()).map(|r| warp::reply::json(&r));
-    }
-    return Ok(format!(
-        "Wrong state to stop operation ('{}'), plase wait until state is '{}'",
-        state,
-        State::Idle
-    ))
-    .map(|r| warp::reply::json(&r));
+    Ok(lifecycle).map(|r| warp::reply::json(&r))
 }
 
 pub async fn start_default_handler(context: ControlContextLock) -> Result<impl Reply, Rejection> {
     let mut context_lock = context.lock().unwrap();
-    let state = context_lock.state();
-    if *state == State::Idle {
-        context_lock.start();
-        return Ok("start event received".to_owned()).map(|r| warp::reply::json(&r));
-    }
-    return Ok(format!(
-        "Wrong state to stop operation ('{}'), plase wait until state is '{}'",
-        state,
-        State::Idle
-    ))
-    .map(|r| warp::reply::json(&r));
+    Ok(context_lock.request_start()?).map(|r| warp::reply::json(&r))
 }
 
 pub async fn stop_handler(context: ControlContextLock) -> Result<impl Reply, Rejection> {
     let mut context_lock = context.lock().unwrap();
-    let state = context_lock.state();
-    if *state == State::Running {
-        context_lock.stop();
-        return Ok("stop event received".to_owned()).map(|r| warp::reply::json(&r));
-    }
-    return Ok(format!(
-        "Wrong state to stop operation ('{}'), plase wait until state is '{}'",
-        state,
-        State::Running
-    ))
-    .map(|r| warp::reply::json(&r));
+    Ok(context_lock.request_stop()?).map(|r| warp::reply::json(&r))
+}
+
+pub async fn vote_timing_handler(
+    context: ControlContextLock,
+    timestamps: VoteTimestamps,
+) -> Result<impl Reply, Rejection> {
+    let mut context_lock = context.lock().unwrap();
+    Ok(context_lock.request_vote_timestamps_update(timestamps)?).map(|r| warp::reply::json(&r))
+}
+
+pub async fn lifecycle_handler(context: ControlContextLock) -> Result<impl Reply, Rejection> {
+    let context_lock = context.lock().unwrap();
+    Ok(context_lock.lifecycle()).map(|r| warp::reply::json(&r))
 }
 
 pub async fn status_handler(context: ControlContextLock) -> Result<impl Reply, Rejection> {
@@ -156,20 +164,36 @@ pub async fn status_handler(context: ControlContextLock) -> Result<impl Reply, R
 }
 
 async fn report_invalid(r: Rejection) -> Result<impl Reply, Infallible> {
-    if let Some(e) = r.find::<file_lister::Error>() {
-        // It was our specific error type, do whatever we want. We
-        // will just print out the error text.
-        Ok(warp::reply::with_status(
-            e.to_string(),
-            StatusCode::BAD_REQUEST,
-        ))
+    let (code, message) = if r.is_not_found() {
+        (StatusCode::NOT_FOUND, "not found".to_string())
+    } else if let Some(e) = r.find::<file_lister::Error>() {
+        (StatusCode::BAD_REQUEST, e.to_string())
+    } else if let Some(e) = r.find::<LifecycleError>() {
+        let code = match e {
+            LifecycleError::WrongState { .. } => StatusCode::CONFLICT,
+            LifecycleError::InvalidVoteTimestamps(_) => StatusCode::BAD_REQUEST,
+        };
+        (code, e.to_string())
+    } else if let Some(e) = r.find::<TokenError>() {
+        (StatusCode::UNAUTHORIZED, e.to_string())
+    } else if let Some(e) = r.find::<warp::reject::MissingHeader>() {
+        (StatusCode::UNAUTHORIZED, e.to_string())
+    } else if let Some(e) = r.find::<warp::filters::body::BodyDeserializeError>() {
+        (StatusCode::BAD_REQUEST, e.to_string())
+    } else if let Some(e) = r.find::<warp::reject::MethodNotAllowed>() {
+        (StatusCode::METHOD_NOT_ALLOWED, e.to_string())
     } else {
-        // Do prettier error reporting for the default error here.
-        Ok(warp::reply::with_status(
-            format!("internal error: {:?}", r),
+        (
             StatusCode::INTERNAL_SERVER_ERROR,
-        ))
-    }
+            format!("internal error: {:?}", r),
+        )
+    };
+
+    let body = ErrorMessage {
+        code: code.as_u16(),
+        message,
+    };
+    Ok(warp::reply::with_status(warp::reply::json(&body), code))
 }
 
 pub async fn authorize_token(
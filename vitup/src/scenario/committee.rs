@@ -0,0 +1,242 @@
+use crate::error::ErrorKind;
+use crate::Result;
+use bech32::{FromBase32, ToBase32};
+use chain_impl_mockchain::vote::{PrivateTallyState, Tally, VotePlanStatus};
+use chain_vote::committee::{ElectionPublicKey, MemberCommunicationKey, MemberState};
+use chain_vote::{Crs, MemberPublicKey, MemberSecretKey, TallyDecryptShare};
+use jormungandr_scenario_tests::scenario::settings::Settings;
+use rand::Rng;
+use rand_chacha::ChaChaRng;
+use rand_core::SeedableRng;
+use std::path::Path;
+
+pub const MEMBER_SECRET_KEY_FILE: &str = "member_secret_key.sk";
+pub const MEMBER_SECRET_KEY_HRP: &str = "p256k1_membersk";
+
+/// Election public key used by wallets to encrypt votes for given private vote plan
+pub fn election_public_key(settings: &Settings, vote_plan: &str) -> Result<ElectionPublicKey> {
+    settings
+        .private_vote_plans
+        .get(vote_plan)
+        .map(|private_vote_plan| private_vote_plan.encrypting_vote_key())
+        .ok_or_else(|| ErrorKind::PrivateVotePlanNotFound(vote_plan.to_string()).into())
+}
+
+/// Committee member keys generated by vitup instead of jormungandr settings, so threshold
+/// can be chosen and test can pre-compute election public key before backend is started.
+///
+/// Anyone who knows the seed can recreate member secret keys and decrypt every private
+/// vote, so seeded keys must never be used outside of tests
+pub struct SeededCommittee {
+    member_states: Vec<MemberState>,
+}
+
+impl SeededCommittee {
+    /// `seed` is hex encoded 32 bytes
+    pub fn new(seed: &str, members: usize, threshold: usize) -> Result<Self> {
+        let invalid_seed =
+            || ErrorKind::InvalidCommittee(format!("seed should be 32 bytes in hex: {}", seed));
+        let bytes = hex::decode(seed.trim()).map_err(|_| invalid_seed())?;
+        if bytes.len() != 32 {
+            bail!(invalid_seed());
+        }
+        let mut seed_bytes = [0u8; 32];
+        seed_bytes.copy_from_slice(&bytes);
+        Ok(Self::from_seed(seed_bytes, members, threshold))
+    }
+
+    /// Committee with keys from system randomness, seed is not kept anywhere
+    pub fn random(members: usize, threshold: usize) -> Self {
+        let mut seed_bytes = [0u8; 32];
+        rand::thread_rng().fill(&mut seed_bytes);
+        Self::from_seed(seed_bytes, members, threshold)
+    }
+
+    fn from_seed(seed_bytes: [u8; 32], members: usize, threshold: usize) -> Self {
+        let mut rng = ChaChaRng::from_seed(seed_bytes);
+        let crs = Crs::from_hash(&seed_bytes);
+        let communication_keys: Vec<_> = (0..members)
+            .map(|_| MemberCommunicationKey::new(&mut rng).to_public())
+            .collect();
+        let member_states = (0..members)
+            .map(|index| MemberState::new(&mut rng, threshold, &crs, &communication_keys, index))
+            .collect();
+        Self { member_states }
+    }
+
+    pub fn member_public_keys(&self) -> Vec<MemberPublicKey> {
+        self.member_states
+            .iter()
+            .map(|state| state.public_key())
+            .collect()
+    }
+
+    pub fn member_secret_keys(&self) -> Vec<MemberSecretKey> {
+        self.member_states
+            .iter()
+            .map(|state| state.secret_key().clone())
+            .collect()
+    }
+
+    pub fn election_public_key(&self) -> ElectionPublicKey {
+        ElectionPublicKey::from_participants(&self.member_public_keys())
+    }
+
+    /// Writes bech32 encoded secret key of each member as
+    /// `{dir}/committee_{n}/member_secret_key.sk`, readable by `CommitteeMembers::load_all`
+    pub fn write_to<P: AsRef<Path>>(&self, dir: P) -> Result<()> {
+        for (index, secret_key) in self.member_secret_keys().iter().enumerate() {
+            let member_dir = dir.as_ref().join(format!("committee_{}", index + 1));
+            std::fs::create_dir_all(&member_dir)?;
+            let encoded = bech32::encode(MEMBER_SECRET_KEY_HRP, secret_key.to_bytes().to_base32())
+                .map_err(|err| ErrorKind::InvalidMemberSecretKey(err.to_string()))?;
+            std::fs::write(member_dir.join(MEMBER_SECRET_KEY_FILE), encoded)?;
+        }
+        Ok(())
+    }
+}
+
+/// Collects secret keys of committee members (e.g. loaded from dumped private vote keys)
+/// and produces decryption shares required by private tally. Only `threshold` members
+/// need to be present, so tally can be tested also when part of committee is unavailable
+pub struct CommitteeMembers {
+    threshold: usize,
+    secret_keys: Vec<MemberSecretKey>,
+}
+
+impl CommitteeMembers {
+    pub fn new(threshold: usize) -> Self {
+        Self {
+            threshold,
+            secret_keys: Vec::new(),
+        }
+    }
+
+    pub fn add(&mut self, secret_key: MemberSecretKey) -> &mut Self {
+        self.secret_keys.push(secret_key);
+        self
+    }
+
+    /// Loads bech32 encoded member secret key from file
+    pub fn load<P: AsRef<Path>>(&mut self, path: P) -> Result<&mut Self> {
+        let path = path.as_ref();
+        let invalid_key = || ErrorKind::InvalidMemberSecretKey(path.to_string_lossy().to_string());
+
+        let content = std::fs::read_to_string(path)?;
+        let (_, data) = bech32::decode(content.trim()).map_err(|_| invalid_key())?;
+        let bytes = Vec::<u8>::from_base32(&data).map_err(|_| invalid_key())?;
+        let secret_key = MemberSecretKey::from_bytes(&bytes).ok_or_else(invalid_key)?;
+        Ok(self.add(secret_key))
+    }
+
+    /// Loads all member secret keys found in private vote keys directory
+    pub fn load_all<P: AsRef<Path>>(&mut self, private_vote_keys: P) -> Result<&mut Self> {
+        let pattern = private_vote_keys
+            .as_ref()
+            .join("**")
+            .join(MEMBER_SECRET_KEY_FILE);
+        for entry in glob::glob(&pattern.to_string_lossy())
+            .map_err(|err| ErrorKind::InvalidMemberSecretKey(err.to_string()))?
+        {
+            self.load(entry?)?;
+        }
+        Ok(self)
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    pub fn len(&self) -> usize {
+        self.secret_keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.secret_keys.is_empty()
+    }
+
+    pub fn is_threshold_reached(&self) -> bool {
+        self.len() >= self.threshold
+    }
+
+    fn check_threshold(&self) -> Result<()> {
+        if !self.is_threshold_reached() {
+            bail!(ErrorKind::NotEnoughCommitteeMembers(
+                self.len(),
+                self.threshold
+            ));
+        }
+        Ok(())
+    }
+
+    /// Decryption shares of all available members for each proposal of vote plan.
+    /// Vote plan needs to have encrypted tally already
+    pub fn decryption_shares(
+        &self,
+        vote_plan_status: &VotePlanStatus,
+    ) -> Result<Vec<Vec<TallyDecryptShare>>> {
+        self.check_threshold()?;
+
+        vote_plan_status
+            .proposals
+            .iter()
+            .enumerate()
+            .map(|(index, proposal)| match &proposal.tally {
+                Some(Tally::Private {
+                    state:
+                        PrivateTallyState::Encrypted {
+                            encrypted_tally, ..
+                        },
+                }) => Ok(self
+                    .secret_keys
+                    .iter()
+                    .map(|secret_key| encrypted_tally.finish(secret_key).1)
+                    .collect()),
+                _ => Err(ErrorKind::TallyNotEncrypted(index).into()),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEED: &str = "0001020304050607080910111213141516171819202122232425262728293031";
+
+    #[test]
+    fn seeded_committee_is_deterministic() {
+        let first = SeededCommittee::new(SEED, 3, 2).unwrap();
+        let second = SeededCommittee::new(SEED, 3, 2).unwrap();
+        assert_eq!(
+            first.election_public_key().to_bytes(),
+            second.election_public_key().to_bytes()
+        );
+        assert!(SeededCommittee::new("00", 3, 2).is_err());
+    }
+
+    #[test]
+    fn threshold_is_reached_with_exactly_threshold_members() {
+        let mut secret_keys = SeededCommittee::new(SEED, 3, 2)
+            .unwrap()
+            .member_secret_keys()
+            .into_iter();
+        let mut members = CommitteeMembers::new(2);
+
+        members.add(secret_keys.next().unwrap());
+        assert!(!members.is_threshold_reached());
+        match members.check_threshold().unwrap_err().kind() {
+            ErrorKind::NotEnoughCommitteeMembers(available, threshold) => {
+                assert_eq!((*available, *threshold), (1, 2))
+            }
+            other => panic!("unexpected error: {}", other),
+        }
+
+        members.add(secret_keys.next().unwrap());
+        assert!(members.is_threshold_reached());
+        assert!(members.check_threshold().is_ok());
+
+        members.add(secret_keys.next().unwrap());
+        assert!(members.is_threshold_reached());
+    }
+}
@@ -1,15 +1,20 @@
+use crate::config::override_blockchain_configuration;
 use crate::scenario::{
     settings::VitSettings,
-    vit_station::{VitStation, VitStationController, VitStationControllerError},
+    vit_station::{StoragePolicy, VitStation, VitStationController, VitStationControllerError},
     wallet::{
         Error as WalletProxyError, WalletProxy, WalletProxyController, WalletProxySpawnParams,
     },
 };
+use crate::setup::start::quick::WALLET_NODE;
 use crate::{error::ErrorKind, Result};
 use iapyx::WalletBackend;
 use indicatif::ProgressBar;
+use jormungandr_lib::interfaces::Block0Configuration;
 use jormungandr_scenario_tests::scenario::{ContextChaCha, Controller, ControllerBuilder};
 use jormungandr_testing_utils::testing::network_builder::{Blockchain, Topology};
+use serde_json::Value;
+use std::collections::HashMap;
 use std::path::Path;
 use vit_servicing_station_tests::common::data::ValidVotePlanParameters;
 use vit_servicing_station_tests::common::data::ValidVotingTemplateGenerator;
@@ -17,10 +22,16 @@ use vit_servicing_station_tests::common::data::ValidVotingTemplateGenerator;
 pub struct VitControllerBuilder {
     controller_builder: ControllerBuilder,
     vit_settings: Option<VitSettings>,
+    free_ports: bool,
+    log_levels: HashMap<String, String>,
+    blockchain_override: Option<Value>,
 }
 
 pub struct VitController {
     vit_settings: VitSettings,
+    log_levels: HashMap<String, String>,
+    storage_policy: StoragePolicy,
+    wallet_debug: bool,
 }
 
 impl VitControllerBuilder {
@@ -28,9 +39,29 @@ impl VitControllerBuilder {
         Self {
             controller_builder: ControllerBuilder::new(title),
             vit_settings: None,
+            free_ports: false,
+            log_levels: HashMap::new(),
+            blockchain_override: None,
         }
     }
 
+    /// fields of generated blockchain configuration to override, applied after
+    /// settings are built from blockchain. Discrimination and fees used by wallets
+    /// have to be resolved beforehand with `override_wallet_parameters`
+    pub fn set_blockchain_override(&mut self, blockchain_override: Value) {
+        self.blockchain_override = Some(blockchain_override);
+    }
+
+    /// log levels for particular nodes, which override context log level
+    pub fn set_log_levels(&mut self, log_levels: HashMap<String, String>) {
+        self.log_levels = log_levels;
+    }
+
+    /// vit station and wallet proxy will listen on ports allocated by OS
+    pub fn use_free_ports(&mut self) {
+        self.free_ports = true;
+    }
+
     pub fn set_topology(&mut self, topology: Topology) {
         self.controller_builder.set_topology(topology);
     }
@@ -39,27 +70,80 @@ impl VitControllerBuilder {
         self.controller_builder.set_blockchain(blockchain);
     }
 
-    pub fn build_settings(&mut self, context: &mut ContextChaCha) {
+    pub fn build_settings(&mut self, context: &mut ContextChaCha) -> Result<()> {
         self.controller_builder.build_settings(context);
-        self.vit_settings = Some(VitSettings::new(context));
+        if let Some(overrides) = &self.blockchain_override {
+            let block0 = &mut self
+                .controller_builder
+                .settings_mut()
+                .network_settings
+                .block0;
+            block0.blockchain_configuration =
+                override_blockchain_configuration(&block0.blockchain_configuration, overrides)?;
+        }
+        let mut vit_settings = VitSettings::new(context);
+        if self.free_ports {
+            vit_settings.use_free_ports()?;
+        }
+        self.vit_settings = Some(vit_settings);
+        Ok(())
+    }
+
+    /// Genesis configuration of settings built with `build_settings`
+    pub fn block0_configuration(&mut self) -> Block0Configuration {
+        self.controller_builder
+            .settings_mut()
+            .network_settings
+            .block0
+            .clone()
     }
 
     pub fn build_controllers(self, context: ContextChaCha) -> Result<(VitController, Controller)> {
         let controller = self.controller_builder.build(context)?;
-        let vit_controller = VitController::new(self.vit_settings.unwrap());
+        let mut vit_controller = VitController::new(self.vit_settings.unwrap());
+        vit_controller.log_levels = self.log_levels;
         Ok((vit_controller, controller))
     }
 }
 
 impl VitController {
     pub fn new(vit_settings: VitSettings) -> Self {
-        Self { vit_settings }
+        Self {
+            vit_settings,
+            log_levels: HashMap::new(),
+            storage_policy: Default::default(),
+            wallet_debug: true,
+        }
+    }
+
+    /// By default vit station database is generated on every spawn. Resumed deployment keeps
+    /// existing database and running one only updates its timestamps
+    pub fn set_storage_policy(&mut self, storage_policy: StoragePolicy) {
+        self.storage_policy = storage_policy;
+    }
+
+    /// Whether iapyx wallets built by controller print requests and responses. Enabled by default
+    pub fn set_wallet_debug(&mut self, wallet_debug: bool) {
+        self.wallet_debug = wallet_debug;
+    }
+
+    fn wallet_backend_settings(&self) -> iapyx::WalletBackendSettings {
+        iapyx::WalletBackendSettings {
+            use_https_for_post: false,
+            enable_debug: self.wallet_debug,
+            certificate: None,
+        }
     }
 
     pub fn vit_settings(&self) -> &VitSettings {
         &self.vit_settings
     }
 
+    /// log level override for given node, if any
+    pub fn log_level(&self, alias: &str) -> Option<&String> {
+        self.log_levels.get(alias)
+    }
+
     /// iapyx wallet is a mock mobile wallet
     /// it uses some production code while handling wallet operation
     // therefore controller has separate method to build such wallet
@@ -68,11 +152,7 @@ impl VitController {
         mnemonics: &str,
         wallet_proxy: &WalletProxyController,
     ) -> Result<iapyx::Controller> {
-        let settings = iapyx::WalletBackendSettings {
-            use_https_for_post: false,
-            enable_debug: true,
-            certificate: None,
-        };
+        let settings = self.wallet_backend_settings();
 
         let backend = WalletBackend::new_from_addresses(
             wallet_proxy.settings().base_address().to_string(),
@@ -93,11 +173,7 @@ impl VitController {
         password: &str,
         wallet_proxy: &WalletProxyController,
     ) -> Result<iapyx::Controller> {
-        let settings = iapyx::WalletBackendSettings {
-            use_https_for_post: false,
-            enable_debug: true,
-            certificate: None,
-        };
+        let settings = self.wallet_backend_settings();
 
         Ok(iapyx::Controller::recover_from_qr(
             wallet_proxy.settings().base_address().to_string(),
@@ -108,6 +184,35 @@ impl VitController {
         .unwrap())
     }
 
+    /// iapyx wallet which talks directly to wallet node and vit station,
+    /// bypassing wallet proxy. Useful for isolating proxy specific failures
+    pub fn iapyx_wallet_from_secret_key_direct<P: AsRef<Path>>(
+        &self,
+        secret_key: P,
+        controller: &Controller,
+        vit_station: &VitStationController,
+    ) -> Result<iapyx::Controller> {
+        let settings = self.wallet_backend_settings();
+
+        let node_setting = controller
+            .settings()
+            .network_settings
+            .nodes
+            .get(WALLET_NODE)
+            .ok_or_else(|| ErrorKind::NodeNotFound(WALLET_NODE.to_string()))?;
+
+        let backend = WalletBackend::new_from_addresses(
+            vit_station.address().to_string(),
+            node_setting.config().rest.listen.to_string(),
+            vit_station.address().to_string(),
+            settings,
+        );
+
+        Ok(iapyx::Controller::recover_from_sk_with_backend(
+            backend, secret_key,
+        )?)
+    }
+
     pub fn spawn_vit_station(
         &self,
         controller: &mut Controller,
@@ -138,8 +243,8 @@ impl VitController {
             &block0_file.as_path(),
             working_directory,
             &version,
-        )
-        .unwrap();
+            self.storage_policy,
+        )?;
         Ok(vit_station.controller())
     }
 
@@ -185,8 +290,7 @@ impl VitController {
             &block0_file.as_path(),
             &working_directory.path(),
             params.protocol.clone(),
-        )
-        .unwrap();
+        )?;
         Ok(wallet_proxy.controller())
     }
 
@@ -1,5 +1,10 @@
+pub mod committee;
 pub mod controller;
 pub mod network;
+pub mod ports;
+pub mod resume;
 pub mod settings;
+pub mod spawned;
+pub mod tally;
 pub mod vit_station;
 pub mod wallet;
@@ -1,11 +1,14 @@
+use crate::error::{ErrorKind, ResultExt};
 use crate::interactive::VitInteractiveCommandExec;
 use crate::interactive::VitUserInteractionController;
 use crate::manager::{ControlContext, ControlContextLock, ManagerService, State};
 use crate::scenario::controller::VitController;
+use crate::scenario::ports::AUTO_ENDPOINT;
+use crate::scenario::spawned::SpawnedNetwork;
 use crate::setup::start::quick::{
     QuickVitBackendSettingsBuilder, LEADER_1, LEADER_2, LEADER_3, LEADER_4, WALLET_NODE,
 };
-use crate::vit_station::VitStationController;
+use crate::vit_station::{StoragePolicy, VitStationController};
 use crate::wallet::WalletProxyController;
 use crate::wallet::WalletProxySpawnParams;
 use crate::Result;
@@ -14,10 +17,7 @@ use jormungandr_lib::interfaces::Explorer;
 use jormungandr_scenario_tests::interactive::UserInteractionController;
 use jormungandr_scenario_tests::scenario::Controller;
 use jormungandr_scenario_tests::NodeController;
-use jormungandr_scenario_tests::{
-    node::{LeadershipMode, PersistenceMode},
-    scenario::Context,
-};
+use jormungandr_scenario_tests::{node::PersistenceMode, scenario::Context};
 use jormungandr_testing_utils::testing::network_builder::SpawnParams;
 use jortestkit::prelude::UserInteraction;
 use rand_chacha::ChaChaRng;
@@ -43,83 +43,158 @@ pub fn setup_network(
     VitStationController,
     WalletProxyController,
 )> {
+    let (nodes, vit_station) = setup_network_without_proxy(
+        controller,
+        vit_controller,
+        vit_parameters,
+        vit_data_generator,
+        vit_version,
+    )?;
+
+    println!("Spawning wallet proxy..");
+
+    let mut params = WalletProxySpawnParams::new(WALLET_NODE);
+    params.with_protocol(protocol.clone());
+    // free port for "auto" endpoint is already allocated in settings (see `VitSettings::use_free_ports`)
+    if endpoint != AUTO_ENDPOINT {
+        params.with_base_address(endpoint);
+    }
+
+    let logs = component_logs(controller, "wallet_proxy");
+    let wallet_proxy = vit_controller
+        .spawn_wallet_proxy_custom(controller, &mut params)
+        .chain_err(|| ErrorKind::ProxyStartupFailed(logs))?;
+
+    println!("Backend network is up");
+
+    Ok((nodes, vit_station, wallet_proxy))
+}
+
+/// Spawns nodes and vit station only. Wallets need to connect directly to
+/// wallet node and vit station (see `VitController::iapyx_wallet_from_secret_key_direct`)
+pub fn setup_network_without_proxy(
+    controller: &mut Controller,
+    vit_controller: &mut VitController,
+    vit_parameters: ValidVotePlanParameters,
+    vit_data_generator: &mut dyn ValidVotingTemplateGenerator,
+    vit_version: String,
+) -> Result<(Vec<NodeController>, VitStationController)> {
+    let nodes = spawn_nodes(controller, vit_controller)?;
+
+    println!("Spawning vit station..");
+
+    let logs = component_logs(controller, "vit_station");
+    let vit_station = vit_controller
+        .spawn_vit_station(controller, vit_parameters, vit_data_generator, vit_version)
+        .chain_err(|| ErrorKind::StationStartupFailed(logs))?;
+
+    Ok((nodes, vit_station))
+}
+
+fn spawn_nodes(
+    controller: &mut Controller,
+    vit_controller: &VitController,
+) -> Result<Vec<NodeController>> {
     println!("Spawning leader 1..");
 
     // bootstrap network
-    let leader_1 = controller.spawn_node_custom(
+    let leader_1 = spawn_node(
+        controller,
+        vit_controller,
+        LEADER_1,
         SpawnParams::new(LEADER_1)
             .leader()
             .persistence_mode(PersistenceMode::Persistent)
             .explorer(Explorer { enabled: true }),
     )?;
-    leader_1.wait_for_bootstrap()?;
     controller.monitor_nodes();
 
     println!("Spawning leader 2..");
 
     //start bft node 2
-    let leader_2 = controller.spawn_node(
+    let leader_2 = spawn_node(
+        controller,
+        vit_controller,
         LEADER_2,
-        LeadershipMode::Leader,
-        PersistenceMode::Persistent,
+        SpawnParams::new(LEADER_2)
+            .leader()
+            .persistence_mode(PersistenceMode::Persistent),
     )?;
-    leader_2.wait_for_bootstrap()?;
 
     println!("Spawning leader 3..");
 
     //start bft node 3
-    let leader_3 = controller.spawn_node(
+    let leader_3 = spawn_node(
+        controller,
+        vit_controller,
         LEADER_3,
-        LeadershipMode::Leader,
-        PersistenceMode::Persistent,
+        SpawnParams::new(LEADER_3)
+            .leader()
+            .persistence_mode(PersistenceMode::Persistent),
     )?;
-    leader_3.wait_for_bootstrap()?;
 
     println!("Spawning leader 4..");
 
     //start bft node 4
-    let leader_4 = controller.spawn_node(
+    let leader_4 = spawn_node(
+        controller,
+        vit_controller,
         LEADER_4,
-        LeadershipMode::Leader,
-        PersistenceMode::Persistent,
+        SpawnParams::new(LEADER_4)
+            .leader()
+            .persistence_mode(PersistenceMode::Persistent),
     )?;
-    leader_4.wait_for_bootstrap()?;
 
     println!("Spawning wallet node..");
 
     // start passive node
-    let wallet_node = controller.spawn_node_custom(
+    let persistent_log = controller.working_directory().path().join("persistent_log");
+    let wallet_node = spawn_node(
+        controller,
+        vit_controller,
+        WALLET_NODE,
         SpawnParams::new(WALLET_NODE)
             .passive()
             .persistence_mode(PersistenceMode::Persistent)
             .explorer(Explorer { enabled: true })
-            .persistent_fragment_log(controller.working_directory().path().join("persistent_log")),
+            .persistent_fragment_log(persistent_log),
     )?;
-    wallet_node.wait_for_bootstrap()?;
 
-    println!("Spawning vit station..");
+    Ok(vec![leader_1, leader_2, leader_3, leader_4, wallet_node])
+}
 
-    // start proxy and vit station
-    let vit_station = vit_controller.spawn_vit_station(
-        controller,
-        vit_parameters,
-        vit_data_generator,
-        vit_version,
-    )?;
-    let wallet_proxy = vit_controller.spawn_wallet_proxy_custom(
-        controller,
-        WalletProxySpawnParams::new(WALLET_NODE)
-            .with_base_address(endpoint)
-            .with_protocol(protocol.clone()),
-    )?;
+/// Spawns node and waits for its bootstrap. Any failure is tagged with node alias
+fn spawn_node(
+    controller: &mut Controller,
+    vit_controller: &VitController,
+    alias: &str,
+    params: &mut SpawnParams,
+) -> Result<NodeController> {
+    if let Some(log_level) = vit_controller.log_level(alias) {
+        params.log_level(
+            log_level.parse().map_err(|_| {
+                ErrorKind::InvalidLogLevel(alias.to_string(), log_level.to_string())
+            })?,
+        );
+    }
 
-    println!("Backend network is up");
+    let logs = component_logs(controller, alias);
+    let node = controller
+        .spawn_node_custom(params)
+        .chain_err(|| ErrorKind::NodeStartupFailed(alias.to_string(), logs.clone()))?;
+    node.wait_for_bootstrap()
+        .chain_err(|| ErrorKind::NodeStartupFailed(alias.to_string(), logs))?;
+    Ok(node)
+}
 
-    Ok((
-        vec![leader_1, leader_2, leader_3, leader_4, wallet_node],
-        vit_station,
-        wallet_proxy,
-    ))
+/// Location of component working directory, where its logs and configuration are stored
+fn component_logs(controller: &Controller, alias: &str) -> String {
+    controller
+        .working_directory()
+        .path()
+        .join(alias)
+        .to_string_lossy()
+        .to_string()
 }
 
 pub fn interactive_mode(
@@ -245,17 +320,20 @@ pub fn single_run(
         *state = State::Starting;
     }
 
-    let (mut vit_controller, mut controller, vit_parameters, version) =
+    let (mut vit_controller, mut controller, mut vit_parameters, version) =
         quick_setup.build(context)?;
     let (nodes_list, vit_station, wallet_proxy) = setup_network(
         &mut controller,
         &mut vit_controller,
-        vit_parameters,
+        vit_parameters.clone(),
         template_generator,
         endpoint,
         protocol,
-        version,
+        version.clone(),
     )?;
+    let mut network = SpawnedNetwork::new(controller, nodes_list, vit_station, wallet_proxy);
+    // votes may already be cast, so respawned station has to keep generated proposals
+    vit_controller.set_storage_policy(StoragePolicy::UpdateTimestamps);
 
     {
         let mut control_context = control_context.lock().unwrap();
@@ -264,6 +342,31 @@ pub fn single_run(
     }
 
     loop {
+        let vote_timestamps_update = control_context
+            .lock()
+            .unwrap()
+            .take_vote_timestamps_update();
+        if let Some(timestamps) = vote_timestamps_update {
+            println!("Regenerating vit station with new vote timestamps..");
+            vit_parameters.set_voting_start(timestamps.vote_start.timestamp());
+            vit_parameters.set_voting_tally_start(timestamps.tally_start.timestamp());
+            vit_parameters.set_voting_tally_end(timestamps.tally_end.timestamp());
+            if let Some(next_vote_start) = timestamps.next_vote_start {
+                vit_parameters.set_next_fund_start_time(next_vote_start.timestamp());
+            }
+            network.respawn_vit_station(|controller| {
+                let logs = component_logs(controller, "vit_station");
+                vit_controller
+                    .spawn_vit_station(
+                        controller,
+                        vit_parameters.clone(),
+                        template_generator,
+                        version.clone(),
+                    )
+                    .chain_err(|| ErrorKind::StationStartupFailed(logs))
+            })?;
+        }
+
         if control_context.lock().unwrap().request_to_stop() {
             {
                 let mut control_context = control_context.lock().unwrap();
@@ -271,13 +374,7 @@ pub fn single_run(
                 *state = State::Stopping;
             }
 
-            vit_station.shutdown();
-            wallet_proxy.shutdown();
-            for node in nodes_list {
-                node.shutdown()?;
-            }
-
-            controller.finalize();
+            network.shutdown()?;
             {
                 let mut control_context = control_context.lock().unwrap();
                 let state = control_context.state_mut();
@@ -0,0 +1,55 @@
+use crate::vit_station::VitStationController;
+use crate::wallet::WalletProxyController;
+use crate::Result;
+use jormungandr_scenario_tests::scenario::Controller;
+use std::fmt;
+use std::net::{SocketAddr, TcpListener};
+
+/// Endpoint value which makes vitup ask OS for free port instead of using fixed one.
+/// Allows to run many networks in parallel (e.g. in integration tests)
+pub const AUTO_ENDPOINT: &str = "auto";
+
+/// Asks OS for free local port. Port is released right away, so it can be reused
+/// by spawned service
+pub fn free_local_address() -> Result<SocketAddr> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    Ok(listener.local_addr()?)
+}
+
+/// Addresses of all services exposed by spawned backend
+#[derive(Debug, Clone)]
+pub struct BackendAddresses {
+    pub wallet_proxy: String,
+    pub vit_station: SocketAddr,
+    pub wallet_node_rest: Option<SocketAddr>,
+}
+
+impl BackendAddresses {
+    pub fn new(
+        controller: &Controller,
+        vit_station: &VitStationController,
+        wallet_proxy: &WalletProxyController,
+    ) -> Self {
+        Self {
+            wallet_proxy: wallet_proxy.settings().base_address().to_string(),
+            vit_station: vit_station.address(),
+            wallet_node_rest: controller
+                .settings()
+                .network_settings
+                .nodes
+                .get(crate::setup::start::quick::WALLET_NODE)
+                .map(|node| node.config().rest.listen),
+        }
+    }
+}
+
+impl fmt::Display for BackendAddresses {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "wallet proxy: {}", self.wallet_proxy)?;
+        write!(f, "vit station: {}", self.vit_station)?;
+        if let Some(wallet_node_rest) = self.wallet_node_rest {
+            write!(f, "\nwallet node rest: {}", wallet_node_rest)?;
+        }
+        Ok(())
+    }
+}
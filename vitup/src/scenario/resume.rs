@@ -0,0 +1,204 @@
+use crate::config::VitStartParameters;
+use crate::error::ErrorKind;
+use crate::Result;
+use chrono::NaiveDateTime;
+use jormungandr_lib::crypto::hash::Hash;
+use jormungandr_scenario_tests::scenario::Controller;
+use jormungandr_scenario_tests::Seed;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+pub const DEPLOYMENT_STATE: &str = "deployment.json";
+
+/// Description of already spawned deployment which allows to bring it up again
+/// with the same seed, parameters and artifacts (block0, vit station database, qr codes)
+/// instead of generating everything from scratch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeploymentState {
+    seed: String,
+    parameters: VitStartParameters,
+    checksums: BTreeMap<PathBuf, String>,
+}
+
+impl DeploymentState {
+    pub fn new(seed: &Seed, parameters: VitStartParameters) -> Self {
+        Self {
+            seed: seed.to_string(),
+            parameters,
+            checksums: BTreeMap::new(),
+        }
+    }
+
+    pub fn seed(&self) -> Result<Seed> {
+        self.seed
+            .parse()
+            .map_err(|_| ErrorKind::DeploymentStateCorrupted(self.seed.clone()).into())
+    }
+
+    pub fn parameters(&self) -> &VitStartParameters {
+        &self.parameters
+    }
+
+    pub fn set_parameters(&mut self, parameters: VitStartParameters) {
+        self.parameters = parameters;
+    }
+
+    /// Records checksums of given files. Paths are stored relative to deployment directory
+    pub fn track<P: AsRef<Path>>(
+        &mut self,
+        deployment_dir: P,
+        files: impl IntoIterator<Item = PathBuf>,
+    ) -> Result<()> {
+        let deployment_dir = deployment_dir.as_ref();
+        for file in files {
+            let relative = file
+                .strip_prefix(deployment_dir)
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|_| file.clone());
+            let checksum = checksum(deployment_dir.join(&relative))?;
+            self.checksums.insert(relative, checksum);
+        }
+        Ok(())
+    }
+
+    pub fn tracked_files(&self) -> impl Iterator<Item = &PathBuf> {
+        self.checksums.keys()
+    }
+
+    /// Checks that all tracked files are still present in deployment directory
+    /// and were not altered since deployment state was saved
+    pub fn verify<P: AsRef<Path>>(&self, deployment_dir: P) -> Result<()> {
+        let deployment_dir = deployment_dir.as_ref();
+        for (file, expected) in &self.checksums {
+            let path = deployment_dir.join(file);
+            if !path.exists() {
+                bail!(ErrorKind::DeploymentFileMissing(
+                    path.to_string_lossy().to_string()
+                ));
+            }
+            let actual = checksum(&path)?;
+            if actual != *expected {
+                bail!(ErrorKind::DeploymentChecksumMismatch(
+                    path.to_string_lossy().to_string(),
+                    expected.clone(),
+                    actual
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, deployment_dir: P) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        std::fs::write(deployment_dir.as_ref().join(DEPLOYMENT_STATE), content)?;
+        Ok(())
+    }
+
+    pub fn load<P: AsRef<Path>>(deployment_dir: P) -> Result<Self> {
+        let path = deployment_dir.as_ref().join(DEPLOYMENT_STATE);
+        if !path.exists() {
+            bail!(ErrorKind::DeploymentFileMissing(
+                path.to_string_lossy().to_string()
+            ));
+        }
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+}
+
+/// Parameters which reproduce the same genesis when deployment is rebuilt: block0 date
+/// and vote timestamps resolved during build are pinned instead of calculated from
+/// current time again
+pub fn pinned_parameters(
+    parameters: &VitStartParameters,
+    controller: &Controller,
+) -> VitStartParameters {
+    let block0_date = controller
+        .settings()
+        .network_settings
+        .block0
+        .blockchain_configuration
+        .block0_date;
+    let mut parameters = parameters.clone();
+    parameters.block0_date = Some(NaiveDateTime::from_timestamp(
+        block0_date.to_secs() as i64,
+        0,
+    ));
+    parameters
+}
+
+/// Keeps content of tracked files in memory, so they can be put back
+/// after controllers were rebuilt on top of existing deployment directory
+pub struct DeploymentSnapshot {
+    files: Vec<(PathBuf, Vec<u8>)>,
+}
+
+impl DeploymentSnapshot {
+    pub fn take<P: AsRef<Path>>(deployment_dir: P, state: &DeploymentState) -> Result<Self> {
+        let deployment_dir = deployment_dir.as_ref();
+        let mut files = Vec::new();
+        for file in state.tracked_files() {
+            let path = deployment_dir.join(file);
+            files.push((path.clone(), std::fs::read(path)?));
+        }
+        Ok(Self { files })
+    }
+
+    /// Checks that file regenerated by rebuilding controllers (e.g. block0) has the same
+    /// content as the saved one. Otherwise controller settings and vote plan parameters
+    /// would not match genesis served by restored deployment
+    pub fn verify_rebuilt<P: AsRef<Path>>(&self, file: P) -> Result<()> {
+        let file = file.as_ref();
+        let target = std::fs::canonicalize(file)?;
+        let saved = self
+            .files
+            .iter()
+            .find(|(path, _)| {
+                std::fs::canonicalize(path)
+                    .map(|path| path == target)
+                    .unwrap_or(false)
+            })
+            .map(|(_, content)| content)
+            .ok_or_else(|| ErrorKind::DeploymentFileMissing(file.to_string_lossy().to_string()))?;
+        if std::fs::read(file)? != *saved {
+            bail!(ErrorKind::DeploymentGenesisMismatch(
+                file.to_string_lossy().to_string()
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn restore(&self) -> Result<()> {
+        for (path, content) in &self.files {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(path, content)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn checksum<P: AsRef<Path>>(path: P) -> Result<String> {
+    let content = std::fs::read(path)?;
+    Ok(Hash::hash_bytes(&content).to_string())
+}
+
+/// Lists files which identify deployment: block0, vit station database and qr codes
+pub fn deployment_files<P: AsRef<Path>>(deployment_dir: P, block0: PathBuf) -> Vec<PathBuf> {
+    let deployment_dir = deployment_dir.as_ref();
+    let mut files = vec![block0];
+
+    let patterns = [
+        deployment_dir.join("*").join("storage.db"),
+        deployment_dir.join("qr-codes").join("*.png"),
+    ];
+
+    for pattern in patterns.iter() {
+        if let Ok(paths) = glob::glob(&pattern.to_string_lossy()) {
+            files.extend(paths.filter_map(|entry| entry.ok()));
+        }
+    }
+    files
+}
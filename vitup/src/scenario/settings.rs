@@ -1,4 +1,6 @@
+use crate::scenario::ports::free_local_address;
 use crate::vit_station::VitStationSettings;
+use crate::Result;
 use jormungandr_scenario_tests::scenario::Context;
 
 use jormungandr_testing_utils::testing::network_builder::WalletProxySettings;
@@ -59,6 +61,22 @@ impl VitSettings {
             wallet_proxies,
         }
     }
+
+    /// Replaces vit station and wallet proxy addresses with free ports allocated by OS
+    pub fn use_free_ports(&mut self) -> Result<()> {
+        for vit_station in self.vit_stations.values_mut() {
+            vit_station.address = free_local_address()?;
+        }
+
+        let vit_station_address = self.vit_stations.values().next().map(|x| x.address);
+        for wallet_proxy in self.wallet_proxies.values_mut() {
+            wallet_proxy.proxy_address = free_local_address()?;
+            if let Some(vit_station_address) = vit_station_address {
+                wallet_proxy.vit_station_address = vit_station_address;
+            }
+        }
+        Ok(())
+    }
 }
 
 impl PrepareVitServerSettings for VitStationSettings {
@@ -0,0 +1,123 @@
+use crate::error::ErrorKind;
+use crate::vit_station::VitStationController;
+use crate::wallet::WalletProxyController;
+use crate::Result;
+use jormungandr_scenario_tests::scenario::Controller;
+use jormungandr_scenario_tests::NodeController;
+
+/// Owns all processes of spawned backend network. Everything is shut down
+/// when guard goes out of scope, even if test panicked in the middle of scenario,
+/// so no child processes are left behind.
+pub struct SpawnedNetwork {
+    controller: Option<Controller>,
+    nodes: Vec<NodeController>,
+    vit_station: Option<VitStationController>,
+    wallet_proxy: Option<WalletProxyController>,
+}
+
+impl SpawnedNetwork {
+    pub fn new(
+        controller: Controller,
+        nodes: Vec<NodeController>,
+        vit_station: VitStationController,
+        wallet_proxy: WalletProxyController,
+    ) -> Self {
+        Self {
+            controller: Some(controller),
+            nodes,
+            vit_station: Some(vit_station),
+            wallet_proxy: Some(wallet_proxy),
+        }
+    }
+
+    pub fn controller(&self) -> &Controller {
+        self.controller.as_ref().unwrap()
+    }
+
+    pub fn controller_mut(&mut self) -> &mut Controller {
+        self.controller.as_mut().unwrap()
+    }
+
+    pub fn nodes(&self) -> &[NodeController] {
+        &self.nodes
+    }
+
+    pub fn node(&self, alias: &str) -> Option<&NodeController> {
+        self.nodes.iter().find(|node| node.alias() == alias)
+    }
+
+    /// Full content of node log captured since node was spawned
+    pub fn node_logs(&self, alias: &str) -> Result<String> {
+        self.node(alias)
+            .map(|node| node.logger().get_log_content())
+            .ok_or_else(|| ErrorKind::NodeNotFound(alias.to_string()).into())
+    }
+
+    /// Node log lines which contains given text, e.g. fragment id
+    pub fn node_log_lines_containing(&self, alias: &str, text: &str) -> Result<Vec<String>> {
+        Ok(self
+            .node_logs(alias)?
+            .lines()
+            .filter(|line| line.contains(text))
+            .map(|line| line.to_string())
+            .collect())
+    }
+
+    pub fn vit_station(&self) -> &VitStationController {
+        self.vit_station.as_ref().unwrap()
+    }
+
+    /// Shuts down current vit station and puts spawned one in its place. New station
+    /// is created by `spawn` only after old one released its resources (port, database)
+    pub fn respawn_vit_station<F>(&mut self, spawn: F) -> Result<()>
+    where
+        F: FnOnce(&mut Controller) -> Result<VitStationController>,
+    {
+        if let Some(vit_station) = self.vit_station.take() {
+            vit_station.shutdown();
+        }
+        let vit_station = spawn(self.controller_mut())?;
+        self.vit_station = Some(vit_station);
+        Ok(())
+    }
+
+    pub fn wallet_proxy(&self) -> &WalletProxyController {
+        self.wallet_proxy.as_ref().unwrap()
+    }
+
+    /// Shuts down network explicitly and reports first node which failed to stop.
+    /// Dropping guard does the same but ignores errors
+    pub fn shutdown(mut self) -> Result<()> {
+        self.teardown()
+    }
+
+    fn teardown(&mut self) -> Result<()> {
+        if let Some(wallet_proxy) = self.wallet_proxy.take() {
+            wallet_proxy.shutdown();
+        }
+
+        if let Some(vit_station) = self.vit_station.take() {
+            vit_station.shutdown();
+        }
+
+        let mut result = Ok(());
+        for node in self.nodes.drain(..) {
+            if let Err(err) = node.shutdown() {
+                if result.is_ok() {
+                    result = Err(err.into());
+                }
+            }
+        }
+
+        if let Some(controller) = self.controller.take() {
+            controller.finalize();
+        }
+        result
+    }
+}
+
+impl Drop for SpawnedNetwork {
+    fn drop(&mut self) {
+        let _ = self.teardown();
+    }
+}
@@ -0,0 +1,88 @@
+use crate::error::ErrorKind;
+use crate::scenario::committee::CommitteeMembers;
+use crate::Result;
+use chain_impl_mockchain::key::Hash;
+use jormungandr_lib::interfaces::VotePlanStatus;
+use jormungandr_scenario_tests::scenario::Controller;
+use jormungandr_scenario_tests::NodeController;
+use std::str::FromStr;
+
+/// Tallies vote plan using committee wallet and returns vote plan status after tally.
+/// For private vote plans encrypted tally is sent first, then decryption shares are
+/// calculated from committee data and private tally is sent. Needs to be called when
+/// vote plan is in tally phase. Committee generated by vitup (with seed or threshold)
+/// is not known to settings, use `private_tally_vote_plan` for it
+pub fn tally_vote_plan(
+    controller: &Controller,
+    committee: &str,
+    vote_plan: &str,
+    node: &NodeController,
+) -> Result<VotePlanStatus> {
+    let mut committee = controller.wallet(committee)?;
+    let vote_plan_def = controller.vote_plan(vote_plan)?;
+    let vote_plan_id = vote_plan_def.id();
+    let fragment_sender = controller.fragment_sender();
+
+    match controller.settings().private_vote_plans.get(vote_plan) {
+        None => {
+            fragment_sender.send_public_vote_tally(
+                &mut committee,
+                &vote_plan_def.clone().into(),
+                node,
+            )?;
+        }
+        Some(private_vote_plan) => {
+            fragment_sender.send_encrypted_tally(
+                &mut committee,
+                &vote_plan_def.clone().into(),
+                node,
+            )?;
+            let vote_plan_status = vote_plan_status(node, &vote_plan_id)?;
+            let shares = private_vote_plan.decrypt_tally(&vote_plan_status.into());
+            fragment_sender.send_private_vote_tally(
+                &mut committee,
+                &vote_plan_def.clone().into(),
+                shares,
+                node,
+            )?;
+        }
+    }
+
+    vote_plan_status(node, &vote_plan_id)
+}
+
+fn vote_plan_status(node: &NodeController, vote_plan_id: &str) -> Result<VotePlanStatus> {
+    let id = Hash::from_str(vote_plan_id)
+        .map_err(|_| ErrorKind::VotePlanNotFound(vote_plan_id.to_string()))?;
+    node.vote_plans()?
+        .into_iter()
+        .find(|vote_plan| vote_plan.id == id.into())
+        .ok_or_else(|| ErrorKind::VotePlanNotFound(vote_plan_id.to_string()).into())
+}
+
+/// Private tally where decryption shares come from given committee members
+/// instead of committee data kept in controller settings
+pub fn private_tally_vote_plan(
+    controller: &Controller,
+    committee: &str,
+    vote_plan: &str,
+    members: &CommitteeMembers,
+    node: &NodeController,
+) -> Result<VotePlanStatus> {
+    let mut committee = controller.wallet(committee)?;
+    let vote_plan_def = controller.vote_plan(vote_plan)?;
+    let vote_plan_id = vote_plan_def.id();
+    let fragment_sender = controller.fragment_sender();
+
+    fragment_sender.send_encrypted_tally(&mut committee, &vote_plan_def.clone().into(), node)?;
+    let vote_plan_status = vote_plan_status(node, &vote_plan_id)?;
+    let shares = members.decryption_shares(&vote_plan_status.into())?;
+    fragment_sender.send_private_vote_tally(
+        &mut committee,
+        &vote_plan_def.clone().into(),
+        shares,
+        node,
+    )?;
+
+    vote_plan_status(node, &vote_plan_id)
+}
@@ -1,6 +1,8 @@
 #![allow(dead_code)]
 
 use super::DbGenerator;
+use diesel::sql_types::BigInt;
+use diesel::{Connection, RunQueryDsl, SqliteConnection};
 use jormungandr_scenario_tests::{
     node::{ProgressBarController, Status},
     style, Context,
@@ -66,6 +68,12 @@ pub enum Error {
         #[debug(skip)]
         logs: Vec<String>,
     },
+    #[error("cannot open vit station database")]
+    DbConnection(#[from] diesel::ConnectionError),
+    #[error("cannot update vit station database")]
+    DbQuery(#[from] diesel::result::Error),
+    #[error("vote plan parameters do not define {0}")]
+    MissingTimestamp(&'static str),
 }
 
 /// send query to a running node
@@ -90,7 +98,75 @@ pub struct VitStation {
 }
 
 const VIT_CONFIG: &str = "vit_config.yaml";
-const STORAGE: &str = "storage.db";
+pub const STORAGE: &str = "storage.db";
+
+/// What happens with database already present in vit station directory when station
+/// is spawned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StoragePolicy {
+    /// database is always generated from current vote plan parameters
+    Regenerate,
+    /// existing database is kept (e.g. when resuming deployment), new one is generated
+    /// only if missing
+    Reuse,
+    /// existing database is kept, only fund and vote plan timestamps are rewritten from
+    /// current vote plan parameters. Proposals, challenges and funds stay the same, so
+    /// votes already cast still refer to existing proposals
+    UpdateTimestamps,
+}
+
+impl Default for StoragePolicy {
+    fn default() -> Self {
+        Self::Regenerate
+    }
+}
+
+/// Generates vit station database according to policy
+pub fn prepare_storage(
+    db_file: &Path,
+    parameters: ValidVotePlanParameters,
+    template_generator: &mut dyn ValidVotingTemplateGenerator,
+    policy: StoragePolicy,
+) -> Result<()> {
+    if db_file.exists() {
+        match policy {
+            StoragePolicy::Reuse => return Ok(()),
+            StoragePolicy::UpdateTimestamps => return update_timestamps(db_file, &parameters),
+            StoragePolicy::Regenerate => std::fs::remove_file(db_file)?,
+        }
+    }
+    DbGenerator::new(parameters).build(db_file, template_generator);
+    Ok(())
+}
+
+/// Rewrites fund and vote plan timestamps of existing database
+fn update_timestamps(db_file: &Path, parameters: &ValidVotePlanParameters) -> Result<()> {
+    let required = |timestamp: Option<i64>, name| timestamp.ok_or(Error::MissingTimestamp(name));
+    let voting_start = required(parameters.voting_start, "voting start")?;
+    let tally_start = required(parameters.voting_tally_start, "voting tally start")?;
+    let tally_end = required(parameters.voting_tally_end, "voting tally end")?;
+    let next_fund_start = required(parameters.next_fund_start_time, "next fund start")?;
+
+    let connection = SqliteConnection::establish(&db_file.to_string_lossy())?;
+    connection.transaction::<_, Error, _>(|| {
+        diesel::sql_query(
+            "UPDATE funds SET fund_start_time = ?, fund_end_time = ?, next_fund_start_time = ?",
+        )
+        .bind::<BigInt, _>(voting_start)
+        .bind::<BigInt, _>(tally_start)
+        .bind::<BigInt, _>(next_fund_start)
+        .execute(&connection)?;
+        diesel::sql_query(
+            "UPDATE voteplans SET chain_vote_start_time = ?, chain_vote_end_time = ?, chain_committee_end_time = ?",
+        )
+        .bind::<BigInt, _>(voting_start)
+        .bind::<BigInt, _>(tally_start)
+        .bind::<BigInt, _>(tally_end)
+        .execute(&connection)?;
+        Ok(())
+    })
+}
+
 const VIT_STATION_LOG: &str = "vit_station.log";
 
 impl VitStationController {
@@ -181,6 +257,7 @@ impl VitStation {
         block0: &Path,
         working_dir: &Path,
         version: &str,
+        storage_policy: StoragePolicy,
     ) -> Result<Self> {
         let dir = working_dir.join(alias);
         std::fs::DirBuilder::new().recursive(true).create(&dir)?;
@@ -195,7 +272,7 @@ impl VitStation {
         let db_file = dir.join(STORAGE);
         dump_settings_to_file(&config_file.to_str().unwrap(), &settings).unwrap();
 
-        DbGenerator::new(parameters).build(&db_file, template_generator);
+        prepare_storage(&db_file, parameters, template_generator, storage_policy)?;
 
         let mut command_builder =
             BootstrapCommandBuilder::new(PathBuf::from("vit-servicing-station-server"));
@@ -286,3 +363,115 @@ impl VitStation {
         *self.status.lock().unwrap() = status
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chain_impl_mockchain::testing::scenario::template::{
+        ProposalDefBuilder, VotePlanDefBuilder,
+    };
+    use chain_impl_mockchain::testing::VoteTestGen;
+    use vit_servicing_station_lib::db::{
+        load_db_connection_pool,
+        queries::{funds::query_fund, proposals::query_all_proposals},
+    };
+    use vit_servicing_station_tests::common::data::ArbitraryValidVotingTemplateGenerator;
+
+    fn parameters(voting_start: i64) -> ValidVotePlanParameters {
+        let mut proposal = ProposalDefBuilder::new(VoteTestGen::external_proposal_id());
+        proposal.options(3);
+        proposal.action_off_chain();
+        let mut vote_plan = VotePlanDefBuilder::new("fund_3");
+        vote_plan.owner("committee_1");
+        vote_plan.vote_phases(1, 2, 3);
+        vote_plan.with_proposal(&mut proposal);
+
+        let mut parameters =
+            ValidVotePlanParameters::new(vec![vote_plan.build()], "fund_3".to_string());
+        parameters.set_voting_start(voting_start);
+        parameters.set_voting_tally_start(voting_start + 100);
+        parameters.set_voting_tally_end(voting_start + 200);
+        parameters.set_next_fund_start_time(voting_start + 300);
+        parameters
+    }
+
+    fn proposal_ids(db_file: &Path) -> Vec<String> {
+        let pool = load_db_connection_pool(db_file.to_str().unwrap()).unwrap();
+        tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(query_all_proposals(&pool))
+            .unwrap()
+            .into_iter()
+            .map(|proposal| proposal.proposal.proposal_id)
+            .collect()
+    }
+
+    fn fund_start_time(db_file: &Path) -> i64 {
+        let pool = load_db_connection_pool(db_file.to_str().unwrap()).unwrap();
+        tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(query_fund(&pool))
+            .unwrap()
+            .fund_start_time
+    }
+
+    #[test]
+    fn regenerated_storage_contains_new_vote_timestamps() {
+        let dir = tempdir::TempDir::new("vit_station_storage").unwrap();
+        let db_file = dir.path().join(STORAGE);
+        let mut generator = ArbitraryValidVotingTemplateGenerator::new();
+
+        prepare_storage(
+            &db_file,
+            parameters(1000),
+            &mut generator,
+            StoragePolicy::Regenerate,
+        )
+        .unwrap();
+        assert_eq!(fund_start_time(&db_file), 1000);
+
+        prepare_storage(
+            &db_file,
+            parameters(5000),
+            &mut generator,
+            StoragePolicy::Regenerate,
+        )
+        .unwrap();
+        assert_eq!(fund_start_time(&db_file), 5000);
+
+        prepare_storage(
+            &db_file,
+            parameters(9000),
+            &mut generator,
+            StoragePolicy::Reuse,
+        )
+        .unwrap();
+        assert_eq!(fund_start_time(&db_file), 5000);
+    }
+
+    #[test]
+    fn updated_storage_keeps_proposals() {
+        let dir = tempdir::TempDir::new("vit_station_storage").unwrap();
+        let db_file = dir.path().join(STORAGE);
+        let mut generator = ArbitraryValidVotingTemplateGenerator::new();
+
+        prepare_storage(
+            &db_file,
+            parameters(1000),
+            &mut generator,
+            StoragePolicy::Regenerate,
+        )
+        .unwrap();
+        let proposals = proposal_ids(&db_file);
+
+        prepare_storage(
+            &db_file,
+            parameters(5000),
+            &mut generator,
+            StoragePolicy::UpdateTimestamps,
+        )
+        .unwrap();
+        assert_eq!(fund_start_time(&db_file), 5000);
+        assert_eq!(proposal_ids(&db_file), proposals);
+    }
+}
@@ -1,7 +1,10 @@
 mod controller;
 mod data;
+mod template;
 
 pub use controller::{
-    Error as VitStationControllerError, VitStation, VitStationController, VitStationSettings,
+    prepare_storage, Error as VitStationControllerError, StoragePolicy, VitStation,
+    VitStationController, VitStationSettings, STORAGE,
 };
 pub use data::DbGenerator;
+pub use template::SeededValidVotingTemplateGenerator;
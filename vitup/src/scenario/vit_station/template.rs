@@ -0,0 +1,67 @@
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaChaRng;
+use std::collections::{HashMap, HashSet};
+use vit_servicing_station_tests::common::data::{
+    ArbitraryValidVotingTemplateGenerator, ChallengeTemplate, FundTemplate, ProposalTemplate,
+    ValidVotingTemplateGenerator,
+};
+
+/// Wraps arbitrary template generator, but assigns proposal and challenge ids
+/// from rng seeded with given value, so they are the same on each run
+pub struct SeededValidVotingTemplateGenerator {
+    inner: ArbitraryValidVotingTemplateGenerator,
+    seed: u64,
+    rng: ChaChaRng,
+    used_ids: HashSet<u32>,
+    challenge_ids: HashMap<String, String>,
+}
+
+impl SeededValidVotingTemplateGenerator {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            inner: ArbitraryValidVotingTemplateGenerator::new(),
+            seed,
+            rng: ChaChaRng::seed_from_u64(seed),
+            used_ids: HashSet::new(),
+            challenge_ids: HashMap::new(),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    fn next_id(&mut self) -> String {
+        loop {
+            let id = self.rng.gen_range(1..u32::MAX);
+            if self.used_ids.insert(id) {
+                return id.to_string();
+            }
+        }
+    }
+}
+
+impl ValidVotingTemplateGenerator for SeededValidVotingTemplateGenerator {
+    fn next_proposal(&mut self) -> ProposalTemplate {
+        let mut proposal = self.inner.next_proposal();
+        proposal.proposal_id = self.next_id();
+        if let Some(challenge_id) = &proposal.challenge_id {
+            if let Some(new_id) = self.challenge_ids.get(challenge_id) {
+                proposal.challenge_id = Some(new_id.clone());
+            }
+        }
+        proposal
+    }
+
+    fn next_challenge(&mut self) -> ChallengeTemplate {
+        let mut challenge = self.inner.next_challenge();
+        let id = self.next_id();
+        self.challenge_ids.insert(challenge.id.clone(), id.clone());
+        challenge.id = id;
+        challenge
+    }
+
+    fn next_fund(&mut self) -> FundTemplate {
+        self.inner.next_fund()
+    }
+}
@@ -0,0 +1,23 @@
+use crate::error::ErrorKind;
+use crate::Result;
+use iapyx::utils::block0::block0_hash_from_file;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+/// Prints hash of block0, which wallets need to match
+#[derive(StructOpt, Debug)]
+#[structopt(setting = structopt::clap::AppSettings::ColoredHelp)]
+pub struct Block0HashCommand {
+    /// block0 binary
+    #[structopt(long = "block0")]
+    pub block0: PathBuf,
+}
+
+impl Block0HashCommand {
+    pub fn exec(self) -> Result<()> {
+        let hash = block0_hash_from_file(&self.block0)
+            .map_err(|err| ErrorKind::InvalidBlock0(err.to_string()))?;
+        println!("{}", hash);
+        Ok(())
+    }
+}
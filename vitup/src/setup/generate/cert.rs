@@ -0,0 +1,55 @@
+use crate::error::ErrorKind;
+use crate::Result;
+use rcgen::{Certificate, CertificateParams, SanType};
+use std::net::{IpAddr, Ipv4Addr};
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+/// Generates self-signed certificate and key for `localhost` and `127.0.0.1`,
+/// which can be passed to wallet proxy `--cert` and `--key` parameters
+#[derive(StructOpt, Debug)]
+#[structopt(setting = structopt::clap::AppSettings::ColoredHelp)]
+pub struct CertCommandArgs {
+    /// output path of certificate in PEM format
+    #[structopt(long = "cert", default_value = "./cert.pem")]
+    pub cert_path: PathBuf,
+
+    /// output path of private key in PEM format
+    #[structopt(long = "key", default_value = "./key.pem")]
+    pub key_path: PathBuf,
+
+    /// additional host names for which certificate is valid
+    #[structopt(long = "host")]
+    pub hosts: Vec<String>,
+}
+
+impl CertCommandArgs {
+    pub fn exec(self) -> Result<()> {
+        let (cert, key) = self_signed_certificate(&self.hosts)?;
+        std::fs::write(&self.cert_path, cert)?;
+        std::fs::write(&self.key_path, key)?;
+        println!(
+            "certificate saved to {:?}, key saved to {:?}",
+            self.cert_path, self.key_path
+        );
+        Ok(())
+    }
+}
+
+/// Returns certificate and private key (both PEM encoded)
+pub fn self_signed_certificate(hosts: &[String]) -> Result<(String, String)> {
+    let mut names = vec!["localhost".to_string()];
+    names.extend(hosts.iter().cloned());
+
+    let mut params = CertificateParams::new(names);
+    params
+        .subject_alt_names
+        .push(SanType::IpAddress(IpAddr::V4(Ipv4Addr::LOCALHOST)));
+
+    let certificate = Certificate::from_params(params)
+        .map_err(|err| ErrorKind::CertificateGenerationFailed(err.to_string()))?;
+    let cert = certificate
+        .serialize_pem()
+        .map_err(|err| ErrorKind::CertificateGenerationFailed(err.to_string()))?;
+    Ok((cert, certificate.serialize_private_key_pem()))
+}
@@ -0,0 +1,131 @@
+//! Converts proposals, challenges and funds defined in csv files into json format
+//! accepted by `ExternalValidVotingTemplateGenerator`. First row of each file is a header
+//! which should contain below columns (in any order):
+//!
+//! proposals: category_name, chain_vote_options, chain_vote_type, challenge_id, challenge_type,
+//!     internal_id, proposal_funds, proposal_id, proposal_impact_score, proposal_summary,
+//!     proposal_title, proposal_url, proposer_email, proposer_name
+//!     (optional: proposal_brief, proposal_goal, proposal_importance, proposal_metrics,
+//!     proposal_solution, proposer_relevant_experience, proposer_url)
+//! challenges: challenge_type, challenge_url, description, fund_id, id, proposers_rewards,
+//!     rewards_total, title
+//! funds: goal, id, rewards_info, threshold
+//!
+//! Empty cells in optional columns are skipped. `chain_vote_options` uses the same
+//! comma separated format as json (e.g. "blank,yes,no"), so the cell needs to be quoted.
+use crate::error::ErrorKind;
+use crate::setup::validate::{
+    validate_templates, CHALLENGE_REQUIRED_FIELDS, FUND_NUMERIC_FIELDS, FUND_REQUIRED_FIELDS,
+    PROPOSAL_REQUIRED_FIELDS,
+};
+use crate::Result;
+use serde_json::{Map, Value};
+use std::path::{Path, PathBuf};
+use vit_servicing_station_tests::common::data::ExternalValidVotingTemplateGenerator;
+
+/// Returns path to json file. If input is a csv file it is converted
+/// and saved in output directory first
+pub fn json_or_converted_csv<P: AsRef<Path>, Q: AsRef<Path>>(
+    input: P,
+    output_directory: Q,
+    required_columns: &[&str],
+    numeric_columns: &[&str],
+) -> Result<PathBuf> {
+    let input = input.as_ref();
+    if !is_csv(input) {
+        return Ok(input.to_path_buf());
+    }
+    let output = output_directory
+        .as_ref()
+        .join(input.file_stem().unwrap_or_default())
+        .with_extension("json");
+    let content = csv_to_json(input, required_columns, numeric_columns)?;
+    std::fs::write(&output, serde_json::to_string_pretty(&content)?)?;
+    Ok(output)
+}
+
+/// Builds template generator from proposals, challenges and funds,
+/// each of which can be either json or csv file
+pub fn template_generator_from_csv<P: AsRef<Path>>(
+    proposals: P,
+    challenges: P,
+    funds: P,
+    output_directory: &Path,
+) -> Result<ExternalValidVotingTemplateGenerator> {
+    let proposals =
+        json_or_converted_csv(proposals, output_directory, PROPOSAL_REQUIRED_FIELDS, &[])?;
+    let challenges =
+        json_or_converted_csv(challenges, output_directory, CHALLENGE_REQUIRED_FIELDS, &[])?;
+    let funds = json_or_converted_csv(
+        funds,
+        output_directory,
+        FUND_REQUIRED_FIELDS,
+        FUND_NUMERIC_FIELDS,
+    )?;
+    validate_templates(&proposals, &challenges, &funds)?;
+    ExternalValidVotingTemplateGenerator::new(proposals, challenges, funds).map_err(Into::into)
+}
+
+fn is_csv(path: &Path) -> bool {
+    path.extension()
+        .map(|extension| extension.eq_ignore_ascii_case("csv"))
+        .unwrap_or(false)
+}
+
+fn csv_to_json(input: &Path, required_columns: &[&str], numeric_columns: &[&str]) -> Result<Value> {
+    let file = input.to_string_lossy().to_string();
+    let mut reader = csv::Reader::from_path(input)?;
+    let headers = reader.headers()?.clone();
+
+    let missing_columns: Vec<String> = required_columns
+        .iter()
+        .filter(|column| !headers.iter().any(|header| header.trim() == **column))
+        .map(|column| column.to_string())
+        .collect();
+
+    if !missing_columns.is_empty() {
+        return Err(ErrorKind::CsvMissingColumns(file, missing_columns).into());
+    }
+
+    let mut rows = Vec::new();
+    for (idx, record) in reader.records().enumerate() {
+        // header is the first line
+        let row = idx + 2;
+        let record =
+            record.map_err(|e| ErrorKind::CsvRowParseError(file.clone(), row, e.to_string()))?;
+
+        let mut entry = Map::new();
+        for (header, cell) in headers.iter().zip(record.iter()) {
+            let header = header.trim();
+            let cell = cell.trim();
+
+            if cell.is_empty() {
+                if required_columns.contains(&header) {
+                    return Err(ErrorKind::CsvRowParseError(
+                        file,
+                        row,
+                        format!("empty value in required column '{}'", header),
+                    )
+                    .into());
+                }
+                continue;
+            }
+
+            let value = if numeric_columns.contains(&header) {
+                let number: i64 = cell.parse().map_err(|_| {
+                    ErrorKind::CsvRowParseError(
+                        file.clone(),
+                        row,
+                        format!("'{}' is not a number in column '{}'", cell, header),
+                    )
+                })?;
+                Value::from(number)
+            } else {
+                Value::String(cell.to_string())
+            };
+            entry.insert(header.to_string(), value);
+        }
+        rows.push(Value::Object(entry));
+    }
+    Ok(Value::Array(rows))
+}
@@ -1,11 +1,13 @@
-use super::{encode, read_config, read_genesis_yaml, read_initials, write_genesis_yaml};
+use super::{
+    encode, read_config, read_genesis_yaml, read_initials, template_generator_from_csv,
+    write_genesis_yaml,
+};
 use crate::setup::start::QuickVitBackendSettingsBuilder;
 use crate::Result;
 use jormungandr_scenario_tests::ProgressBarMode as ScenarioProgressBarMode;
 use jormungandr_scenario_tests::{Context, Seed};
 use std::path::PathBuf;
 use structopt::StructOpt;
-use vit_servicing_station_tests::common::data::ExternalValidVotingTemplateGenerator;
 
 #[derive(StructOpt, Debug)]
 #[structopt(setting = structopt::clap::AppSettings::ColoredHelp)]
@@ -18,21 +20,21 @@ pub struct ExternalDataCommandArgs {
     #[structopt(long = "config")]
     pub config: PathBuf,
 
-    /// proposals import json
+    /// proposals import json or csv
     #[structopt(
         long = "proposals",
         default_value = "../resources/external/proposals.json"
     )]
     pub proposals: PathBuf,
 
-    /// challenges import json
+    /// challenges import json or csv
     #[structopt(
         long = "challenges",
         default_value = "../resources/external/challenges.json"
     )]
     pub challenges: PathBuf,
 
-    /// funds import json
+    /// funds import json or csv
     #[structopt(long = "funds", default_value = "../resources/external/funds.json")]
     pub funds: PathBuf,
 
@@ -75,9 +77,12 @@ impl ExternalDataCommandArgs {
         let (vit_controller, mut controller, vit_parameters, version) =
             quick_setup.build(context)?;
 
-        let mut template_generator =
-            ExternalValidVotingTemplateGenerator::new(self.proposals, self.challenges, self.funds)
-                .unwrap();
+        let mut template_generator = template_generator_from_csv(
+            &self.proposals,
+            &self.challenges,
+            &self.funds,
+            &self.output_directory,
+        )?;
 
         // generate vit station data
         let vit_station = vit_controller.spawn_vit_station(
@@ -111,39 +116,12 @@ impl ExternalDataCommandArgs {
         }
 
         write_genesis_yaml(block0_configuration, &genesis)?;
-        println!("genesis.yaml: {:?}", std::fs::canonicalize(&genesis)?);
         encode(&genesis, &block0)?;
-        println!("block0: {:?}", std::fs::canonicalize(&block0)?);
-
-        println!("Fund id: {}", quick_setup.parameters().fund_id);
-        println!(
-            "voteplan ids: {:?}",
-            controller
-                .vote_plans()
-                .iter()
-                .map(|x| x.id())
-                .collect::<Vec<String>>()
-        );
-        println!(
-            "vote start timestamp: {:?}",
-            quick_setup.parameters().vote_start_timestamp
-        );
-        println!(
-            "tally start timestamp: {:?}",
-            quick_setup.parameters().tally_start_timestamp
-        );
-        println!(
-            "tally end timestamp: {:?}",
-            quick_setup.parameters().tally_end_timestamp
-        );
-        println!(
-            "next vote start time: {:?}",
-            quick_setup.parameters().next_vote_start_time
-        );
-        println!(
-            "refresh timestamp: {:?}",
-            quick_setup.parameters().refresh_time
-        );
+
+        let mut report = quick_setup.report(&controller);
+        report.genesis = Some(std::fs::canonicalize(&genesis)?);
+        report.block0 = std::fs::canonicalize(&block0)?;
+        println!("{}", report);
         Ok(())
     }
 }
@@ -1,18 +1,21 @@
+mod csv_template;
 mod external;
 mod perf;
 mod random;
 
+pub use csv_template::{json_or_converted_csv, template_generator_from_csv};
 pub use external::ExternalDataCommandArgs;
 pub use jormungandr_lib::interfaces::Initial;
 pub use perf::PerfDataCommandArgs;
 pub use random::RandomDataCommandArgs;
 
-use crate::config::DataGenerationConfig;
+use crate::config::{read_snapshot, snapshot_total_value, DataGenerationConfig};
+use crate::error::ErrorKind;
 use crate::Result;
 use chain_core::property::Block;
 use chain_core::property::Serialize;
 use chain_impl_mockchain::ledger::Ledger;
-use jormungandr_lib::interfaces::Block0Configuration;
+use jormungandr_lib::interfaces::{Block0Configuration, InitialUTxO};
 use std::path::Path;
 
 use structopt::StructOpt;
@@ -54,6 +57,29 @@ pub fn read_initials<P: AsRef<Path>>(initials: P) -> Result<Vec<Initial>> {
     serde_json::from_str(&initial).map_err(Into::into)
 }
 
+/// Maximum number of outputs of single fund entry in genesis
+const MAX_FUND_ENTRIES: usize = 255;
+
+/// Fund entries of genesis built from snapshot file (see `read_snapshot` for format).
+/// Unlike `read_initials`, which reads `initial` section of genesis-like file
+pub fn read_initials_from_snapshot<P: AsRef<Path>>(snapshot: P) -> Result<Vec<Initial>> {
+    let entries = read_snapshot(snapshot)?;
+    snapshot_total_value(&entries)?;
+    let mut utxos = Vec::new();
+    for entry in entries {
+        utxos.push(InitialUTxO {
+            address: entry.address.parse().map_err(|_| {
+                ErrorKind::InvalidSnapshot(format!("invalid address '{}'", entry.address))
+            })?,
+            value: entry.value.into(),
+        });
+    }
+    Ok(utxos
+        .chunks(MAX_FUND_ENTRIES)
+        .map(|chunk| Initial::Fund(chunk.to_vec()))
+        .collect())
+}
+
 pub fn write_genesis_yaml<P: AsRef<Path>>(genesis: Block0Configuration, path: P) -> Result<()> {
     use std::io::Write;
     let content = serde_yaml::to_string(&genesis)?;
@@ -120,38 +120,12 @@ impl PerfDataCommandArgs {
         self.split_secrets(root_directory)?;
 
         write_genesis_yaml(block0_configuration, &genesis)?;
-        println!("genesis.yaml: {:?}", std::fs::canonicalize(&genesis)?);
         encode(&genesis, &block0)?;
-        println!("block0: {:?}", std::fs::canonicalize(&block0)?);
-        println!("Fund id: {}", quick_setup.parameters().fund_id);
-        println!(
-            "voteplan ids: {:?}",
-            controller
-                .vote_plans()
-                .iter()
-                .map(|x| x.id())
-                .collect::<Vec<String>>()
-        );
-        println!(
-            "vote start timestamp: {:?}",
-            quick_setup.parameters().vote_start_timestamp
-        );
-        println!(
-            "tally start timestamp: {:?}",
-            quick_setup.parameters().tally_start_timestamp
-        );
-        println!(
-            "tally end timestamp: {:?}",
-            quick_setup.parameters().tally_end_timestamp
-        );
-        println!(
-            "next vote start time: {:?}",
-            quick_setup.parameters().next_vote_start_time
-        );
-        println!(
-            "refresh timestamp: {:?}",
-            quick_setup.parameters().refresh_time
-        );
+
+        let mut report = quick_setup.report(&controller);
+        report.genesis = Some(std::fs::canonicalize(&genesis)?);
+        report.block0 = std::fs::canonicalize(&block0)?;
+        println!("{}", report);
         Ok(())
     }
 
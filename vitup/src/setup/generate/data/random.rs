@@ -1,4 +1,5 @@
 use crate::setup::start::QuickVitBackendSettingsBuilder;
+use crate::vit_station::SeededValidVotingTemplateGenerator;
 use crate::Result;
 
 use super::{encode, read_config, read_genesis_yaml, write_genesis_yaml};
@@ -6,7 +7,9 @@ use jormungandr_scenario_tests::ProgressBarMode as ScenarioProgressBarMode;
 use jormungandr_scenario_tests::{Context, Seed};
 use std::path::PathBuf;
 use structopt::StructOpt;
-use vit_servicing_station_tests::common::data::ArbitraryValidVotingTemplateGenerator;
+use vit_servicing_station_tests::common::data::{
+    ArbitraryValidVotingTemplateGenerator, ValidVotingTemplateGenerator,
+};
 #[derive(StructOpt, Debug)]
 #[structopt(setting = structopt::clap::AppSettings::ColoredHelp)]
 pub struct RandomDataCommandArgs {
@@ -17,6 +20,10 @@ pub struct RandomDataCommandArgs {
     /// how many qr to generate
     #[structopt(long = "config")]
     pub config: PathBuf,
+
+    /// seed for proposal and challenge ids. If set, ids are the same on each run
+    #[structopt(long = "seed")]
+    pub seed: Option<u64>,
 }
 
 impl RandomDataCommandArgs {
@@ -48,13 +55,19 @@ impl RandomDataCommandArgs {
 
         let (vit_controller, mut controller, vit_parameters, version) =
             quick_setup.build(context)?;
-        let mut template_generator = ArbitraryValidVotingTemplateGenerator::new();
+        let mut template_generator: Box<dyn ValidVotingTemplateGenerator> = match self.seed {
+            Some(seed) => {
+                println!("proposal ids seed: {}", seed);
+                Box::new(SeededValidVotingTemplateGenerator::new(seed))
+            }
+            None => Box::new(ArbitraryValidVotingTemplateGenerator::new()),
+        };
 
         // generate vit station data
         let vit_station = vit_controller.spawn_vit_station(
             &mut controller,
             vit_parameters,
-            &mut template_generator,
+            template_generator.as_mut(),
             version,
         )?;
         vit_station.shutdown();
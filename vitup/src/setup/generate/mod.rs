@@ -1,9 +1,14 @@
+mod cert;
 mod committee;
 mod data;
 mod qr;
 mod snapshot;
 
+pub use cert::{self_signed_certificate, CertCommandArgs};
 pub use committee::CommitteeIdCommandArgs;
-pub use data::{read_config, DataCommandArgs, ExternalDataCommandArgs, RandomDataCommandArgs};
+pub use data::{
+    read_config, read_initials_from_snapshot, DataCommandArgs, ExternalDataCommandArgs,
+    RandomDataCommandArgs,
+};
 pub use qr::QrCommandArgs;
 pub use snapshot::SnapshotCommandArgs;
@@ -1,6 +1,7 @@
 use crate::config::Initials;
 use crate::setup::start::QuickVitBackendSettingsBuilder;
 use crate::Result;
+use iapyx::{QrCodeOptions, QrErrorCorrection};
 use jormungandr_scenario_tests::ProgressBarMode as ScenarioProgressBarMode;
 use jormungandr_scenario_tests::{Context, Seed};
 use jortestkit::prelude::read_file;
@@ -23,6 +24,15 @@ pub struct QrCommandArgs {
 
     #[structopt(long = "global-pin", default_value = "1234")]
     pub global_pin: String,
+
+    /// error correction level (low, medium, quartile, high). Higher level survives
+    /// poor print quality better, but produces denser code
+    #[structopt(long = "error-correction", default_value = "high")]
+    pub error_correction: QrErrorCorrection,
+
+    /// size of single qr code module in pixels
+    #[structopt(long = "module-size", default_value = "8")]
+    pub module_size: u32,
 }
 
 impl QrCommandArgs {
@@ -40,6 +50,10 @@ impl QrCommandArgs {
         );
 
         let mut quick_setup = QuickVitBackendSettingsBuilder::new();
+        quick_setup.qr_code_options(QrCodeOptions {
+            error_correction: self.error_correction,
+            module_size: self.module_size,
+        });
 
         if let Some(mapping) = self.initials_mapping {
             let content = read_file(mapping);
@@ -1,20 +1,24 @@
+pub mod block0;
 pub mod convert;
 pub mod diff;
 pub mod generate;
 pub mod start;
 pub mod validate;
+pub mod vote_key;
 
 use crate::error::Result;
 use crate::mock::MockStartCommandArgs;
-use crate::setup::generate::CommitteeIdCommandArgs;
+use crate::setup::generate::{CertCommandArgs, CommitteeIdCommandArgs};
 use crate::setup::generate::{QrCommandArgs, SnapshotCommandArgs};
 use crate::setup::start::AdvancedStartCommandArgs;
+use block0::Block0HashCommand;
 use convert::ConvertCommand;
 use diff::DiffCommand;
 use generate::DataCommandArgs;
 use start::QuickStartCommandArgs;
 use structopt::StructOpt;
 use validate::ValidateCommand;
+use vote_key::VoteAccountIdCommand;
 
 #[derive(StructOpt, Debug)]
 pub enum VitCliCommand {
@@ -28,6 +32,10 @@ pub enum VitCliCommand {
     Validate(ValidateCommand),
     // convert data
     Convert(ConvertCommand),
+    /// print block0 hash
+    Block0Hash(Block0HashCommand),
+    /// print voting account id of catalyst vote key
+    VoteAccountId(VoteAccountIdCommand),
 }
 
 impl VitCliCommand {
@@ -38,6 +46,8 @@ impl VitCliCommand {
             Self::Diff(diff_command) => diff_command.exec(),
             Self::Validate(validate_command) => validate_command.exec(),
             Self::Convert(convert_command) => convert_command.exec(),
+            Self::Block0Hash(block0_hash_command) => block0_hash_command.exec(),
+            Self::VoteAccountId(vote_account_id_command) => vote_account_id_command.exec(),
         }
     }
 }
@@ -72,6 +82,8 @@ pub enum GenerateCommand {
     Snapshot(SnapshotCommandArgs),
     /// Committee Id
     Committee(CommitteeIdCommandArgs),
+    /// self-signed certificate and key for local https testing
+    Cert(CertCommandArgs),
 }
 
 impl GenerateCommand {
@@ -81,6 +93,7 @@ impl GenerateCommand {
             Self::Data(data_start_command) => data_start_command.exec(),
             Self::Snapshot(snapshot_start_command) => snapshot_start_command.exec(),
             Self::Committee(generate_committee_command) => generate_committee_command.exec(),
+            Self::Cert(generate_cert_command) => generate_cert_command.exec(),
         }
     }
 }
@@ -1,11 +1,14 @@
+use crate::config::{validate_fees, Initials, MergeConflictPolicy};
 use crate::manager::ControlContext;
 use crate::manager::ManagerService;
 use crate::scenario::network::single_run;
 use crate::scenario::network::{endless_mode, interactive_mode, setup_network};
+use crate::scenario::ports::AUTO_ENDPOINT;
 use crate::setup::generate::read_config;
 use crate::setup::start::quick::parse_mode_from_str;
 use crate::setup::start::quick::Mode;
 use crate::setup::start::QuickVitBackendSettingsBuilder;
+use crate::setup::validate::validate_templates;
 use crate::Result;
 use jormungandr_scenario_tests::programs::prepare_command;
 use jormungandr_scenario_tests::{
@@ -50,7 +53,7 @@ pub struct AdvancedStartCommandArgs {
     #[structopt(long = "seed")]
     pub seed: Option<Seed>,
 
-    /// level for all nodes
+    /// level for all nodes. Particular nodes can be overridden with `log_levels` in config
     #[structopt(long = "log-level", default_value = "info")]
     pub log_level: String,
 
@@ -93,8 +96,14 @@ pub struct AdvancedStartCommandArgs {
     #[structopt(long = "funds", default_value = "../resources/external/funds.json")]
     pub funds: PathBuf,
 
+    /// snapshot json, which wallets are added to initials from config
     #[structopt(long = "snapshot")]
     pub snapshot: Option<PathBuf>,
+
+    /// stake of snapshot entries is divided by this value to get wallet funds
+    /// (e.g. 1000000 to convert lovelace to ada)
+    #[structopt(long = "snapshot-stake-divisor", default_value = "1")]
+    pub snapshot_stake_divisor: u64,
 }
 
 impl AdvancedStartCommandArgs {
@@ -129,6 +138,7 @@ impl AdvancedStartCommandArgs {
         );
 
         let config = read_config(&self.config)?;
+        validate_fees(&config.linear_fees, config.params.voting_power)?;
 
         println!("{:?}", config.params);
 
@@ -136,10 +146,32 @@ impl AdvancedStartCommandArgs {
         quick_setup.upload_parameters(config.params.clone());
         quick_setup.fees(config.linear_fees);
         quick_setup.set_external_committees(config.committees);
+        quick_setup.log_levels(config.log_levels);
+        if endpoint == AUTO_ENDPOINT {
+            quick_setup.use_free_ports();
+        }
+
+        if let Some(snapshot) = &self.snapshot {
+            let mut initials = config
+                .params
+                .initials
+                .clone()
+                .unwrap_or_else(|| Initials(Vec::new()));
+            let (snapshot_initials, report) =
+                Initials::from_snapshot_with_stake_divisor(snapshot, self.snapshot_stake_divisor)?;
+            for address in &report.zero_funds {
+                println!(
+                    "warning: snapshot entry '{}' has zero funds after dividing stake by {}",
+                    address, self.snapshot_stake_divisor
+                );
+            }
+            initials.merge(snapshot_initials, MergeConflictPolicy::Fail)?;
+            quick_setup.initials(initials);
+        }
 
+        validate_templates(&self.proposals, &self.challenges, &self.funds)?;
         let mut template_generator =
-            ExternalValidVotingTemplateGenerator::new(self.proposals, self.challenges, self.funds)
-                .unwrap();
+            ExternalValidVotingTemplateGenerator::new(self.proposals, self.challenges, self.funds)?;
 
         testing_directory.push(quick_setup.title());
         if testing_directory.exists() {
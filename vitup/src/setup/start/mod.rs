@@ -3,6 +3,6 @@ pub mod quick;
 
 pub use advanced::AdvancedStartCommandArgs;
 pub use quick::{
-    QuickStartCommandArgs, QuickVitBackendSettingsBuilder, LEADER_1, LEADER_2, LEADER_3, LEADER_4,
-    WALLET_NODE,
+    BuildReport, QuickStartCommandArgs, QuickVitBackendSettingsBuilder, LEADER_1, LEADER_2,
+    LEADER_3, LEADER_4, WALLET_NODE,
 };
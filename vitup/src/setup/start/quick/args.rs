@@ -1,12 +1,20 @@
 use super::mode::{parse_mode_from_str, Mode};
 use super::QuickVitBackendSettingsBuilder;
-use crate::config::Initials;
+use crate::config::{Initials, VitStartParameters, VoteTiming};
+use crate::scenario::controller::VitController;
 use crate::scenario::network::build_template_generator;
 use crate::scenario::network::service_mode;
 use crate::scenario::network::{endless_mode, interactive_mode, setup_network};
+use crate::scenario::ports::{BackendAddresses, AUTO_ENDPOINT};
+use crate::scenario::resume::{
+    deployment_files, pinned_parameters, DeploymentSnapshot, DeploymentState,
+};
+use crate::scenario::vit_station::StoragePolicy;
 use crate::Result;
-use iapyx::Protocol;
+use chain_addr::Discrimination;
+use iapyx::{Protocol, QrCodeOptions, QrErrorCorrection};
 use jormungandr_scenario_tests::programs::prepare_command;
+use jormungandr_scenario_tests::scenario::Controller;
 use jormungandr_scenario_tests::{
     parse_progress_bar_mode_from_str, Context, ProgressBarMode, Seed,
 };
@@ -96,6 +104,10 @@ pub struct QuickStartCommandArgs {
     #[structopt(long = "snapshot-timestamp")]
     pub snapshot_timestamp: Option<String>,
 
+    /// genesis date of block0 in format "%Y-%m-%d %H:%M:%S". Defaults to now
+    #[structopt(long = "block0-timestamp")]
+    pub block0_timestamp: Option<String>,
+
     /// slot duration
     #[structopt(long = "slot-duration", default_value = "20")]
     pub slot_duration: u8,
@@ -121,7 +133,8 @@ pub struct QuickStartCommandArgs {
     )]
     pub mode: Mode,
 
-    /// endopint in format: 127.0.0.1:80
+    /// endopint in format: 127.0.0.1:80. Use 'auto' to let OS pick free ports
+    /// for wallet proxy and vit station
     #[structopt(long = "endpoint", default_value = "0.0.0.0:80")]
     pub endpoint: String,
 
@@ -129,6 +142,21 @@ pub struct QuickStartCommandArgs {
     #[structopt(long = "private")]
     pub private: bool,
 
+    /// number of committee members, each with own wallet and private vote keys
+    #[structopt(long = "committee-members", default_value = "1")]
+    pub committee_members: usize,
+
+    /// number of committee members required to decrypt private tally.
+    /// By default all members are required. When defined, member keys are generated
+    /// by vitup and dumped to 'committee' directory
+    #[structopt(long = "committee-threshold")]
+    pub committee_threshold: Option<usize>,
+
+    /// hex encoded 32 bytes seed for deterministic committee keys of private voting.
+    /// For tests only: anyone who knows the seed can decrypt all votes
+    #[structopt(long = "committee-seed")]
+    pub committee_seed: Option<String>,
+
     /// switch to private voting type
     #[structopt(long = "version")]
     pub version: String,
@@ -140,9 +168,32 @@ pub struct QuickStartCommandArgs {
     #[structopt(long = "ideascale")]
     pub ideascale: bool,
 
+    /// use test address discrimination (`ta` prefix) instead of production one (`ca` prefix)
+    #[structopt(long = "testing-discrimination")]
+    pub testing_discrimination: bool,
+
     /// token, only applicable if service mode is used
     #[structopt(long = "token")]
     pub token: Option<String>,
+
+    /// resume deployment already generated in root-dir (block0, vit station database, qr codes)
+    /// instead of generating new one. Integrity of deployment files is verified before spawning.
+    /// Not applicable if service mode is used
+    #[structopt(long = "resume")]
+    pub resume: bool,
+
+    /// write nodes topology (trusted peers) as graphviz DOT file during setup
+    #[structopt(long = "topology-dot")]
+    pub topology_dot: Option<PathBuf>,
+
+    /// error correction level of qr codes (low, medium, quartile, high). Higher level
+    /// survives poor print quality better, but produces denser code
+    #[structopt(long = "qr-error-correction", default_value = "high")]
+    pub qr_error_correction: QrErrorCorrection,
+
+    /// size of single qr code module in pixels
+    #[structopt(long = "qr-module-size", default_value = "8")]
+    pub qr_module_size: u32,
 }
 
 impl QuickStartCommandArgs {
@@ -152,7 +203,7 @@ impl QuickStartCommandArgs {
         let jormungandr = prepare_command(&self.jormungandr);
         let jcli = prepare_command(&self.jcli);
         let mut progress_bar_mode = self.progress_bar_mode;
-        let seed = self
+        let mut seed = self
             .seed
             .unwrap_or_else(|| Seed::generate(rand::rngs::OsRng));
         let mut testing_directory = self.testing_directory;
@@ -162,20 +213,15 @@ impl QuickStartCommandArgs {
         let endpoint = self.endpoint;
         let ideascale = self.ideascale;
         let token = self.token;
+        let resume = self.resume;
 
         if mode == Mode::Interactive {
             progress_bar_mode = ProgressBarMode::None;
         }
 
-        let context = Context::new(
-            seed,
-            jormungandr,
-            jcli,
-            Some(testing_directory.clone()),
-            generate_documentation,
-            progress_bar_mode,
-            log_level,
-        );
+        if resume && mode == Mode::Service {
+            bail!("resume is not supported in service mode");
+        }
 
         let mut quick_setup = QuickVitBackendSettingsBuilder::new();
 
@@ -195,6 +241,15 @@ impl QuickStartCommandArgs {
             });
         }
 
+        if self.testing_discrimination {
+            quick_setup.discrimination(Discrimination::Test);
+        }
+
+        quick_setup.qr_code_options(QrCodeOptions {
+            error_correction: self.qr_error_correction,
+            module_size: self.qr_module_size,
+        });
+
         let vote_timestamps = vec![
             self.vote_start_timestamp.clone(),
             self.tally_start_timestamp.clone(),
@@ -207,9 +262,11 @@ impl QuickStartCommandArgs {
         }
 
         quick_setup
-            .vote_start_epoch(self.vote_start_epoch)
-            .tally_start_epoch(self.tally_start_epoch)
-            .tally_end_epoch(self.tally_end_epoch)
+            .vote_timing(VoteTiming::new(
+                self.vote_start_epoch,
+                self.tally_start_epoch,
+                self.tally_end_epoch,
+            ))
             .vote_start_timestamp(self.vote_start_timestamp)
             .tally_start_timestamp(self.tally_start_timestamp)
             .tally_end_timestamp(self.tally_end_timestamp)
@@ -220,16 +277,54 @@ impl QuickStartCommandArgs {
             .proposals_count(self.proposals)
             .voting_power(self.voting_power)
             .private(self.private)
+            .committee_members(self.committee_members)
+            .committee_threshold(self.committee_threshold)
+            .committee_seed(self.committee_seed)
             .version(self.version);
+        quick_setup.block0_date(self.block0_timestamp)?;
+
+        if endpoint == AUTO_ENDPOINT {
+            quick_setup.use_free_ports();
+        }
+
+        testing_directory.push(quick_setup.title());
+
+        let deployment = if resume {
+            println!("verifying deployment in {:?}..", testing_directory);
+            let state = DeploymentState::load(&testing_directory)?;
+            state.verify(&testing_directory)?;
+            seed = state.seed()?;
+            quick_setup.upload_parameters(state.parameters().clone());
+            Deployment::Resumed(DeploymentSnapshot::take(&testing_directory, &state)?)
+        } else {
+            if testing_directory.exists() {
+                std::fs::remove_dir_all(&testing_directory)?;
+            }
+            Deployment::New(DeploymentState::new(
+                &seed,
+                quick_setup.parameters().clone(),
+            ))
+        };
+
+        let context = Context::new(
+            seed,
+            jormungandr,
+            jcli,
+            testing_directory.parent().map(|x| x.to_path_buf()),
+            generate_documentation,
+            progress_bar_mode,
+            log_level,
+        );
 
         jormungandr_scenario_tests::introduction::print(&context, "VOTING BACKEND");
 
         let template_generator = Box::leak(build_template_generator(ideascale));
 
-        testing_directory.push(quick_setup.title());
-        if testing_directory.exists() {
-            std::fs::remove_dir_all(&testing_directory)?;
+        if let Some(topology_dot) = self.topology_dot {
+            std::fs::write(&topology_dot, quick_setup.topology_dot())?;
+            println!("topology dumped to {:?}", topology_dot);
         }
+
         match mode {
             Mode::Service => service_mode(
                 context,
@@ -242,7 +337,8 @@ impl QuickStartCommandArgs {
             Mode::Endless => {
                 let (mut vit_controller, mut controller, vit_parameters, version) =
                     quick_setup.build(context)?;
-                let (_nodes_list, _vit_station, _wallet_proxy) = setup_network(
+                deployment.before_spawn(&controller, &mut vit_controller)?;
+                let (_nodes_list, vit_station, wallet_proxy) = setup_network(
                     &mut controller,
                     &mut vit_controller,
                     vit_parameters,
@@ -251,11 +347,21 @@ impl QuickStartCommandArgs {
                     quick_setup.protocol(),
                     version,
                 )?;
+                deployment.after_spawn(
+                    &testing_directory,
+                    &controller,
+                    quick_setup.parameters(),
+                )?;
+                println!(
+                    "{}",
+                    BackendAddresses::new(&controller, &vit_station, &wallet_proxy)
+                );
                 endless_mode()?;
             }
             Mode::Interactive => {
                 let (mut vit_controller, mut controller, vit_parameters, version) =
                     quick_setup.build(context)?;
+                deployment.before_spawn(&controller, &mut vit_controller)?;
 
                 let (nodes_list, vit_station, wallet_proxy) = setup_network(
                     &mut controller,
@@ -266,9 +372,59 @@ impl QuickStartCommandArgs {
                     quick_setup.protocol(),
                     version,
                 )?;
+                deployment.after_spawn(
+                    &testing_directory,
+                    &controller,
+                    quick_setup.parameters(),
+                )?;
                 interactive_mode(controller, nodes_list, vit_station, wallet_proxy)?;
             }
         }
         Ok(())
     }
 }
+
+enum Deployment {
+    New(DeploymentState),
+    Resumed(DeploymentSnapshot),
+}
+
+impl Deployment {
+    /// Resumed deployment is rebuilt from saved seed and pinned parameters, so rebuilt
+    /// genesis must be identical to the saved one. Qr codes (encrypted with random nonce)
+    /// are brought back and vit station keeps its database
+    fn before_spawn(
+        &self,
+        controller: &Controller,
+        vit_controller: &mut VitController,
+    ) -> Result<()> {
+        match self {
+            Self::New(_) => Ok(()),
+            Self::Resumed(snapshot) => {
+                snapshot.verify_rebuilt(controller.block0_file())?;
+                snapshot.restore()?;
+                vit_controller.set_storage_policy(StoragePolicy::Reuse);
+                Ok(())
+            }
+        }
+    }
+
+    fn after_spawn(
+        self,
+        deployment_dir: &Path,
+        controller: &Controller,
+        parameters: &VitStartParameters,
+    ) -> Result<()> {
+        match self {
+            Self::New(mut state) => {
+                state.set_parameters(pinned_parameters(parameters, controller));
+                state.track(
+                    deployment_dir,
+                    deployment_files(deployment_dir, controller.block0_file()),
+                )?;
+                state.save(deployment_dir)
+            }
+            Self::Resumed(_) => Ok(()),
+        }
+    }
+}
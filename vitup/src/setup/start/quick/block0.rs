@@ -0,0 +1,36 @@
+use crate::Result;
+use chain_core::property::Serialize;
+use jormungandr_lib::crypto::hash::Hash;
+use jormungandr_lib::interfaces::Block0Configuration;
+use std::path::Path;
+
+/// Genesis block generated without spawning backend, together with configuration
+/// it was encoded from
+#[derive(Debug, Clone)]
+pub struct Block0Artifacts {
+    pub block0: Vec<u8>,
+    pub hash: Hash,
+    pub configuration: Block0Configuration,
+}
+
+impl Block0Artifacts {
+    pub fn new(configuration: Block0Configuration) -> Result<Self> {
+        let block = configuration.to_block();
+        Ok(Self {
+            block0: block.serialize_as_vec()?,
+            hash: block.header.hash().into(),
+            configuration,
+        })
+    }
+
+    /// Writes binary block0 to given path and genesis yaml next to it
+    pub fn write_to<P: AsRef<Path>>(&self, block0: P) -> Result<()> {
+        let block0 = block0.as_ref();
+        std::fs::write(block0, &self.block0)?;
+        std::fs::write(
+            block0.with_extension("yaml"),
+            serde_yaml::to_string(&self.configuration)?,
+        )?;
+        Ok(())
+    }
+}
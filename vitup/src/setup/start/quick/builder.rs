@@ -1,18 +1,20 @@
-use crate::config::VitStartParameters;
+use super::{Block0Artifacts, BuildReport};
+use crate::config::{override_wallet_parameters, validate_fees, VitStartParameters, VoteTiming};
+use crate::scenario::committee::{election_public_key, SeededCommittee};
 use crate::scenario::controller::VitController;
 use crate::scenario::controller::VitControllerBuilder;
-use crate::{config::Initials, Result};
+use crate::{config::Initials, error::ErrorKind, Result};
 use assert_fs::fixture::{ChildPath, PathChild};
-use chain_crypto::SecretKey;
+use chain_addr::Discrimination;
+use chain_crypto::{Ed25519Extended, SecretKey};
 use chain_impl_mockchain::testing::scenario::template::VotePlanDef;
 use chain_impl_mockchain::vote::PayloadType;
 use chain_impl_mockchain::{
     testing::scenario::template::{ProposalDefBuilder, VotePlanDefBuilder},
     value::Value,
 };
-use chain_vote::committee::ElectionPublicKey;
 use chrono::naive::NaiveDateTime;
-use iapyx::Protocol;
+use iapyx::{save_qr_code, Protocol, QrCodeOptions};
 use jormungandr_lib::interfaces::CommitteeIdDef;
 use jormungandr_lib::time::SecondsSinceUnixEpoch;
 use jormungandr_scenario_tests::scenario::settings::Settings;
@@ -22,8 +24,8 @@ use jormungandr_scenario_tests::scenario::{
 };
 use jormungandr_testing_utils::testing::network_builder::{Blockchain, Node, WalletTemplate};
 use jormungandr_testing_utils::wallet::LinearFee;
-use jormungandr_testing_utils::{qr_code::KeyQrCode, wallet::ElectionPublicKeyExtension};
-use std::{collections::HashMap, iter};
+use jormungandr_testing_utils::wallet::{ElectionPublicKeyExtension, Wallet};
+use std::{collections::HashMap, iter, sync::Arc};
 use vit_servicing_station_tests::common::data::ValidVotePlanParameters;
 
 pub const LEADER_1: &str = "Leader1";
@@ -31,6 +33,23 @@ pub const LEADER_2: &str = "Leader2";
 pub const LEADER_3: &str = "Leader3";
 pub const LEADER_4: &str = "Leader4";
 pub const WALLET_NODE: &str = "Wallet_Node";
+/// directory with member secret keys of committee generated by vitup
+pub const COMMITTEE_DIR: &str = "committee";
+
+/// Settings built by `QuickVitBackendSettingsBuilder::build_block0`, from which
+/// controllers can be built later
+pub struct PreparedBackend {
+    context: ContextChaCha,
+    builder: VitControllerBuilder,
+    templates: HashMap<WalletTemplate, String>,
+    block0: Block0Artifacts,
+}
+
+impl PreparedBackend {
+    pub fn block0(&self) -> &Block0Artifacts {
+        &self.block0
+    }
+}
 
 #[derive(Clone)]
 pub struct QuickVitBackendSettingsBuilder {
@@ -41,6 +60,10 @@ pub struct QuickVitBackendSettingsBuilder {
     title: String,
     //needed for load tests when we relay on secret keys instead of qrs
     skip_qr_generation: bool,
+    qr_code_options: QrCodeOptions,
+    free_ports: bool,
+    log_levels: HashMap<String, String>,
+    committee: Option<Arc<SeededCommittee>>,
 }
 
 impl Default for QuickVitBackendSettingsBuilder {
@@ -50,6 +73,7 @@ impl Default for QuickVitBackendSettingsBuilder {
 }
 
 const FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+const LOG_LEVELS: [&str; 7] = ["off", "critical", "error", "warn", "info", "debug", "trace"];
 
 impl QuickVitBackendSettingsBuilder {
     pub fn new() -> Self {
@@ -60,6 +84,10 @@ impl QuickVitBackendSettingsBuilder {
             fees: LinearFee::new(0, 0, 0),
             external_committees: Vec::new(),
             skip_qr_generation: false,
+            qr_code_options: Default::default(),
+            free_ports: false,
+            log_levels: HashMap::new(),
+            committee: None,
         }
     }
 
@@ -75,6 +103,53 @@ impl QuickVitBackendSettingsBuilder {
         self.skip_qr_generation = true;
     }
 
+    /// error correction level and module size of generated qr codes
+    pub fn qr_code_options(&mut self, qr_code_options: QrCodeOptions) -> &mut Self {
+        self.qr_code_options = qr_code_options;
+        self
+    }
+
+    /// log levels for particular nodes (alias -> level). Nodes without override
+    /// use log level from context
+    pub fn log_levels(&mut self, log_levels: HashMap<String, String>) -> &mut Self {
+        self.log_levels = log_levels;
+        self
+    }
+
+    fn validate_log_levels(&self) -> Result<()> {
+        let aliases: Vec<&str> = self
+            .topology_definition()
+            .into_iter()
+            .map(|(alias, _)| alias)
+            .collect();
+
+        for (alias, level) in &self.log_levels {
+            if !aliases.contains(&alias.as_str()) {
+                bail!(ErrorKind::InvalidLogLevel(
+                    alias.clone(),
+                    format!("unknown node, expected one of: {:?}", aliases)
+                ));
+            }
+            if !LOG_LEVELS.contains(&level.to_lowercase().as_str()) {
+                bail!(ErrorKind::InvalidLogLevel(
+                    alias.clone(),
+                    format!(
+                        "unknown level '{}', expected one of: {:?}",
+                        level, LOG_LEVELS
+                    )
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// vit station and wallet proxy will listen on ports allocated by OS, which allows
+    /// to run several backends in parallel. Nodes ports are allocated by context
+    pub fn use_free_ports(&mut self) -> &mut Self {
+        self.free_ports = true;
+        self
+    }
+
     pub fn parameters(&self) -> &VitStartParameters {
         &self.parameters
     }
@@ -84,6 +159,11 @@ impl QuickVitBackendSettingsBuilder {
         self
     }
 
+    pub fn discrimination(&mut self, discrimination: Discrimination) -> &mut Self {
+        self.parameters.discrimination = discrimination;
+        self
+    }
+
     pub fn protocol(&self) -> &Protocol {
         &self.parameters.protocol
     }
@@ -105,6 +185,13 @@ impl QuickVitBackendSettingsBuilder {
         self
     }
 
+    pub fn vote_timing(&mut self, vote_timing: VoteTiming) -> &mut Self {
+        self.parameters.vote_start = vote_timing.vote_start as u64;
+        self.parameters.vote_tally = vote_timing.tally_start as u64;
+        self.parameters.tally_end = vote_timing.tally_end as u64;
+        self
+    }
+
     pub fn vote_start_epoch(&mut self, vote_start_epoch: u32) -> &mut Self {
         self.parameters.vote_start = vote_start_epoch as u64;
         self
@@ -158,6 +245,22 @@ impl QuickVitBackendSettingsBuilder {
         self
     }
 
+    pub fn block0_date(&mut self, block0_date: Option<String>) -> Result<&mut Self> {
+        if let Some(timestamp) = block0_date {
+            let date = NaiveDateTime::parse_from_str(&timestamp, FORMAT)
+                .map_err(|e| ErrorKind::InvalidBlock0Date(timestamp.clone(), e.to_string()))?;
+            self.parameters.block0_date = Some(date);
+        }
+        Ok(self)
+    }
+
+    /// Overrides arbitrary fields of generated blockchain configuration. Overrides are
+    /// applied last, so they take precedence over all parameters set by other methods
+    pub fn blockchain_override(&mut self, blockchain_override: serde_json::Value) -> &mut Self {
+        self.parameters.blockchain_override = Some(blockchain_override);
+        self
+    }
+
     pub fn vote_start_timestamp(&mut self, vote_start_timestamp: Option<String>) -> &mut Self {
         if let Some(timestamp) = vote_start_timestamp {
             self.parameters.vote_start_timestamp =
@@ -191,6 +294,71 @@ impl QuickVitBackendSettingsBuilder {
         self
     }
 
+    pub fn committee_members(&mut self, committee_members: usize) -> &mut Self {
+        self.parameters.committee_members = committee_members;
+        self
+    }
+
+    pub fn committee_threshold(&mut self, committee_threshold: Option<usize>) -> &mut Self {
+        self.parameters.committee_threshold = committee_threshold;
+        self
+    }
+
+    /// Derives committee member keys from given seed instead of generating them randomly.
+    /// Test only, see `SeededCommittee`
+    pub fn committee_seed(&mut self, committee_seed: Option<String>) -> &mut Self {
+        self.parameters.committee_seed = committee_seed;
+        self
+    }
+
+    /// Committee of private vote plans generated by vitup: keys derived from committee seed
+    /// or, if only threshold is defined, random keys with that threshold. Otherwise private
+    /// vote plans use committee keys generated by jormungandr settings
+    fn generate_committee(&self) -> Result<Option<SeededCommittee>> {
+        if !self.parameters.private {
+            return Ok(None);
+        }
+        let members = self.parameters.committee_members;
+        let threshold = self.parameters.committee_threshold();
+        match (
+            &self.parameters.committee_seed,
+            self.parameters.committee_threshold,
+        ) {
+            (Some(seed), _) => Ok(Some(SeededCommittee::new(seed, members, threshold)?)),
+            (None, Some(_)) => Ok(Some(SeededCommittee::random(members, threshold))),
+            (None, None) => Ok(None),
+        }
+    }
+
+    /// Committee generated by last build, see `generate_committee`
+    pub fn committee(&self) -> Option<&SeededCommittee> {
+        self.committee.as_deref()
+    }
+
+    /// Aliases of committee wallets: committee_1 .. committee_n. First one owns vote plans
+    pub fn committee_wallets(&self) -> Vec<String> {
+        (1..=self.parameters.committee_members)
+            .map(|index| format!("committee_{}", index))
+            .collect()
+    }
+
+    fn validate_committee(&self) -> Result<()> {
+        let members = self.parameters.committee_members;
+        let threshold = self.parameters.committee_threshold();
+        if members == 0 {
+            bail!(ErrorKind::InvalidCommittee(
+                "at least one committee member is required".to_string()
+            ));
+        }
+        if threshold == 0 || threshold > members {
+            bail!(ErrorKind::InvalidCommittee(format!(
+                "threshold {} should be in range 1..={}",
+                threshold, members
+            )));
+        }
+        Ok(())
+    }
+
     pub fn recalculate_voting_periods_if_needed(&mut self, block0_date: SecondsSinceUnixEpoch) {
         let epoch_duration: u64 =
             self.parameters.slot_duration as u64 * self.parameters.slots_per_epoch as u64;
@@ -226,6 +394,39 @@ impl QuickVitBackendSettingsBuilder {
         }
     }
 
+    /// Structured summary of backend built by this builder with given controller
+    pub fn report(&self, controller: &Controller) -> BuildReport {
+        let initials = self.parameters.initials.as_ref();
+        let block0 = controller.block0_file();
+        let qr_codes = if self.skip_qr_generation {
+            None
+        } else {
+            block0.parent().map(|dir| dir.join("qr-codes"))
+        };
+        BuildReport {
+            title: self.title(),
+            fund_id: self.parameters.fund_id,
+            wallets: initials.map(Initials::wallet_count).unwrap_or(0),
+            zero_funds_wallets: initials.map(Initials::zero_funds_count).unwrap_or(0),
+            committee_members: self.parameters.committee_members,
+            proposals: self.parameters.proposals,
+            challenges: self.parameters.challenges,
+            vote_plans: controller.vote_plans().iter().map(|x| x.id()).collect(),
+            vote_start_timestamp: self.parameters.vote_start_timestamp,
+            tally_start_timestamp: self.parameters.tally_start_timestamp,
+            tally_end_timestamp: self.parameters.tally_end_timestamp,
+            next_vote_start_time: self.parameters.next_vote_start_time,
+            refresh_time: self.parameters.refresh_time,
+            block0,
+            genesis: None,
+            qr_codes,
+        }
+    }
+
+    pub fn print_report(&self, controller: &Controller) {
+        println!("{}", self.report(controller));
+    }
+
     pub fn upload_parameters(&mut self, parameters: VitStartParameters) {
         self.parameters = parameters;
     }
@@ -234,7 +435,7 @@ impl QuickVitBackendSettingsBuilder {
         &self,
         vote_plans: Vec<VotePlanDef>,
         settings: &Settings,
-    ) -> ValidVotePlanParameters {
+    ) -> Result<ValidVotePlanParameters> {
         let mut parameters = ValidVotePlanParameters::new(vote_plans, self.fund_name());
         parameters.set_voting_power_threshold((self.parameters.voting_power * 1_000_000) as i64);
         parameters.set_challenges_count(self.parameters.challenges);
@@ -253,52 +454,74 @@ impl QuickVitBackendSettingsBuilder {
         parameters.calculate_challenges_total_funds = false;
 
         if self.parameters.private {
-            let private_key_data = settings.private_vote_plans.get(&self.fund_name()).unwrap();
-            let key: ElectionPublicKey = private_key_data.encrypting_vote_key();
-            parameters.set_vote_encryption_key(key.to_base32().unwrap());
+            let fund_name = self.fund_name();
+            if !has_committee_wallet(settings, &self.committe_wallet) {
+                bail!(ErrorKind::CommitteeWalletNotFound(
+                    self.committe_wallet.clone()
+                ));
+            }
+            let key = match self.committee() {
+                Some(committee) => committee.election_public_key(),
+                None => election_public_key(settings, &fund_name)?,
+            };
+            let encoded_key = key
+                .to_base32()
+                .map_err(|err| ErrorKind::ElectionKeyEncodingFailed(fund_name, err.to_string()))?;
+            parameters.set_vote_encryption_key(encoded_key);
         }
-        parameters
+        Ok(parameters)
+    }
+
+    /// Nodes aliases with their trusted peers. Leaders are bootstrapped one after another,
+    /// each trusting all previously started ones, while wallet node trusts all leaders
+    fn topology_definition(&self) -> Vec<(&'static str, Vec<&'static str>)> {
+        vec![
+            (LEADER_1, vec![]),
+            (LEADER_2, vec![LEADER_1]),
+            (LEADER_3, vec![LEADER_1, LEADER_2]),
+            (LEADER_4, vec![LEADER_1, LEADER_2, LEADER_3]),
+            (WALLET_NODE, vec![LEADER_1, LEADER_2, LEADER_3, LEADER_4]),
+        ]
     }
 
     pub fn build_topology(&mut self) -> Topology {
         let mut topology_builder = TopologyBuilder::new();
 
-        // Leader 1
-        let leader_1 = Node::new(LEADER_1);
-        topology_builder.register_node(leader_1);
-
-        // leader 2
-        let mut leader_2 = Node::new(LEADER_2);
-        leader_2.add_trusted_peer(LEADER_1);
-        topology_builder.register_node(leader_2);
-
-        // leader 3
-        let mut leader_3 = Node::new(LEADER_3);
-        leader_3.add_trusted_peer(LEADER_1);
-        leader_3.add_trusted_peer(LEADER_2);
-        topology_builder.register_node(leader_3);
-
-        // leader 4
-        let mut leader_4 = Node::new(LEADER_4);
-        leader_4.add_trusted_peer(LEADER_1);
-        leader_4.add_trusted_peer(LEADER_2);
-        leader_4.add_trusted_peer(LEADER_3);
-        topology_builder.register_node(leader_4);
-
-        // passive
-        let mut passive = Node::new(WALLET_NODE);
-        passive.add_trusted_peer(LEADER_1);
-        passive.add_trusted_peer(LEADER_2);
-        passive.add_trusted_peer(LEADER_3);
-        passive.add_trusted_peer(LEADER_4);
-
-        topology_builder.register_node(passive);
+        for (alias, trusted_peers) in self.topology_definition() {
+            let mut node = Node::new(alias);
+            for trusted_peer in trusted_peers {
+                node.add_trusted_peer(trusted_peer);
+            }
+            topology_builder.register_node(node);
+        }
 
         topology_builder.build()
     }
 
-    pub fn build_vote_plans(&mut self) -> Vec<VotePlanDef> {
-        iter::from_fn(|| {
+    /// Graphviz DOT representation of topology. Edges point from node to its trusted peer
+    pub fn topology_dot(&self) -> String {
+        let mut dot = format!("digraph \"{}\" {{\n", self.title);
+        for (alias, trusted_peers) in self.topology_definition() {
+            let shape = if alias == WALLET_NODE {
+                "ellipse"
+            } else {
+                "box"
+            };
+            dot.push_str(&format!("    \"{}\" [shape={}];\n", alias, shape));
+            for trusted_peer in trusted_peers {
+                dot.push_str(&format!("    \"{}\" -> \"{}\";\n", alias, trusted_peer));
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    pub fn build_vote_plans(&mut self) -> Result<Vec<VotePlanDef>> {
+        let committee_keys = self
+            .committee()
+            .map(|committee| committee.member_public_keys());
+
+        Ok(iter::from_fn(|| {
             let mut proposal_builder = ProposalDefBuilder::new(
                 chain_impl_mockchain::testing::VoteTestGen::external_proposal_id(),
             );
@@ -325,6 +548,9 @@ impl QuickVitBackendSettingsBuilder {
 
             if self.parameters.private {
                 vote_plan_builder.payload_type(PayloadType::Private);
+                if let Some(committee_keys) = &committee_keys {
+                    vote_plan_builder.committee_keys(committee_keys.clone());
+                }
             }
             vote_plan_builder.vote_phases(
                 self.parameters.vote_start as u32,
@@ -336,7 +562,7 @@ impl QuickVitBackendSettingsBuilder {
             });
             vote_plan_builder.build()
         })
-        .collect()
+        .collect())
     }
 
     pub fn dump_qrs(
@@ -370,7 +596,17 @@ impl QuickVitBackendSettingsBuilder {
                 .unwrap();
             let png = folder.child(format!("{}_{}.png", alias, pin));
             println!("[{}/{}] Qr dumped to {:?}", idx + 1, total, png.path());
-            wallet.save_qr_code(png.path(), &pin_to_bytes(&pin));
+            if self.qr_code_options == QrCodeOptions::default() {
+                wallet.save_qr_code(png.path(), &pin_to_bytes(&pin));
+            } else {
+                save_qr_code(
+                    &wallet_secret_key(&wallet),
+                    &pin_to_bytes(&pin),
+                    self.qr_code_options,
+                    png.path(),
+                )
+                .map_err(|err| ErrorKind::QrCodeGenerationFailed(err.to_string()))?;
+            }
         }
 
         if let Some(initials) = &self.parameters.initials {
@@ -381,21 +617,123 @@ impl QuickVitBackendSettingsBuilder {
 
                 for i in 1..zero_funds_initial_counts + 1 {
                     let sk = SecretKey::generate(rand::thread_rng());
-                    let qr = KeyQrCode::generate(sk.clone(), &pin_to_bytes(&zero_funds_pin));
-                    let img = qr.to_img();
                     let png = folder.child(format!("zero_funds_{}_{}.png", i, zero_funds_pin));
-                    img.save(png.path())?;
+                    save_qr_code(
+                        &sk,
+                        &pin_to_bytes(&zero_funds_pin),
+                        self.qr_code_options,
+                        png.path(),
+                    )
+                    .map_err(|err| ErrorKind::QrCodeGenerationFailed(err.to_string()))?;
                 }
             }
         }
         Ok(())
     }
 
+    /// Generates genesis block without creating controllers or starting any process.
+    /// Useful for distributing block0 ahead of deployment (e.g. to proxy `--block0`).
+    /// Returned backend keeps settings (keys, block0 date) block0 was built from, so
+    /// controllers built by `build_prepared` serve exactly the same genesis
+    pub fn build_block0(&mut self, mut context: ContextChaCha) -> Result<PreparedBackend> {
+        let (mut builder, templates) = self.build_settings(&mut context)?;
+        let block0 = Block0Artifacts::new(builder.block0_configuration())?;
+        Ok(PreparedBackend {
+            context,
+            builder,
+            templates,
+            block0,
+        })
+    }
+
     pub fn build(
         &mut self,
-        mut context: ContextChaCha,
+        context: ContextChaCha,
+    ) -> Result<(VitController, Controller, ValidVotePlanParameters, String)> {
+        let prepared = self.build_block0(context)?;
+        self.build_prepared(prepared)
+    }
+
+    /// Builds controllers from settings prepared by `build_block0`
+    pub fn build_prepared(
+        &mut self,
+        prepared: PreparedBackend,
     ) -> Result<(VitController, Controller, ValidVotePlanParameters, String)> {
+        let PreparedBackend {
+            context,
+            builder,
+            templates,
+            ..
+        } = prepared;
+        let child = context.child_directory(self.title());
+
+        println!("building controllers..");
+
+        let (vit_controller, controller) = builder.build_controllers(context)?;
+
+        if !self.skip_qr_generation {
+            self.dump_qrs(&controller, &templates, &child)?;
+        }
+
+        println!("dumping secret keys..");
+
+        // keys generated by settings are not used by vote plans when committee comes
+        // from vitup, so dumping them would only mislead
+        match self.committee() {
+            Some(committee) => committee.write_to(child.child(COMMITTEE_DIR).path())?,
+            None => controller.settings().dump_private_vote_keys(child),
+        }
+
+        println!("adjusting vote plan timing..");
+
+        self.recalculate_voting_periods_if_needed(
+            controller
+                .settings()
+                .network_settings
+                .block0
+                .blockchain_configuration
+                .block0_date,
+        );
+
+        let parameters =
+            self.vote_plan_parameters(controller.vote_plans(), &controller.settings())?;
+        Ok((
+            vit_controller,
+            controller,
+            parameters,
+            self.parameters.version.clone(),
+        ))
+    }
+
+    /// Validates parameters and builds blockchain settings (including block0 configuration)
+    /// together with wallet templates and their pins
+    fn build_settings(
+        &mut self,
+        context: &mut ContextChaCha,
+    ) -> Result<(VitControllerBuilder, HashMap<WalletTemplate, String>)> {
+        let (discrimination, fees) = match &self.parameters.blockchain_override {
+            Some(overrides) => {
+                override_wallet_parameters(self.parameters.discrimination, self.fees, overrides)?
+            }
+            None => (self.parameters.discrimination, self.fees),
+        };
+        validate_fees(&fees, self.parameters.voting_power)?;
+        self.parameters.vote_timing()?.validate()?;
+        self.validate_log_levels()?;
+        self.validate_committee()?;
+        self.committee = self.generate_committee()?.map(Arc::new);
+        if let Some(initials) = &self.parameters.initials {
+            initials.validate_addresses(discrimination)?;
+        }
+
         let mut builder = VitControllerBuilder::new(&self.title);
+        if self.free_ports {
+            builder.use_free_ports();
+        }
+        builder.set_log_levels(self.log_levels.clone());
+        if let Some(blockchain_override) = &self.parameters.blockchain_override {
+            builder.set_blockchain_override(blockchain_override.clone());
+        }
 
         println!("building blockchain parameters..");
 
@@ -418,28 +756,41 @@ impl QuickVitBackendSettingsBuilder {
         blockchain.add_leader(LEADER_2);
         blockchain.add_leader(LEADER_3);
         blockchain.add_leader(LEADER_4);
-        blockchain.set_linear_fee(self.fees);
-        blockchain.set_discrimination(chain_addr::Discrimination::Production);
+        blockchain.set_linear_fee(fees);
+        blockchain.set_discrimination(discrimination);
+        if let Some(block0_date) = self.parameters.block0_date {
+            blockchain.set_block0_date(SecondsSinceUnixEpoch::from_secs(
+                block0_date.timestamp() as u64
+            ));
+        }
 
         if !self.external_committees.is_empty() {
             blockchain.set_external_committees(self.external_committees.clone());
         }
 
-        let committe_wallet = WalletTemplate::new_account(
-            self.committe_wallet.clone(),
-            Value(1_000_000_000),
-            blockchain.discrimination(),
-        );
-        blockchain.add_wallet(committe_wallet);
-        blockchain.add_committee(self.committe_wallet.clone());
-
-        let child = context.child_directory(self.title());
+        for committee_wallet in self.committee_wallets() {
+            let committee_template = WalletTemplate::new_account(
+                committee_wallet.clone(),
+                Value(1_000_000_000),
+                blockchain.discrimination(),
+            );
+            blockchain.add_wallet(committee_template);
+            blockchain.add_committee(committee_wallet);
+        }
 
-        println!("building initials..");
+        println!(
+            "building initials ({} wallets)..",
+            self.parameters
+                .initials
+                .as_ref()
+                .map(Initials::wallet_count)
+                .unwrap_or(0)
+        );
 
         let mut templates = HashMap::new();
         if let Some(initials) = &self.parameters.initials {
-            blockchain.set_external_wallets(initials.external_templates());
+            blockchain
+                .set_external_wallets(initials.external_templates(blockchain.discrimination())?);
             templates =
                 initials.templates(self.parameters.voting_power, blockchain.discrimination());
             for (wallet, _) in templates.iter().filter(|(x, _)| *x.value() > Value::zero()) {
@@ -448,45 +799,67 @@ impl QuickVitBackendSettingsBuilder {
         }
         println!("building voteplan..");
 
-        self.build_vote_plans()
+        self.build_vote_plans()?
             .into_iter()
             .for_each(|vote_plan_def| blockchain.add_vote_plan(vote_plan_def));
         builder.set_blockchain(blockchain);
-        builder.build_settings(&mut context);
-
-        println!("building controllers..");
-
-        let (vit_controller, controller) = builder.build_controllers(context)?;
-
-        if !self.skip_qr_generation {
-            self.dump_qrs(&controller, &templates, &child)?;
-        }
-
-        println!("dumping secret keys..");
-
-        controller.settings().dump_private_vote_keys(child);
-
-        println!("adjusting vote plan timing..");
+        builder.build_settings(context)?;
+        Ok((builder, templates))
+    }
+}
 
-        self.recalculate_voting_periods_if_needed(
-            controller
-                .settings()
-                .network_settings
-                .block0
-                .blockchain_configuration
-                .block0_date,
-        );
+fn has_committee_wallet(settings: &Settings, committee_wallet: &str) -> bool {
+    settings
+        .network_settings
+        .wallets
+        .contains_key(committee_wallet)
+}
 
-        let parameters = self.vote_plan_parameters(controller.vote_plans(), &controller.settings());
-        Ok((
-            vit_controller,
-            controller,
-            parameters,
-            self.parameters.version.clone(),
-        ))
+fn wallet_secret_key(wallet: &Wallet) -> SecretKey<Ed25519Extended> {
+    match wallet {
+        Wallet::Account(account) => account.signing_key().as_ref().clone(),
+        Wallet::UTxO(utxo) => utxo.last_signing_key().as_ref().clone(),
+        Wallet::Delegation(delegation) => delegation.last_signing_key().as_ref().clone(),
     }
 }
 
 pub fn pin_to_bytes(pin: &str) -> Vec<u8> {
     pin.chars().map(|x| x.to_digit(10).unwrap() as u8).collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::TempDir;
+    use jormungandr_scenario_tests::programs::prepare_command;
+    use jormungandr_scenario_tests::{Context, ProgressBarMode};
+    use jormungandr_testing_utils::testing::network_builder::Seed;
+    use std::path::PathBuf;
+
+    #[test]
+    fn spawned_genesis_matches_prepared_block0() {
+        let testing_directory = TempDir::new().unwrap();
+        let context = Context::new(
+            Seed::generate(rand::rngs::OsRng),
+            prepare_command(PathBuf::from("jormungandr")),
+            prepare_command(PathBuf::from("jcli")),
+            Some(testing_directory.path().to_path_buf()),
+            false,
+            ProgressBarMode::None,
+            "info".to_string(),
+        );
+
+        let mut quick_setup = QuickVitBackendSettingsBuilder::new();
+        let prepared = quick_setup.build_block0(context).unwrap();
+        let block0 = prepared.block0().clone();
+        let (_, controller, _, _) = quick_setup.build_prepared(prepared).unwrap();
+
+        let spawned =
+            Block0Artifacts::new(controller.settings().network_settings.block0.clone()).unwrap();
+        assert_eq!(spawned.hash, block0.hash);
+        assert_eq!(
+            std::fs::read(controller.block0_file()).unwrap(),
+            block0.block0
+        );
+    }
+}
@@ -1,9 +1,14 @@
 mod args;
+mod block0;
 mod builder;
 mod mode;
+mod report;
 
 pub use args::QuickStartCommandArgs;
+pub use block0::Block0Artifacts;
 pub use builder::{
-    QuickVitBackendSettingsBuilder, LEADER_1, LEADER_2, LEADER_3, LEADER_4, WALLET_NODE,
+    PreparedBackend, QuickVitBackendSettingsBuilder, LEADER_1, LEADER_2, LEADER_3, LEADER_4,
+    WALLET_NODE,
 };
 pub use mode::{parse_mode_from_str, Mode};
+pub use report::BuildReport;
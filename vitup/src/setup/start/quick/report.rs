@@ -0,0 +1,55 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::PathBuf;
+
+/// Summary of generated backend: wallets, vote plans, timing and produced files
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BuildReport {
+    pub title: String,
+    pub fund_id: i32,
+    pub wallets: usize,
+    pub zero_funds_wallets: usize,
+    pub committee_members: usize,
+    pub proposals: u32,
+    pub challenges: usize,
+    pub vote_plans: Vec<String>,
+    pub vote_start_timestamp: Option<NaiveDateTime>,
+    pub tally_start_timestamp: Option<NaiveDateTime>,
+    pub tally_end_timestamp: Option<NaiveDateTime>,
+    pub next_vote_start_time: Option<NaiveDateTime>,
+    pub refresh_time: Option<NaiveDateTime>,
+    pub block0: PathBuf,
+    pub genesis: Option<PathBuf>,
+    pub qr_codes: Option<PathBuf>,
+}
+
+impl fmt::Display for BuildReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(genesis) = &self.genesis {
+            writeln!(f, "genesis.yaml: {:?}", genesis)?;
+        }
+        writeln!(f, "block0: {:?}", self.block0)?;
+        if let Some(qr_codes) = &self.qr_codes {
+            writeln!(f, "qr codes: {:?}", qr_codes)?;
+        }
+        writeln!(f, "Fund id: {}", self.fund_id)?;
+        writeln!(
+            f,
+            "wallets: {} (zero funds: {})",
+            self.wallets, self.zero_funds_wallets
+        )?;
+        writeln!(f, "committee members: {}", self.committee_members)?;
+        writeln!(
+            f,
+            "proposals: {}, challenges: {}",
+            self.proposals, self.challenges
+        )?;
+        writeln!(f, "voteplan ids: {:?}", self.vote_plans)?;
+        writeln!(f, "vote start timestamp: {:?}", self.vote_start_timestamp)?;
+        writeln!(f, "tally start timestamp: {:?}", self.tally_start_timestamp)?;
+        writeln!(f, "tally end timestamp: {:?}", self.tally_end_timestamp)?;
+        writeln!(f, "next vote start time: {:?}", self.next_vote_start_time)?;
+        write!(f, "refresh timestamp: {:?}", self.refresh_time)
+    }
+}
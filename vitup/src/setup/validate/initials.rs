@@ -0,0 +1,28 @@
+use crate::config::Initials;
+use crate::Result;
+use jortestkit::prelude::read_file;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+/// Validates initials file and prints how many wallets it would create
+#[derive(StructOpt, Debug)]
+#[structopt(setting = structopt::clap::AppSettings::ColoredHelp)]
+pub struct InitialsValidateCommand {
+    /// initials json
+    #[structopt(long = "initials")]
+    pub initials: PathBuf,
+}
+
+impl InitialsValidateCommand {
+    pub fn exec(self) -> Result<()> {
+        let initials: Initials = serde_json::from_str(&read_file(&self.initials))?;
+        println!("initials file: {:?}", self.initials);
+        println!("wallets: {}", initials.wallet_count());
+        println!("zero funds wallets: {}", initials.zero_funds_count());
+        println!(
+            "external wallets: {}",
+            initials.wallet_count() - initials.count()
+        );
+        Ok(())
+    }
+}
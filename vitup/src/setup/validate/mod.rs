@@ -1,19 +1,34 @@
 mod ideascale;
+mod initials;
+mod qr;
+mod template;
 
 use crate::Result;
 use ideascale::IdeascaleValidateCommand;
+use initials::InitialsValidateCommand;
+pub use qr::{verify_qr_codes, QrValidateCommand};
 use structopt::StructOpt;
+pub use template::{
+    validate_templates, CHALLENGE_REQUIRED_FIELDS, FUND_NUMERIC_FIELDS, FUND_REQUIRED_FIELDS,
+    PROPOSAL_REQUIRED_FIELDS,
+};
 
 #[derive(StructOpt, Debug)]
 #[structopt(setting = structopt::clap::AppSettings::ColoredHelp)]
 pub enum ValidateCommand {
     Ideascale(IdeascaleValidateCommand),
+    /// print number of wallets defined by initials
+    Initials(InitialsValidateCommand),
+    /// check that qr codes decode with their pins to wallets funded in block0
+    Qr(QrValidateCommand),
 }
 
 impl ValidateCommand {
     pub fn exec(self) -> Result<()> {
         match self {
             Self::Ideascale(ideascale) => ideascale.exec(),
+            Self::Initials(initials) => initials.exec(),
+            Self::Qr(qr) => qr.exec(),
         }
     }
 }
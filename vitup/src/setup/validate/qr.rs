@@ -0,0 +1,108 @@
+use crate::error::ErrorKind;
+use crate::Result;
+use chain_core::property::Deserialize;
+use chain_impl_mockchain::block::Block;
+use iapyx::{pin_to_bytes, Wallet};
+use jormungandr_lib::interfaces::{Block0Configuration, Initial};
+use jormungandr_testing_utils::qr_code::KeyQrCode;
+use std::collections::HashSet;
+use std::convert::TryInto;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use structopt::StructOpt;
+
+/// prefix of qr codes which are not funded in block0
+const ZERO_FUNDS_PREFIX: &str = "zero_funds_";
+
+/// Checks that each generated qr code decodes with its pin to wallet funded in block0
+#[derive(StructOpt, Debug)]
+#[structopt(setting = structopt::clap::AppSettings::ColoredHelp)]
+pub struct QrValidateCommand {
+    /// folder with qr codes named `{alias}_{pin}.png`
+    #[structopt(long = "qr-codes")]
+    pub qr_codes: PathBuf,
+
+    /// block0 binary
+    #[structopt(long = "block0")]
+    pub block0: PathBuf,
+}
+
+impl QrValidateCommand {
+    pub fn exec(self) -> Result<()> {
+        let verified = verify_qr_codes(&self.qr_codes, &self.block0)?;
+        println!("{} qr codes match block0 entries", verified);
+        Ok(())
+    }
+}
+
+/// Decodes every qr code in folder using pin from its file name, recovers wallet and
+/// checks that its address is funded in block0. Qr codes of zero funds wallets are only
+/// decoded. Returns number of verified qr codes
+pub fn verify_qr_codes<P: AsRef<Path>, Q: AsRef<Path>>(qr_codes: P, block0: Q) -> Result<usize> {
+    let reader = BufReader::new(std::fs::File::open(block0.as_ref())?);
+    let block = Block::deserialize(reader).map_err(|err| {
+        ErrorKind::QrCodeMismatch(
+            block0.as_ref().to_string_lossy().to_string(),
+            format!("cannot read block0: {}", err),
+        )
+    })?;
+    let genesis = Block0Configuration::from_block(&block).map_err(|err| {
+        ErrorKind::QrCodeMismatch(
+            block0.as_ref().to_string_lossy().to_string(),
+            format!("cannot read block0: {}", err),
+        )
+    })?;
+    let discrimination = genesis.blockchain_configuration.discrimination;
+
+    let funded: HashSet<chain_addr::Address> = genesis
+        .initial
+        .iter()
+        .filter_map(|initial| match initial {
+            Initial::Fund(utxos) => Some(utxos.iter().map(|utxo| utxo.address.clone().into())),
+            _ => None,
+        })
+        .flatten()
+        .collect();
+
+    let mut verified = 0;
+    for entry in std::fs::read_dir(qr_codes.as_ref())? {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("png") {
+            continue;
+        }
+        let mismatch = |reason: String| -> crate::error::Error {
+            ErrorKind::QrCodeMismatch(path.to_string_lossy().to_string(), reason).into()
+        };
+
+        let stem = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .ok_or_else(|| mismatch("cannot read file name".to_string()))?;
+        let pin = stem
+            .rsplitn(2, '_')
+            .next()
+            .filter(|pin| !pin.is_empty() && pin.chars().all(|c| c.is_ascii_digit()))
+            .ok_or_else(|| mismatch("expected file name in format {alias}_{pin}".to_string()))?;
+
+        let img = image::open(&path).map_err(|err| mismatch(err.to_string()))?;
+        let secret = KeyQrCode::decode(img, &pin_to_bytes(pin))
+            .map_err(|err| mismatch(format!("cannot decode with pin {}: {}", pin, err)))?
+            .get(0)
+            .cloned()
+            .ok_or_else(|| mismatch("qr code does not contain any key".to_string()))?;
+
+        if !stem.starts_with(ZERO_FUNDS_PREFIX) {
+            let bin: [u8; 64] = secret.leak_secret().as_ref().try_into().unwrap();
+            let wallet =
+                Wallet::recover_from_utxo(&bin).map_err(|err| mismatch(err.to_string()))?;
+            if !funded.contains(&wallet.account(discrimination)) {
+                return Err(mismatch(format!(
+                    "wallet {} is not funded in block0",
+                    wallet.identifier(discrimination)
+                )));
+            }
+        }
+        verified += 1;
+    }
+    Ok(verified)
+}
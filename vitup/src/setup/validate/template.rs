@@ -0,0 +1,207 @@
+use crate::error::ErrorKind;
+use crate::Result;
+use serde_json::Value;
+use std::path::Path;
+
+pub const PROPOSAL_REQUIRED_FIELDS: &[&str] = &[
+    "category_name",
+    "chain_vote_options",
+    "chain_vote_type",
+    "challenge_id",
+    "challenge_type",
+    "internal_id",
+    "proposal_funds",
+    "proposal_id",
+    "proposal_impact_score",
+    "proposal_summary",
+    "proposal_title",
+    "proposal_url",
+    "proposer_email",
+    "proposer_name",
+];
+
+pub const CHALLENGE_REQUIRED_FIELDS: &[&str] = &[
+    "challenge_type",
+    "challenge_url",
+    "description",
+    "fund_id",
+    "id",
+    "proposers_rewards",
+    "rewards_total",
+    "title",
+];
+
+pub const FUND_REQUIRED_FIELDS: &[&str] = &["goal", "id", "rewards_info", "threshold"];
+pub const FUND_NUMERIC_FIELDS: &[&str] = &["id", "threshold"];
+
+/// Checks that external proposals, challenges and funds files can be used for
+/// generating vit station data. Reports first problem found with file, entry and field name
+pub fn validate_templates<P: AsRef<Path>, Q: AsRef<Path>, R: AsRef<Path>>(
+    proposals: P,
+    challenges: Q,
+    funds: R,
+) -> Result<()> {
+    validate_file(proposals, PROPOSAL_REQUIRED_FIELDS, &[])?;
+    validate_file(challenges, CHALLENGE_REQUIRED_FIELDS, &[])?;
+    validate_file(funds, FUND_REQUIRED_FIELDS, FUND_NUMERIC_FIELDS)
+}
+
+fn validate_file<P: AsRef<Path>>(
+    path: P,
+    required_fields: &[&str],
+    numeric_fields: &[&str],
+) -> Result<()> {
+    let file = path.as_ref().to_string_lossy().to_string();
+    let content = std::fs::read_to_string(path.as_ref())?;
+    let value: Value = serde_json::from_str(&content)
+        .map_err(|e| ErrorKind::InvalidTemplate(file.clone(), 0, "".to_string(), e.to_string()))?;
+    validate_entries(&file, &value, required_fields, numeric_fields)
+}
+
+pub fn validate_entries(
+    file: &str,
+    value: &Value,
+    required_fields: &[&str],
+    numeric_fields: &[&str],
+) -> Result<()> {
+    let invalid = |entry: usize, field: &str, problem: &str| -> crate::error::Error {
+        ErrorKind::InvalidTemplate(
+            file.to_string(),
+            entry,
+            field.to_string(),
+            problem.to_string(),
+        )
+        .into()
+    };
+
+    let entries = value
+        .as_array()
+        .ok_or_else(|| invalid(0, "", "expected array of entries"))?;
+
+    for (idx, entry) in entries.iter().enumerate() {
+        let entry_no = idx + 1;
+        let object = entry
+            .as_object()
+            .ok_or_else(|| invalid(entry_no, "", "expected object"))?;
+
+        for field in required_fields {
+            match object.get(*field) {
+                None | Some(Value::Null) => {
+                    return Err(invalid(entry_no, field, "missing required field"))
+                }
+                Some(Value::Number(_)) if numeric_fields.contains(field) => {}
+                Some(_) if numeric_fields.contains(field) => {
+                    return Err(invalid(entry_no, field, "expected number"))
+                }
+                Some(Value::String(_)) => {}
+                Some(_) => return Err(invalid(entry_no, field, "expected string")),
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{
+        validate_entries, CHALLENGE_REQUIRED_FIELDS, FUND_NUMERIC_FIELDS, FUND_REQUIRED_FIELDS,
+        PROPOSAL_REQUIRED_FIELDS,
+    };
+    use crate::error::ErrorKind;
+    use serde_json::json;
+
+    fn assert_missing_field(result: crate::Result<()>, expected_field: &str) {
+        match result.unwrap_err().kind() {
+            ErrorKind::InvalidTemplate(_, entry, field, _) => {
+                assert_eq!(*entry, 1);
+                assert_eq!(field, expected_field);
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    pub fn test_proposal_missing_required_field() {
+        let proposals = json!([{
+            "category_name": "Fund4",
+            "chain_vote_options": "blank,yes,no",
+            "chain_vote_type": "public",
+            "challenge_id": "3",
+            "challenge_type": "simple",
+            "internal_id": "1",
+            "proposal_funds": "10000",
+            "proposal_impact_score": "320",
+            "proposal_summary": "summary",
+            "proposal_title": "Test Proposal",
+            "proposal_url": "http://ideascale.com/t/UM5UZBgzC",
+            "proposer_email": "proposer@example.com",
+            "proposer_name": "Proposer"
+        }]);
+
+        assert_missing_field(
+            validate_entries("proposals.json", &proposals, PROPOSAL_REQUIRED_FIELDS, &[]),
+            "proposal_id",
+        );
+    }
+
+    #[test]
+    pub fn test_challenge_missing_required_field() {
+        let challenges = json!([{
+            "challenge_type": "simple",
+            "challenge_url": "https://cardano.ideascale.com/a/campaign-home/25869",
+            "description": "description",
+            "fund_id": "4",
+            "id": "2",
+            "proposers_rewards": "0",
+            "rewards_total": "200000"
+        }]);
+
+        assert_missing_field(
+            validate_entries(
+                "challenges.json",
+                &challenges,
+                CHALLENGE_REQUIRED_FIELDS,
+                &[],
+            ),
+            "title",
+        );
+    }
+
+    #[test]
+    pub fn test_fund_missing_required_field() {
+        let funds = json!([{
+            "id": 4,
+            "goal": "Create, fund and deliver the future of Cardano.",
+            "rewards_info": "Create, fund and deliver the future of Cardano."
+        }]);
+
+        assert_missing_field(
+            validate_entries(
+                "funds.json",
+                &funds,
+                FUND_REQUIRED_FIELDS,
+                FUND_NUMERIC_FIELDS,
+            ),
+            "threshold",
+        );
+    }
+
+    #[test]
+    pub fn test_valid_fund() {
+        let funds = json!([{
+            "id": 4,
+            "goal": "Create, fund and deliver the future of Cardano.",
+            "rewards_info": "Create, fund and deliver the future of Cardano.",
+            "threshold": 450
+        }]);
+
+        assert!(validate_entries(
+            "funds.json",
+            &funds,
+            FUND_REQUIRED_FIELDS,
+            FUND_NUMERIC_FIELDS
+        )
+        .is_ok());
+    }
+}
@@ -0,0 +1,37 @@
+use crate::error::ErrorKind;
+use crate::Result;
+use iapyx::utils::vote_key::{vote_account_id, vote_account_id_from_file};
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+/// Prints voting account id derived from catalyst vote key, so registration
+/// can be correlated with entry in snapshot
+#[derive(StructOpt, Debug)]
+#[structopt(setting = structopt::clap::AppSettings::ColoredHelp)]
+pub struct VoteAccountIdCommand {
+    /// bech32 secret or public key (e.g. content of catalyst-vote.skey)
+    #[structopt(long = "key", conflicts_with = "key-file")]
+    pub key: Option<String>,
+
+    /// file with bech32 secret or public key (e.g. catalyst-vote.pkey)
+    #[structopt(long = "key-file")]
+    pub key_file: Option<PathBuf>,
+}
+
+impl VoteAccountIdCommand {
+    pub fn exec(self) -> Result<()> {
+        let account_id = match (self.key, self.key_file) {
+            (Some(key), _) => vote_account_id(&key),
+            (None, Some(key_file)) => vote_account_id_from_file(key_file),
+            (None, None) => {
+                return Err(ErrorKind::InvalidVoteKey(
+                    "no --key or --key-file provided".to_string(),
+                )
+                .into())
+            }
+        }
+        .map_err(|err| ErrorKind::InvalidVoteKey(err.to_string()))?;
+        println!("{}", account_id);
+        Ok(())
+    }
+}